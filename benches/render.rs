@@ -0,0 +1,111 @@
+//! 盤面の画像を描く時間と、描いている間に他の処理が待たされる時間
+//!
+//! ```text
+//! cargo bench --bench render
+//! ```
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use discord_reversibot_rust::quantum_gomoku::gen_image::{
+    draw_quantum_board_image, gen_quantum_board_image,
+};
+use discord_reversibot_rust::quantum_gomoku::gomoku::Stone;
+use discord_reversibot_rust::render::theme::BoardStyle;
+use tokio::runtime::{Builder, Runtime};
+
+/// 石がいくつか置かれた、呼ぶたびに違う盤面 (キャッシュに当たらないようにする)
+fn next_board() -> [[Stone; 19]; 19] {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+
+    let mut board = [[Stone::None; 19]; 19];
+    for i in 0..40 {
+        board[i / 15][i % 15] = match count >> i & 1 {
+            0 => Stone::Black90,
+            _ => Stone::White70,
+        };
+    }
+    board[7][7] = Stone::Black70;
+
+    return board;
+}
+
+/// BOTと同じく、手番の処理は1つのスレッドに詰め込まれていることもある
+fn runtime() -> Runtime {
+    Builder::new_current_thread().enable_all().build().unwrap()
+}
+
+fn render(c: &mut Criterion) {
+    let runtime = runtime();
+    let style = BoardStyle::default();
+    let board = next_board();
+
+    let mut group = c.benchmark_group("quantum_board");
+
+    group.bench_function("draw", |b| {
+        b.iter(|| draw_quantum_board_image(board, Some((7, 7)), style))
+    });
+
+    // 2回目からはキャッシュから返る (メッセージの更新など)
+    group.bench_function("cached", |b| {
+        b.to_async(&runtime)
+            .iter(|| gen_quantum_board_image(board, Some((7, 7)), style))
+    });
+
+    group.finish();
+}
+
+/// 描いている間に、ほかの試合の処理 (すぐ終わるタスク) が動き出すまでの時間
+fn tick_latency(c: &mut Criterion) {
+    let runtime = runtime();
+    let style = BoardStyle::default();
+
+    let mut group = c.benchmark_group("tick_while_rendering");
+
+    // 以前のように、非同期の処理の中でそのまま描く
+    group.bench_function("inline", |b| {
+        b.to_async(&runtime).iter_custom(|iters| async move {
+            let mut total = Duration::ZERO;
+
+            for _ in 0..iters {
+                let board = next_board();
+                let render =
+                    tokio::spawn(async move { draw_quantum_board_image(board, None, style) });
+
+                let start = Instant::now();
+                total += tokio::spawn(async move { start.elapsed() }).await.unwrap();
+                render.await.unwrap();
+            }
+
+            total
+        })
+    });
+
+    group.bench_function("spawn_blocking", |b| {
+        b.to_async(&runtime).iter_custom(|iters| async move {
+            let mut total = Duration::ZERO;
+
+            for _ in 0..iters {
+                let board = next_board();
+                let render = tokio::spawn(gen_quantum_board_image(board, None, style));
+
+                let start = Instant::now();
+                total += tokio::spawn(async move { start.elapsed() }).await.unwrap();
+                render.await.unwrap();
+            }
+
+            total
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(20);
+    targets = render, tick_latency
+}
+criterion_main!(benches);
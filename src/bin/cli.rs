@@ -0,0 +1,387 @@
+//! 端末でリバーシ・量子五目並べを遊ぶ (BOTのトークンなしでエンジンの変更を確かめる用)
+//!
+//! ```text
+//! cargo run --bin cli -- reversi [--size <N>] [--parallel] [--anti]
+//! cargo run --bin cli -- q_gomoku [--ai] [--png <DIR>] [--font <FILE>] [--theme <THEME>] [--labels]
+//! ```
+
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::process::exit;
+
+use discord_reversibot_rust::color::Color;
+use discord_reversibot_rust::coordinate::parse_coordinate;
+use discord_reversibot_rust::quantum_gomoku::ai;
+use discord_reversibot_rust::quantum_gomoku::gen_image::{
+    gen_observation_image, gen_quantum_board_image, gen_quantum_heatmap_image,
+};
+use discord_reversibot_rust::quantum_gomoku::gomoku::{ObservedStone, QuantumGomoku, Stone, Turn};
+use discord_reversibot_rust::render::font;
+use discord_reversibot_rust::render::theme::{BoardStyle, Theme};
+use discord_reversibot_rust::reversi::reversi::{self, Layout, Reversi, Rule};
+
+const USAGE: &str = "usage: cli reversi [--size <N>] [--parallel] [--anti]
+       cli q_gomoku [--ai] [--png <DIR>] [--font <FILE>] [--theme <THEME>] [--labels]
+
+  --size <N>       リバーシの盤の大きさ (6, 8, 10)
+  --parallel       リバーシの初期配置を平行にする
+  --anti           石が少ないほうが勝ちのリバーシにする
+  --ai             白をAIにする (量子五目並べのみ)
+  --png <DIR>      量子五目並べの盤面の画像を DIR に保存する
+  --font <FILE>    画像の文字に使うフォント (既定: font/font.ttf、なければビットマップ文字)
+  --theme <THEME>  画像のテーマ (wood, dark, high_contrast, colorblind)
+  --labels         画像の量子の石に確率を描く";
+
+const QUANTUM_GOMOKU_ALPHABETS: &str = "ABCDEFGHIJKLMNO";
+
+#[tokio::main]
+async fn main() {
+    let mut game = None;
+    let mut with_ai = false;
+    let mut png_dir = None;
+    let mut font_path = None;
+    let mut theme = None;
+    let mut stone_labels = false;
+    let mut size = None;
+    let mut layout = Layout::Cross;
+    let mut rule = Rule::Standard;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--ai" => with_ai = true,
+            "--png" => match args.next() {
+                Some(dir) => png_dir = Some(PathBuf::from(dir)),
+                None => usage(),
+            },
+            "--font" => match args.next() {
+                Some(file) => font_path = Some(PathBuf::from(file)),
+                None => usage(),
+            },
+            "--labels" => stone_labels = true,
+            "--size" => match args.next().and_then(|value| value.parse::<usize>().ok()) {
+                Some(value) if reversi::SIZES.contains(&value) => size = Some(value),
+                _ => usage(),
+            },
+            "--parallel" => layout = Layout::Parallel,
+            "--anti" => rule = Rule::Anti,
+            "--theme" => match args.next().as_deref().and_then(Theme::from_id) {
+                Some(value) => theme = Some(value),
+                None => usage(),
+            },
+            "reversi" | "q_gomoku" => game = Some(arg),
+            _ => usage(),
+        }
+    }
+
+    match game.as_deref() {
+        Some("reversi")
+            if with_ai
+                || png_dir.is_some()
+                || font_path.is_some()
+                || theme.is_some()
+                || stone_labels =>
+        {
+            eprintln!("リバーシでは --ai・--png・--font・--theme・--labels は使えません。");
+            exit(2);
+        }
+        Some("reversi") => play_reversi(size.unwrap_or(8), layout, rule),
+        Some("q_gomoku") if size.is_some() || layout != Layout::Cross || rule != Rule::Standard => {
+            eprintln!("量子五目並べでは --size・--parallel・--anti は使えません。");
+            exit(2);
+        }
+        Some("q_gomoku") => {
+            if png_dir.is_some() {
+                load_font(font_path);
+            }
+            let style = BoardStyle {
+                theme: theme.unwrap_or_default(),
+                stone_labels,
+            };
+            play_quantum_gomoku(with_ai, png_dir, style).await
+        }
+        _ => usage(),
+    }
+}
+
+fn usage() -> ! {
+    eprintln!("{}", USAGE);
+    exit(2);
+}
+
+/// 指定がなければ既定のフォントを探し、なくても黙ってビットマップ文字にする
+fn load_font(font_path: Option<PathBuf>) {
+    let path = font_path
+        .clone()
+        .unwrap_or_else(|| PathBuf::from("font/font.ttf"));
+
+    if let Err(why) = font::load_font(&path) {
+        if font_path.is_some() {
+            eprintln!("{} を読み込めませんでした: {}", path.display(), why);
+            exit(2);
+        }
+    }
+}
+
+/// 1行読む (EOF または "quit" なら None)
+fn read_line(prompt: &str) -> Option<String> {
+    print!("{}", prompt);
+    io::stdout().flush().ok()?;
+
+    let mut line = String::new();
+    if io::stdin().lock().read_line(&mut line).ok()? == 0 {
+        return None;
+    }
+
+    let line = line.trim().to_string();
+    if line == "quit" {
+        return None;
+    }
+
+    return Some(line);
+}
+
+fn play_reversi(size: usize, layout: Layout, rule: Rule) {
+    let mut reversi = Reversi::with_layout(size, layout).with_rule(rule);
+
+    while !reversi.is_game_end() {
+        let color = reversi.turn;
+        let name = reversi_name(color);
+
+        if !reversi.player_can_place(color) {
+            println!("{} は置ける場所がないためパスします。", name);
+            reversi.switch_turn();
+            continue;
+        }
+
+        println!("{}", reversi.print_board());
+
+        let line = match read_line(&format!("{} の番です。座標 (例: D3): ", name)) {
+            Some(line) => line,
+            None => return,
+        };
+
+        let (row, column) = match parse_coordinate(&line, reversi.alphabets()) {
+            Some(square) => square,
+            None => {
+                println!("座標が正しくありません。");
+                continue;
+            }
+        };
+
+        match reversi.do_place(row as i32, column as i32, color) {
+            Ok(()) => reversi.switch_turn(),
+            Err(why) => println!("そのマスには置けません。({:?})", why),
+        }
+    }
+
+    println!("{}", reversi.print_board());
+
+    let black = reversi.count_stones(reversi::Stone::BLACK);
+    let white = reversi.count_stones(reversi::Stone::WHITE);
+    println!("試合が終了しました。\nBlack: {}\nWhite: {}", black, white);
+
+    match reversi.winner() {
+        Some(color) => println!("Winner: {}", reversi_name(color)),
+        None => println!("引き分けです。"),
+    }
+}
+
+fn reversi_name(color: Color) -> &'static str {
+    match color {
+        Color::Black => "🔵 黒",
+        Color::White => "⚪ 白",
+    }
+}
+
+async fn play_quantum_gomoku(with_ai: bool, png_dir: Option<PathBuf>, style: BoardStyle) {
+    let mut gomoku = QuantumGomoku::new();
+    let mut moves = 0;
+
+    loop {
+        println!("{}", print_quantum_board(&gomoku));
+
+        if let Some(dir) = &png_dir {
+            let bytes = gen_quantum_board_image(gomoku.board, gomoku.last_move, style).await;
+            save_png(dir, &format!("board_{:03}.png", moves), &bytes);
+        }
+
+        let color = gomoku.current_turn.majority();
+
+        let (row, column, observe) = if with_ai && color == ObservedStone::White {
+            match ai_choice(&gomoku) {
+                Some(choice) => choice,
+                None => {
+                    println!("🤖 AI は置ける場所がありません。");
+                    return;
+                }
+            }
+        } else {
+            let prompt = format!(
+                "{} の番です ({})。座標 (例: H8、観測するなら H8 o): ",
+                gomoku_name(color),
+                stone_note(gomoku.current_turn)
+            );
+            let line = match read_line(&prompt) {
+                Some(line) => line,
+                None => return,
+            };
+
+            if line == "heatmap" {
+                match &png_dir {
+                    Some(dir) => {
+                        let bytes = gen_quantum_heatmap_image(&gomoku, style).await;
+                        save_png(dir, &format!("heatmap_{:03}.png", moves), &bytes);
+                    }
+                    None => println!("ヒートマップは --png を指定したときに保存できます。"),
+                }
+                continue;
+            }
+
+            let (text, observe) = match line.strip_suffix('o') {
+                Some(text) if text.ends_with(' ') => (text, true),
+                _ => (line.as_str(), false),
+            };
+
+            match parse_coordinate(text, QUANTUM_GOMOKU_ALPHABETS) {
+                Some((row, column)) => (row, column, observe),
+                None => {
+                    println!("座標が正しくありません。");
+                    continue;
+                }
+            }
+        };
+
+        if let Err(why) = gomoku.do_place(row, column) {
+            println!("そのマスには置けません。({:?})", why);
+            continue;
+        }
+        gomoku.switch_turn();
+        moves += 1;
+
+        if with_ai && color == ObservedStone::White {
+            println!(
+                "🤖 AI は {}{} に置きました。",
+                &QUANTUM_GOMOKU_ALPHABETS[row..=row],
+                column + 1
+            );
+        }
+
+        // 盤面が埋まるとどちらも置けないので、観測して決着をつける
+        let full = gomoku.is_full(QUANTUM_GOMOKU_ALPHABETS.len());
+        if !(observe || full) {
+            continue;
+        }
+
+        let (winner, observed_board, connected_mass) = gomoku.judge_winner();
+
+        println!("観測しました。");
+        println!("{}", print_observed_board(&observed_board));
+
+        if let Some(dir) = &png_dir {
+            let bytes = gen_observation_image(
+                gomoku.board,
+                gomoku.last_move,
+                observed_board,
+                connected_mass,
+                style,
+            )
+            .await;
+            save_png(dir, &format!("observation_{:03}.png", moves), &bytes);
+        }
+
+        match winner {
+            ObservedStone::None if full => {
+                println!("観測した結果、揃いませんでした。盤面が埋まったので引き分けです。");
+                return;
+            }
+            ObservedStone::None => println!("観測した結果、揃いませんでした。"),
+            winner => {
+                println!(
+                    "{} がそろいました！ 試合が終了しました。",
+                    gomoku_name(winner)
+                );
+                return;
+            }
+        }
+    }
+}
+
+/// BOTと同じく、置く場所を選んでから置いたあとの盤面で観測するかを決める
+fn ai_choice(gomoku: &QuantumGomoku) -> Option<(usize, usize, bool)> {
+    let color = gomoku.current_turn.majority();
+    let (row, column) = ai::choose_place(gomoku)?;
+
+    let mut placed = gomoku.clone();
+    placed.do_place(row, column).ok()?;
+    placed.switch_turn();
+
+    Some((row, column, ai::should_observe(&placed, color)))
+}
+
+fn gomoku_name(color: ObservedStone) -> &'static str {
+    match color {
+        ObservedStone::Black => "黒",
+        _ => "白",
+    }
+}
+
+fn stone_note(turn: Turn) -> &'static str {
+    match turn {
+        Turn::Black90 => "90%黒 - 10%白",
+        Turn::Black70 => "70%黒 - 30%白",
+        Turn::White90 => "90%白 - 10%黒",
+        Turn::White70 => "70%白 - 30%黒",
+    }
+}
+
+/// 黒になる確率で石を表す (B9 = 90%黒, W7 = 70%白)
+fn print_quantum_board(gomoku: &QuantumGomoku) -> String {
+    print_grid(|row, column| match gomoku.board[row][column] {
+        Stone::Black90 => "B9",
+        Stone::Black70 => "B7",
+        Stone::White70 => "W7",
+        Stone::White90 => "W9",
+        Stone::None => " .",
+    })
+}
+
+fn print_observed_board(observed_board: &[[ObservedStone; 19]; 19]) -> String {
+    print_grid(|row, column| match observed_board[row][column] {
+        ObservedStone::Black => " B",
+        ObservedStone::White => " W",
+        ObservedStone::None => " .",
+    })
+}
+
+fn print_grid(square: impl Fn(usize, usize) -> &'static str) -> String {
+    let size = QUANTUM_GOMOKU_ALPHABETS.len();
+
+    let mut board = String::from("  ");
+    for number in 1..=size {
+        board += &format!(" {:>2}", number);
+    }
+    board += "\n";
+
+    for (row, alphabet) in QUANTUM_GOMOKU_ALPHABETS.chars().enumerate() {
+        board += &format!("{} ", alphabet);
+
+        for column in 0..size {
+            board += " ";
+            board += square(row, column);
+        }
+
+        board += "\n";
+    }
+
+    return board;
+}
+
+fn save_png(dir: &Path, filename: &str, bytes: &[u8]) {
+    let path = dir.join(filename);
+
+    match std::fs::create_dir_all(dir).and_then(|_| std::fs::write(&path, bytes)) {
+        Ok(()) => println!("{} を保存しました。", path.display()),
+        Err(why) => eprintln!("{} を保存できませんでした: {}", path.display(), why),
+    }
+}
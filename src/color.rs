@@ -0,0 +1,27 @@
+/// 対戦者の色 (手番など、石のない状態がありえないところで使う)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Color {
+    Black,
+    White,
+}
+
+impl Color {
+    /// 相手の色
+    pub fn rival(self) -> Color {
+        match self {
+            Color::Black => Color::White,
+            Color::White => Color::Black,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rival() {
+        assert_eq!(Color::White, Color::Black.rival());
+        assert_eq!(Color::Black, Color::White.rival());
+    }
+}
@@ -0,0 +1,56 @@
+use serenity::all::{CommandInteraction, Context, CreateInteractionResponseMessage};
+use serenity::builder::CreateCommand;
+use serenity::model::application::ResolvedOption;
+
+use crate::discord::Discord;
+use crate::error::error_message;
+use crate::game::Game;
+use crate::global_data::get_sessions;
+use crate::i18n::{self, Locale, Text};
+
+/// `/{COMMAND}_end` で試合を終了する
+pub async fn run<G: Game>(
+    _options: &[ResolvedOption<'_>],
+    interaction: &CommandInteraction,
+    ctx: &Context,
+    locale: Locale,
+) -> CreateInteractionResponseMessage {
+    return end::<G>(ctx, interaction.channel_id.get(), locale).await;
+}
+
+/// `channel_id` の試合を終了する
+pub async fn end<G: Game>(
+    discord: &impl Discord,
+    channel_id_u64: u64,
+    locale: Locale,
+) -> CreateInteractionResponseMessage {
+    let inter_data = CreateInteractionResponseMessage::new();
+
+    let sessions = match get_sessions::<G>(discord).await {
+        Ok(sessions) => sessions,
+        Err(why) => return error_message(&why, locale),
+    };
+
+    // 表から外してから、石を置いている途中の処理を待って終了済みにする
+    let removed = sessions.lock().await.remove(&channel_id_u64);
+
+    match removed {
+        Some(session) => session.lock().await.ended = true,
+        None => return inter_data.content(Text::NoGame.get(locale)),
+    }
+
+    return inter_data.content(Text::GameEnded.get(locale));
+}
+
+pub fn register<G: Game>() -> CreateCommand {
+    i18n::command(
+        &format!("{}_end", G::COMMAND),
+        Some(&|locale| {
+            Text::EndCommand.format(
+                locale,
+                &[("game", G::NAME.get(locale)), ("command", G::COMMAND)],
+            )
+        }),
+        &|locale| Text::EndDescription.format(locale, &[("game", G::NAME.get(locale))]),
+    )
+}
@@ -0,0 +1,170 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use serenity::all::{
+    CommandInteraction, CommandOptionType, Context, CreateAttachment,
+    CreateInteractionResponseMessage, ResolvedValue, UserId,
+};
+use serenity::builder::CreateCommand;
+use serenity::model::application::ResolvedOption;
+use tokio::sync::Mutex;
+
+use crate::components::push_stone::turn_info;
+use crate::discord::Discord;
+use crate::error::error_message;
+use crate::game::session::{names, Clock, InputMode, Session, SessionUser};
+use crate::game::Game;
+use crate::global_data::{get_sessions, get_time_control, get_user_settings};
+use crate::i18n::{self, Locale, Text};
+
+/// `/{COMMAND}_start` で試合を開始する
+pub async fn run<G: Game>(
+    options: &[ResolvedOption<'_>],
+    interaction: &CommandInteraction,
+    ctx: &Context,
+    locale: Locale,
+) -> CreateInteractionResponseMessage {
+    let inter_data = CreateInteractionResponseMessage::new();
+
+    let rival_user = if let Some(ResolvedOption {
+        value: ResolvedValue::User(rival_user, _),
+        ..
+    }) = options.iter().find(|option| option.name == "user")
+    {
+        rival_user
+    } else {
+        return inter_data.content(Text::InvalidRival.get(locale));
+    };
+
+    let input_mode = match options.iter().find(|option| option.name == "input") {
+        Some(ResolvedOption {
+            value: ResolvedValue::String("menus"),
+            ..
+        }) => InputMode::Menus,
+        _ => InputMode::Buttons,
+    };
+
+    return start::<G>(
+        ctx,
+        interaction.channel_id.get(),
+        interaction.user.id,
+        (rival_user.id, rival_user.bot),
+        input_mode,
+        options,
+        locale,
+    )
+    .await;
+}
+
+/// `channel_id` で `user_id` (黒) と `rival` (白) の試合を始める
+///
+/// `rival` は (ユーザーID, Botかどうか)。`options` はゲームごとの開始時の設定。
+pub async fn start<G: Game>(
+    discord: &impl Discord,
+    channel_id_u64: u64,
+    user_id: UserId,
+    rival: (UserId, bool),
+    input_mode: InputMode,
+    options: &[ResolvedOption<'_>],
+    locale: Locale,
+) -> CreateInteractionResponseMessage {
+    let inter_data = CreateInteractionResponseMessage::new();
+    let (rival_id, rival_is_bot) = rival;
+
+    // このBOT自身を指定した場合はBOTと対戦する
+    let is_ai = G::HAS_AI && rival_id == discord.current_user_id();
+
+    if rival_is_bot && !is_ai {
+        return inter_data.content(Text::BotRival.get(locale));
+    }
+
+    let sessions = match get_sessions::<G>(discord).await {
+        Ok(sessions) => sessions,
+        Err(why) => return error_message(&why, locale),
+    };
+
+    if sessions.lock().await.contains_key(&channel_id_u64) {
+        return inter_data.content(Text::AlreadyPlaying.get(locale));
+    }
+
+    let white_user = if is_ai {
+        SessionUser::new_ai(rival_id)
+    } else {
+        SessionUser::new(rival_id)
+    };
+
+    let mut session = Session::new(G::new(options), SessionUser::new(user_id), white_user);
+    session.input_mode = input_mode;
+    let time_control = get_time_control(discord).await;
+    session.clock = Clock::new(time_control);
+
+    let names = names(discord, session.user_ids()).await;
+    let style = get_user_settings(discord, user_id).await.board_style();
+    let board_render = session.game.render(style);
+
+    let mut content = turn_info(&session.game, &names, locale);
+    if let Some(minutes) = time_control.game_minutes {
+        let text = Text::TimeControlGame.format(locale, &[("minutes", &minutes.to_string())]);
+        content = text + "\n" + &content;
+    }
+    if let Some(seconds) = time_control.move_seconds {
+        let text = Text::TimeControlMove.format(locale, &[("seconds", &seconds.to_string())]);
+        content = text + "\n" + &content;
+    }
+    if let Some(intro) = session.game.intro(locale) {
+        content = intro + "\n" + &content;
+    }
+    let components = session.components(locale);
+
+    // 名前と設定を調べている間に同じチャンネルで試合が始まっていないか確かめてから入れる
+    {
+        let mut sessions = sessions.lock().await;
+        if sessions.contains_key(&channel_id_u64) {
+            return inter_data.content(Text::AlreadyPlaying.get(locale));
+        }
+        sessions.insert(channel_id_u64, Arc::new(Mutex::new(session)));
+    }
+
+    let board = board_render.await;
+    if let Some(text) = board.text {
+        content = content + "\n\n" + &text;
+    }
+
+    let mut inter_data = inter_data.content(content).components(components);
+
+    for (filename, bytes) in board.files {
+        inter_data = inter_data.add_file(CreateAttachment::bytes(Cow::from(bytes), filename));
+    }
+
+    return inter_data;
+}
+
+pub fn register<G: Game>() -> CreateCommand {
+    let name = format!("{}_start", G::COMMAND);
+
+    let mut command = i18n::command(
+        &name,
+        Some(&|locale| {
+            Text::StartCommand.format(
+                locale,
+                &[("game", G::NAME.get(locale)), ("command", G::COMMAND)],
+            )
+        }),
+        &|locale| Text::StartDescription.format(locale, &[("game", G::NAME.get(locale))]),
+    )
+    .add_option(
+        i18n::option(
+            CommandOptionType::User,
+            "user",
+            Text::RivalOption,
+            Text::RivalOptionDescription,
+        )
+        .required(true),
+    );
+
+    for option in G::start_options() {
+        command = command.add_option(option);
+    }
+
+    return command;
+}
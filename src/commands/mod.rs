@@ -1,7 +1,10 @@
 pub mod ping;
 
-pub mod reversi_end;
-pub mod reversi_start;
+pub mod move_stone;
+pub mod opening;
 
-pub mod q_gomoku_end;
-pub mod q_gomoku_start;
+pub mod game_end;
+pub mod game_start;
+
+pub mod registration;
+pub mod settings;
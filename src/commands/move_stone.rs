@@ -0,0 +1,178 @@
+use serenity::all::{
+    CommandInteraction, CommandOptionType, Context, CreateInteractionResponseMessage, ResolvedValue,
+};
+use serenity::builder::CreateCommand;
+use serenity::model::application::ResolvedOption;
+
+use crate::components::coordinate_input::place_by_text;
+use crate::discord::Discord;
+use crate::error::{error_message, BotError};
+use crate::game::Game;
+use crate::global_data::get_sessions;
+use crate::gomoku::gomoku::Gomoku;
+use crate::i18n::{self, Locale, Text};
+use crate::quantum_gomoku::gomoku::QuantumGomoku;
+use crate::quantum_reversi::reversi::QuantumReversi;
+use crate::reversi::reversi::Reversi;
+
+pub async fn run(
+    options: &[ResolvedOption<'_>],
+    interaction: &CommandInteraction,
+    ctx: &Context,
+    locale: Locale,
+) -> CreateInteractionResponseMessage {
+    let inter_data = CreateInteractionResponseMessage::new();
+
+    let mut coordinate = "";
+    let mut observe = false;
+    let mut game_name = None;
+
+    for option in options {
+        match (option.name, &option.value) {
+            ("coordinate", ResolvedValue::String(value)) => coordinate = value,
+            ("observe", ResolvedValue::Boolean(value)) => observe = *value,
+            ("game", ResolvedValue::String(value)) => game_name = Some(*value),
+            _ => (),
+        }
+    }
+
+    let channel_id_u64 = interaction.channel_id.get();
+
+    let game_name = match game_name {
+        Some(game_name) => game_name,
+        None => match active_games(ctx, channel_id_u64).await[..] {
+            [game_name] => game_name,
+            [] => return inter_data.content(Text::NoGame.get(locale)).ephemeral(true),
+            _ => {
+                return inter_data
+                    .content(Text::MultipleGames.get(locale))
+                    .ephemeral(true)
+            }
+        },
+    };
+
+    let user_id = interaction.user.id;
+
+    // 新しいメッセージとして送るが、続きを打てるようにメニューもついている
+    let result = match game_name {
+        Reversi::COMMAND => {
+            place_by_text::<Reversi>(
+                ctx,
+                channel_id_u64,
+                user_id,
+                coordinate,
+                action::<Reversi>(observe),
+                locale,
+            )
+            .await
+        }
+        QuantumGomoku::COMMAND => {
+            place_by_text::<QuantumGomoku>(
+                ctx,
+                channel_id_u64,
+                user_id,
+                coordinate,
+                action::<QuantumGomoku>(observe),
+                locale,
+            )
+            .await
+        }
+        QuantumReversi::COMMAND => {
+            place_by_text::<QuantumReversi>(
+                ctx,
+                channel_id_u64,
+                user_id,
+                coordinate,
+                action::<QuantumReversi>(observe),
+                locale,
+            )
+            .await
+        }
+        Gomoku::COMMAND => {
+            place_by_text::<Gomoku>(
+                ctx,
+                channel_id_u64,
+                user_id,
+                coordinate,
+                action::<Gomoku>(observe),
+                locale,
+            )
+            .await
+        }
+        _ => Err(BotError::NoGame),
+    };
+
+    match result {
+        Ok(data) => data,
+        Err(why) => error_message(&why, locale),
+    }
+}
+
+/// 観測などの2つ目のボタンがあるゲームでは `observe` でそちらを使う
+fn action<G: Game>(observe: bool) -> &'static str {
+    match G::ACTIONS.get(1) {
+        Some(action) if observe => action.id,
+        _ => G::ACTIONS[0].id,
+    }
+}
+
+async fn is_active<G: Game>(discord: &impl Discord, channel_id: u64) -> bool {
+    match get_sessions::<G>(discord).await {
+        Ok(sessions) => sessions.lock().await.contains_key(&channel_id),
+        Err(_) => false,
+    }
+}
+
+async fn active_games(discord: &impl Discord, channel_id: u64) -> Vec<&'static str> {
+    let mut games = vec![];
+
+    if is_active::<Reversi>(discord, channel_id).await {
+        games.push(Reversi::COMMAND);
+    }
+    if is_active::<QuantumGomoku>(discord, channel_id).await {
+        games.push(QuantumGomoku::COMMAND);
+    }
+    if is_active::<QuantumReversi>(discord, channel_id).await {
+        games.push(QuantumReversi::COMMAND);
+    }
+    if is_active::<Gomoku>(discord, channel_id).await {
+        games.push(Gomoku::COMMAND);
+    }
+
+    return games;
+}
+
+pub fn register() -> CreateCommand {
+    let game = i18n::option(
+        CommandOptionType::String,
+        "game",
+        Text::GameOption,
+        Text::GameOptionDescription,
+    );
+    let game = i18n::string_choice(game, Reversi::NAME, Reversi::COMMAND);
+    let game = i18n::string_choice(game, QuantumGomoku::NAME, QuantumGomoku::COMMAND);
+    let game = i18n::string_choice(game, QuantumReversi::NAME, QuantumReversi::COMMAND);
+    let game = i18n::string_choice(game, Gomoku::NAME, Gomoku::COMMAND);
+
+    i18n::command(
+        "move",
+        Some(&|locale| Text::MoveCommand.get(locale).to_string()),
+        &|locale| Text::MoveDescription.get(locale).to_string(),
+    )
+    .add_option(
+        i18n::option(
+            CommandOptionType::String,
+            "coordinate",
+            Text::CoordinateOption,
+            Text::CoordinateOptionDescription,
+        )
+        .required(true),
+    )
+    .add_option(i18n::option(
+        CommandOptionType::Boolean,
+        "observe",
+        Text::ObserveOption,
+        Text::ObserveOptionDescription,
+    ))
+    .add_option(game)
+}
@@ -0,0 +1,105 @@
+use serenity::all::{CommandOptionType, CreateInteractionResponseMessage, ResolvedValue};
+use serenity::builder::CreateCommand;
+use serenity::model::application::ResolvedOption;
+
+use crate::game::reversi::opening_name;
+use crate::i18n::{self, Locale, Text};
+use crate::reversi::opening;
+
+pub fn run(options: &[ResolvedOption<'_>], locale: Locale) -> CreateInteractionResponseMessage {
+    let mut moves = "";
+    for option in options {
+        if let ("moves", ResolvedValue::String(value)) = (option.name, &option.value) {
+            moves = value;
+        }
+    }
+
+    return CreateInteractionResponseMessage::new()
+        .content(lookup(moves, locale))
+        .ephemeral(true);
+}
+
+/// 手順の定石と、その続きになる定石
+pub fn lookup(text: &str, locale: Locale) -> String {
+    let moves = match opening::parse_moves(text) {
+        Some(moves) if !moves.is_empty() => moves,
+        _ => return Text::InvalidMoves.get(locale).to_string(),
+    };
+
+    if let Err((index, _)) = opening::replay(&moves) {
+        return Text::IllegalMoveInSequence.format(
+            locale,
+            &[
+                ("number", &(index + 1).to_string()),
+                ("square", &opening::notation(&moves[index..=index])),
+            ],
+        );
+    }
+
+    let mut lines = vec![Text::MovesNote.format(locale, &[("moves", &opening::notation(&moves))])];
+
+    match opening::find(&moves) {
+        Some(found) => {
+            lines.push(Text::Opening.format(locale, &[("name", opening_name(found, locale))]))
+        }
+        None => lines.push(Text::NoOpening.get(locale).to_string()),
+    }
+
+    let continuations = opening::continuations(&moves);
+    if !continuations.is_empty() {
+        lines.push(Text::OpeningContinuations.get(locale).to_string());
+
+        for (continuation, continuation_moves) in continuations {
+            lines.push(format!(
+                "- {}: {}",
+                opening_name(continuation, locale),
+                opening::notation(&continuation_moves)
+            ));
+        }
+    }
+
+    return lines.join("\n");
+}
+
+pub fn register() -> CreateCommand {
+    i18n::command(
+        "opening",
+        Some(&|locale| Text::OpeningCommand.get(locale).to_string()),
+        &|locale| Text::OpeningDescription.get(locale).to_string(),
+    )
+    .add_option(
+        i18n::option(
+            CommandOptionType::String,
+            "moves",
+            Text::MovesOption,
+            Text::MovesOptionDescription,
+        )
+        .required(true),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup_opening() {
+        let text = lookup("d3 c3", Locale::En);
+        assert!(text.contains("Moves: D3 C3"), "{}", text);
+        assert!(text.contains("Opening: Diagonal"), "{}", text);
+        assert!(text.contains("- Rabbit: D3 C3 C4 E3 F4"), "{}", text);
+
+        let text = lookup("F5 D6 C3 D3 C4 F4 C5 B3 C2", Locale::Ja);
+        assert!(text.contains("定石: ローズ"), "{}", text);
+        assert!(!text.contains("この先の定石"), "{}", text);
+
+        assert_eq!(
+            "手順が正しくありません。(例: F5 D6 C3)",
+            lookup("F5 Z9", Locale::Ja)
+        );
+        assert_eq!(
+            "Move 3 (A1) cannot be played.",
+            lookup("F5 D6 A1", Locale::En)
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use serenity::all::{CreateCommand, GuildId};
+
+use crate::discord::Discord;
+
+/// 登録前後のコマンドの違い (コマンド名)
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct CommandChanges {
+    pub added: Vec<String>,
+    pub updated: Vec<String>,
+    pub removed: Vec<String>,
+    pub unchanged: Vec<String>,
+}
+
+/// コマンドを一括で上書き登録し、変わったところをログに出す
+///
+/// `guild_ids` があればそのサーバーだけに登録する (すぐに反映されるので開発用)。
+/// なければグローバルに登録する。どちらも定義から消したコマンドは削除される。
+/// もう一方に前に登録したコマンドは二重に表示されるので、空にして消す。
+pub async fn register_commands(
+    discord: &impl Discord,
+    guild_ids: &[u64],
+    definitions: Vec<CreateCommand>,
+) {
+    if guild_ids.is_empty() {
+        set_commands(discord, None, definitions).await;
+
+        for guild_id in discord.guild_ids() {
+            set_commands(discord, Some(guild_id), vec![]).await;
+        }
+
+        return;
+    }
+
+    for guild_id in guild_ids.iter().map(|id| GuildId::new(*id)) {
+        set_commands(discord, Some(guild_id), definitions.clone()).await;
+    }
+
+    set_commands(discord, None, vec![]).await;
+}
+
+/// `guild_id` (None ならグローバル) のコマンドを `definitions` で上書きする
+async fn set_commands(
+    discord: &impl Discord,
+    guild_id: Option<GuildId>,
+    definitions: Vec<CreateCommand>,
+) {
+    let scope = match guild_id {
+        Some(guild_id) => format!("guild {guild_id}"),
+        None => "global".to_string(),
+    };
+
+    let before = match discord.commands(guild_id).await {
+        Ok(commands) => commands,
+        Err(why) => return println!("Cannot get {scope} commands: {why}"),
+    };
+
+    // 消すコマンドがなければ登録しない (BOTが入っているサーバーごとに呼ぶため)
+    if definitions.is_empty() && before.is_empty() {
+        return;
+    }
+
+    match discord.set_commands(guild_id, definitions).await {
+        Ok(after) => log_changes(&scope, &diff(&before, &after)),
+        Err(why) => println!("Cannot set {scope} commands: {why}"),
+    }
+}
+
+/// (コマンド名, バージョン) の一覧を比べる (内容が変わるとバージョンが上がる)
+fn diff(before: &[(String, u64)], after: &[(String, u64)]) -> CommandChanges {
+    let mut changes = CommandChanges::default();
+
+    for (name, version) in after {
+        match before.iter().find(|(before_name, _)| before_name == name) {
+            None => changes.added.push(name.clone()),
+            Some((_, before_version)) if before_version != version => {
+                changes.updated.push(name.clone())
+            }
+            Some(_) => changes.unchanged.push(name.clone()),
+        }
+    }
+
+    for (name, _) in before {
+        if !after.iter().any(|(after_name, _)| after_name == name) {
+            changes.removed.push(name.clone());
+        }
+    }
+
+    return changes;
+}
+
+fn log_changes(scope: &str, changes: &CommandChanges) {
+    println!(
+        "SetCommands ({}): added {:?}, updated {:?}, removed {:?}, unchanged {}",
+        scope,
+        changes.added,
+        changes.updated,
+        changes.removed,
+        changes.unchanged.len()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discord::FakeDiscord;
+    use serenity::all::UserId;
+
+    fn commands(list: &[(&str, u64)]) -> Vec<(String, u64)> {
+        list.iter()
+            .map(|(name, version)| (name.to_string(), *version))
+            .collect()
+    }
+
+    #[test]
+    fn diff_commands() {
+        let before = commands(&[("ping", 1), ("reversi_start", 1), ("old", 1)]);
+        let after = commands(&[("ping", 1), ("reversi_start", 2), ("move", 3)]);
+
+        assert_eq!(
+            CommandChanges {
+                added: vec!["move".to_string()],
+                updated: vec!["reversi_start".to_string()],
+                removed: vec!["old".to_string()],
+                unchanged: vec!["ping".to_string()],
+            },
+            diff(&before, &after)
+        );
+    }
+
+    #[tokio::test]
+    async fn clear_other_scope() {
+        let discord = FakeDiscord::new(UserId::new(1), &[]).with_guilds(&[10, 20]);
+        let definitions = || vec![CreateCommand::new("ping"), CreateCommand::new("move")];
+        let (guild_10, guild_20) = (Some(GuildId::new(10)), Some(GuildId::new(20)));
+
+        // グローバルに登録する (どのサーバーにもコマンドがないので、サーバーは上書きしない)
+        register_commands(&discord, &[], definitions()).await;
+        assert_eq!(vec![None], discord.take_set_calls());
+        assert_eq!(vec!["ping", "move"], discord.command_names(None));
+
+        // サーバーだけに登録し、グローバルのコマンドを消す
+        register_commands(&discord, &[10], definitions()).await;
+        assert_eq!(vec![guild_10, None], discord.take_set_calls());
+        assert_eq!(vec!["ping", "move"], discord.command_names(guild_10));
+        assert!(discord.command_names(None).is_empty());
+
+        // グローバルに戻すと、サーバーのコマンドを消す
+        register_commands(&discord, &[], definitions()).await;
+        assert_eq!(vec![None, guild_10], discord.take_set_calls());
+        assert_eq!(vec!["ping", "move"], discord.command_names(None));
+        assert!(discord.command_names(guild_10).is_empty());
+        assert!(discord.command_names(guild_20).is_empty());
+    }
+}
@@ -0,0 +1,176 @@
+use serenity::all::{
+    CommandInteraction, CommandOptionType, Context, CreateInteractionResponseMessage,
+    ResolvedValue, UserId,
+};
+use serenity::builder::CreateCommand;
+use serenity::model::application::ResolvedOption;
+
+use crate::discord::Discord;
+use crate::error::{error_message, log_error, BotError};
+use crate::global_data::GlobalSettings;
+use crate::i18n::{self, Locale, Text};
+use crate::render::theme::Theme;
+use crate::settings::UserSettings;
+
+pub fn theme_name(theme: Theme) -> Text {
+    match theme {
+        Theme::Wood => Text::ThemeWood,
+        Theme::Dark => Text::ThemeDark,
+        Theme::HighContrast => Text::ThemeHighContrast,
+        Theme::Colorblind => Text::ThemeColorblind,
+    }
+}
+
+pub async fn run(
+    options: &[ResolvedOption<'_>],
+    interaction: &CommandInteraction,
+    ctx: &Context,
+    locale: Locale,
+) -> CreateInteractionResponseMessage {
+    let user_id = interaction.user.id;
+    let mut result = Err(BotError::UnexpectedInteraction("settings".to_string()));
+
+    // `/settings theme theme:<テーマ>` `/settings labels show:<bool>`
+    for option in options {
+        let sub_options = match &option.value {
+            ResolvedValue::SubCommand(sub_options) => sub_options,
+            _ => continue,
+        };
+
+        for sub_option in sub_options {
+            match (option.name, sub_option.name, &sub_option.value) {
+                ("theme", "theme", ResolvedValue::String(value)) => {
+                    if let Some(theme) = Theme::from_id(value) {
+                        result = set_theme(ctx, user_id, theme, locale).await;
+                    }
+                }
+                ("labels", "show", ResolvedValue::Boolean(show)) => {
+                    result = set_stone_labels(ctx, user_id, *show, locale).await;
+                }
+                _ => (),
+            }
+        }
+    }
+
+    match result {
+        Ok(data) => data,
+        Err(why) => error_message(&why, locale),
+    }
+}
+
+/// テーマを変えて保存する
+pub async fn set_theme(
+    discord: &impl Discord,
+    user_id: UserId,
+    theme: Theme,
+    locale: Locale,
+) -> Result<CreateInteractionResponseMessage, BotError> {
+    update(discord, user_id, |settings| settings.theme = theme).await?;
+
+    let content = Text::ThemeChanged.format(locale, &[("theme", theme_name(theme).get(locale))]);
+
+    return Ok(CreateInteractionResponseMessage::new()
+        .content(content)
+        .ephemeral(true));
+}
+
+/// 量子の石に確率を描くかを変えて保存する
+pub async fn set_stone_labels(
+    discord: &impl Discord,
+    user_id: UserId,
+    show: bool,
+    locale: Locale,
+) -> Result<CreateInteractionResponseMessage, BotError> {
+    update(discord, user_id, |settings| settings.stone_labels = show).await?;
+
+    let content = if show {
+        Text::LabelsShown
+    } else {
+        Text::LabelsHidden
+    };
+
+    return Ok(CreateInteractionResponseMessage::new()
+        .content(content.get(locale))
+        .ephemeral(true));
+}
+
+/// 設定を変えて保存する (保存に失敗しても、起動している間は変えた設定を使う)
+async fn update(
+    discord: &impl Discord,
+    user_id: UserId,
+    change: impl FnOnce(&mut UserSettings),
+) -> Result<(), BotError> {
+    let settings = {
+        let data = discord.data().read().await;
+
+        data.get::<GlobalSettings>()
+            .cloned()
+            .ok_or(BotError::MissingData("GlobalSettings"))?
+    };
+
+    let snapshot = {
+        let mut settings = settings.write().await;
+
+        let mut user_settings = settings.get(user_id);
+        change(&mut user_settings);
+        settings.set(user_id, user_settings);
+
+        settings.snapshot()
+    };
+
+    // ファイルは表のロックを離してから、非同期の処理の外で書く
+    if let Some(snapshot) = snapshot {
+        match tokio::task::spawn_blocking(move || snapshot.save()).await {
+            Ok(Ok(())) => (),
+            Ok(Err(why)) => println!("Settings: cannot save: {why}"),
+            Err(why) => log_error(&BotError::Task(why.to_string())),
+        }
+    }
+
+    return Ok(());
+}
+
+pub fn register() -> CreateCommand {
+    let mut theme = i18n::option(
+        CommandOptionType::String,
+        "theme",
+        Text::ThemeOption,
+        Text::ThemeOptionDescription,
+    )
+    .required(true);
+    for value in Theme::ALL {
+        theme = i18n::string_choice(theme, theme_name(value), value.id());
+    }
+
+    i18n::command(
+        "settings",
+        Some(&|locale| Text::SettingsCommand.get(locale).to_string()),
+        &|locale| Text::SettingsDescription.get(locale).to_string(),
+    )
+    .add_option(
+        i18n::option(
+            CommandOptionType::SubCommand,
+            "theme",
+            Text::ThemeOption,
+            Text::ThemeSubcommandDescription,
+        )
+        .add_sub_option(theme),
+    )
+    .add_option(
+        i18n::option(
+            CommandOptionType::SubCommand,
+            "labels",
+            Text::LabelsOption,
+            Text::LabelsSubcommandDescription,
+        )
+        .add_sub_option(
+            i18n::option(
+                CommandOptionType::Boolean,
+                "show",
+                Text::ShowOption,
+                Text::ShowOptionDescription,
+            )
+            .required(true),
+        ),
+    )
+}
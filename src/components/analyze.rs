@@ -0,0 +1,60 @@
+use std::borrow::Cow;
+
+use serenity::all::{
+    CreateAttachment, CreateInteractionResponse, CreateInteractionResponseMessage,
+};
+
+use crate::discord::{Discord, Input};
+use crate::error::BotError;
+use crate::game::session::names;
+use crate::game::Game;
+use crate::global_data::{get_finished_sessions, get_user_settings};
+
+/// 「解析」ボタン (custom_id は `{ID}_analyze_{番号}`) が押されたとき
+///
+/// 決着した試合を解析し、埋め込みとグラフの画像を新しいメッセージで送る。対戦者でなくても押せる。
+pub async fn run<G: Game>(
+    discord: &impl Discord,
+    input: &Input,
+    id: &str,
+) -> Result<CreateInteractionResponse, BotError> {
+    if !G::HAS_ANALYSIS {
+        return Err(BotError::UnexpectedInteraction(input.custom_id.clone()));
+    }
+
+    let id = id
+        .parse::<u32>()
+        .map_err(|_| BotError::UnexpectedInteraction(input.custom_id.clone()))?;
+
+    // 画像は押した人の設定で描く
+    let style = get_user_settings(discord, input.user_id)
+        .await
+        .board_style();
+
+    let session = {
+        let finished = get_finished_sessions::<G>(discord).await?;
+        let finished = finished.lock().await;
+
+        finished
+            .get(input.channel_id, id)
+            .ok_or(BotError::NoFinishedGame)?
+    };
+
+    // 名前は試合のロックを離してから調べる
+    let user_ids = session.lock().await.user_ids();
+    let names = names(discord, user_ids).await;
+
+    let analyze = session
+        .lock()
+        .await
+        .game
+        .analyze(&names, input.locale, style);
+    let analysis = analyze.await?;
+
+    let mut data = CreateInteractionResponseMessage::new().embed(analysis.embed);
+    for (filename, bytes) in analysis.files {
+        data = data.add_file(CreateAttachment::bytes(Cow::from(bytes), filename));
+    }
+
+    return Ok(CreateInteractionResponse::Message(data));
+}
@@ -0,0 +1,81 @@
+use serenity::all::{
+    CreateActionRow, CreateInputText, CreateInteractionResponse, CreateInteractionResponseMessage,
+    CreateModal, InputTextStyle, UserId,
+};
+
+use crate::components::push_stone;
+use crate::coordinate::parse_coordinate;
+use crate::discord::{Discord, Input};
+use crate::error::BotError;
+use crate::game::session::find_session;
+use crate::game::Game;
+use crate::global_data::get_sessions;
+use crate::i18n::{Locale, Text};
+
+/// "D3" のような座標の文字列で手番の人の石を置く
+pub async fn place_by_text<G: Game>(
+    discord: &impl Discord,
+    channel_id: u64,
+    user_id: UserId,
+    text: &str,
+    action: &str,
+    locale: Locale,
+) -> Result<CreateInteractionResponseMessage, BotError> {
+    let alphabets = {
+        let sessions = get_sessions::<G>(discord).await?;
+        let session = find_session(&sessions, channel_id).await?;
+        let alphabets = session.lock().await.game.alphabets();
+
+        alphabets
+    };
+
+    let (row, column) = parse_coordinate(text, alphabets).ok_or(BotError::InvalidCoordinate)?;
+
+    push_stone::place::<G>(discord, channel_id, user_id, row, column, action, locale).await
+}
+
+/// 「座標を入力」ボタン (custom_id は `{ID}_input_{action}`) が押されたときに入力欄を開く
+pub fn open_modal(input: &Input) -> CreateInteractionResponse {
+    let input_text = CreateInputText::new(
+        InputTextStyle::Short,
+        Text::CoordinateField.get(input.locale),
+        "coordinate",
+    )
+    .placeholder("D3")
+    .min_length(2)
+    .max_length(3)
+    .required(true);
+
+    let modal = CreateModal::new(
+        format!("{}_modal", input.custom_id),
+        Text::InputCoordinate.get(input.locale),
+    )
+    .components(vec![CreateActionRow::InputText(input_text)]);
+
+    return CreateInteractionResponse::Modal(modal);
+}
+
+/// 入力欄に入力された座標に石を置く
+pub async fn submit<G: Game>(
+    discord: &impl Discord,
+    input: &Input,
+    action: &str,
+) -> Result<CreateInteractionResponse, BotError> {
+    if !G::ACTIONS.iter().any(|a| a.id == action) {
+        return Err(BotError::UnexpectedInteraction(input.custom_id.clone()));
+    }
+
+    let text = input.values.first().map(String::as_str).unwrap_or_default();
+
+    let data = place_by_text::<G>(
+        discord,
+        input.channel_id,
+        input.user_id,
+        text,
+        action,
+        input.locale,
+    )
+    .await?;
+
+    return Ok(CreateInteractionResponse::UpdateMessage(data));
+}
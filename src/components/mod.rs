@@ -1,6 +1,96 @@
+pub mod analyze;
 pub mod choice_pos;
+pub mod coordinate_input;
 pub mod push_stone;
+pub mod view;
 
-pub mod qgomoku_choice_pos;
-pub mod qgomoku_push_stone;
-pub mod qgomoku_push_stone_observe;
+use serenity::all::CreateInteractionResponse;
+
+use crate::discord::{Discord, Input};
+use crate::error::error_message;
+use crate::game::Game;
+use crate::gomoku::gomoku::Gomoku;
+use crate::quantum_gomoku::gomoku::QuantumGomoku;
+use crate::quantum_reversi::reversi::QuantumReversi;
+use crate::reversi::reversi::Reversi;
+
+/// メッセージのコンポーネントへの返信 (どのゲームのものでもなければ None)
+///
+/// custom_id は `{Game::ID}_...` (例: `reversi_place_D3`, `qgomoku_push_stone_observe`)
+pub async fn component(discord: &impl Discord, input: &Input) -> Option<CreateInteractionResponse> {
+    let custom_id = input.custom_id.as_str();
+
+    let builder = if let Some(rest) = custom_id.strip_prefix("reversi_") {
+        game_component::<Reversi>(discord, input, rest).await
+    } else if let Some(rest) = custom_id.strip_prefix("qgomoku_") {
+        game_component::<QuantumGomoku>(discord, input, rest).await
+    } else if let Some(rest) = custom_id.strip_prefix("qreversi_") {
+        game_component::<QuantumReversi>(discord, input, rest).await
+    } else if let Some(rest) = custom_id.strip_prefix("gomoku_") {
+        game_component::<Gomoku>(discord, input, rest).await
+    } else {
+        return None;
+    };
+
+    return Some(builder);
+}
+
+/// 「座標を入力」の入力欄への返信 (custom_id は `{Game::ID}_input_{action}_modal`)
+pub async fn modal(discord: &impl Discord, input: &Input) -> Option<CreateInteractionResponse> {
+    let custom_id = input.custom_id.as_str();
+
+    let builder = if let Some(rest) = custom_id.strip_prefix("reversi_") {
+        game_modal::<Reversi>(discord, input, rest).await
+    } else if let Some(rest) = custom_id.strip_prefix("qgomoku_") {
+        game_modal::<QuantumGomoku>(discord, input, rest).await
+    } else if let Some(rest) = custom_id.strip_prefix("qreversi_") {
+        game_modal::<QuantumReversi>(discord, input, rest).await
+    } else if let Some(rest) = custom_id.strip_prefix("gomoku_") {
+        game_modal::<Gomoku>(discord, input, rest).await
+    } else {
+        return None;
+    };
+
+    return Some(builder);
+}
+
+/// ゲームのメッセージのコンポーネント (custom_id の `{Game::ID}_` より後ろ) を振り分ける
+async fn game_component<G: Game>(
+    discord: &impl Discord,
+    input: &Input,
+    rest: &str,
+) -> CreateInteractionResponse {
+    let result = match rest {
+        "choice_alphabet" => choice_pos::alphabet::<G>(discord, input).await,
+        "choice_number" => choice_pos::number::<G>(discord, input).await,
+        rest if rest.starts_with("place_") => {
+            push_stone::run_square::<G>(discord, input, &rest["place_".len()..]).await
+        }
+        rest if rest.starts_with("input_") => Ok(coordinate_input::open_modal(input)),
+        rest if rest.starts_with("analyze_") => {
+            analyze::run::<G>(discord, input, &rest["analyze_".len()..]).await
+        }
+        rest if rest.starts_with("view_") => {
+            view::run::<G>(discord, input, &rest["view_".len()..]).await
+        }
+        action => push_stone::run::<G>(discord, input, action).await,
+    };
+
+    match result {
+        Ok(builder) => builder,
+        Err(why) => CreateInteractionResponse::Message(error_message(&why, input.locale)),
+    }
+}
+
+async fn game_modal<G: Game>(
+    discord: &impl Discord,
+    input: &Input,
+    rest: &str,
+) -> CreateInteractionResponse {
+    let action = rest.trim_start_matches("input_").trim_end_matches("_modal");
+
+    match coordinate_input::submit::<G>(discord, input, action).await {
+        Ok(builder) => builder,
+        Err(why) => CreateInteractionResponse::Message(error_message(&why, input.locale)),
+    }
+}
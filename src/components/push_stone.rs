@@ -1,216 +1,250 @@
+use std::borrow::Cow;
+
 use serenity::all::{
-    ComponentInteraction, Context, CreateInteractionResponse, CreateInteractionResponseMessage,
-    UserId,
+    CreateAttachment, CreateInteractionResponse, CreateInteractionResponseMessage, UserId,
 };
 
-use crate::global_data::GlobalReversiStats;
-use crate::reversi::reversi::Stone;
+use crate::coordinate::parse_coordinate;
+use crate::discord::{Discord, Input};
+use crate::error::BotError;
+use crate::game::session::{analysis_components, find_session, names, remove_session, AiMove};
+use crate::game::{Game, Names, Rendered};
+use crate::global_data::{get_finished_sessions, get_sessions, get_user_settings};
+use crate::i18n::{Locale, Text};
+
+/// 「置く」などのボタン (custom_id は `{ID}_{action}`) が押されたとき
+pub async fn run<G: Game>(
+    discord: &impl Discord,
+    input: &Input,
+    action: &str,
+) -> Result<CreateInteractionResponse, BotError> {
+    if !G::ACTIONS.iter().any(|a| a.id == action) {
+        return Err(BotError::UnexpectedInteraction(input.custom_id.clone()));
+    }
 
-pub async fn run(ctx: &Context, interaction: &ComponentInteraction) {
-    let inter_userid = interaction.user.id;
-    let inter_channelid = interaction.channel_id.get();
+    let inter_userid = input.user_id;
+    let inter_channelid = input.channel_id;
 
-    let is_contains_key = {
-        let data = ctx.data.read().await;
+    let (push_row, push_column) = {
+        let sessions = get_sessions::<G>(discord).await?;
+        let session = find_session(&sessions, inter_channelid).await?;
+        let session = session.lock().await;
 
-        let reversi_stats = data
-            .get::<GlobalReversiStats>()
-            .expect("Expected GlobalReversiStats in TypeMap.");
-        let reversi_stats = reversi_stats.lock().await;
+        session.check_turn(inter_userid)?;
 
-        reversi_stats.contains_key(&inter_channelid)
+        // 今のターンの人の指定した行列を取り出す
+        session.choiced_square().ok_or(BotError::NotChoiced)?
     };
 
-    if !(is_contains_key) {
-        return;
-    }
-
-    let check_userid = {
-        let data = ctx.data.read().await;
-
-        let reversi_stats = data
-            .get::<GlobalReversiStats>()
-            .expect("Expected GlobalReversiStats in TypeMap.");
-        let reversi_stats = reversi_stats.lock().await;
-
-        let channel_stats = reversi_stats.get(&inter_channelid).unwrap();
+    let data = place::<G>(
+        discord,
+        inter_channelid,
+        inter_userid,
+        push_row,
+        push_column,
+        action,
+        input.locale,
+    )
+    .await?;
+
+    return Ok(CreateInteractionResponse::UpdateMessage(data));
+}
 
-        match channel_stats.reversi.turn_stone {
-            Stone::BLACK => channel_stats.black_user.as_ref().unwrap().id,
-            Stone::WHITE => channel_stats.white_user.as_ref().unwrap().id,
-            Stone::NONE => panic!("NoneError"),
-        }
+/// 置けるマスのボタン (custom_id は `{ID}_place_D3` など) が押されたとき
+pub async fn run_square<G: Game>(
+    discord: &impl Discord,
+    input: &Input,
+    label: &str,
+) -> Result<CreateInteractionResponse, BotError> {
+    let alphabets = {
+        let sessions = get_sessions::<G>(discord).await?;
+        let session = find_session(&sessions, input.channel_id).await?;
+        let alphabets = session.lock().await.game.alphabets();
+
+        alphabets
     };
 
-    if inter_userid != check_userid {
-        cant_notice(ctx, interaction, "手番ではないため置けません。").await;
-        return;
-    }
-
-    let (push_row, push_column) = {
-        let data = ctx.data.read().await;
-
-        let reversi_stats = data
-            .get::<GlobalReversiStats>()
-            .expect("Expected GlobalReversiStats in TypeMap.");
-        let reversi_stats = reversi_stats.lock().await;
-
-        let channel_stats = reversi_stats.get(&inter_channelid).unwrap();
-
-        // 今のリバーシのターンの人の指定した行列を取り出す
-        let (alphabet, number) = {
-            let black_user = channel_stats.black_user.as_ref().unwrap();
-            let white_user = channel_stats.white_user.as_ref().unwrap();
-
-            match channel_stats.reversi.turn_stone {
-                Stone::BLACK => (
-                    black_user.choiced_alphabet.clone(),
-                    black_user.choiced_number.clone(),
-                ),
-                Stone::WHITE => (
-                    white_user.choiced_alphabet.clone(),
-                    white_user.choiced_number.clone(),
-                ),
-                Stone::NONE => panic!("NoneError"),
-            }
-        };
+    let (push_row, push_column) =
+        parse_coordinate(label, alphabets).ok_or(BotError::InvalidCoordinate)?;
+
+    let data = place::<G>(
+        discord,
+        input.channel_id,
+        input.user_id,
+        push_row,
+        push_column,
+        G::ACTIONS[0].id,
+        input.locale,
+    )
+    .await?;
+
+    return Ok(CreateInteractionResponse::UpdateMessage(data));
+}
 
-        if let (Some(alphabet), Some(number)) = (alphabet, number) {
-            ("ABCDEFGH".find(&alphabet), "12345678".find(&number))
-        } else {
-            (None, None)
-        }
+/// 手番の人が (row, column) に石を置き、更新後の試合のメッセージを返す
+///
+/// ボタン・座標の入力・`/move` のどれから置いた場合もここを通る。
+/// 次がBOTの手番なら、BOTも同じ流れで置く。
+pub async fn place<G: Game>(
+    discord: &impl Discord,
+    channel_id: u64,
+    user_id: UserId,
+    row: usize,
+    column: usize,
+    action: &str,
+    locale: Locale,
+) -> Result<CreateInteractionResponseMessage, BotError> {
+    let sessions = get_sessions::<G>(discord).await?;
+    let shared_session = find_session(&sessions, channel_id).await?;
+
+    // 名前と設定は試合のロックを離してから調べる (対戦者は試合の途中で変わらない)
+    let user_ids = shared_session.lock().await.user_ids();
+    let names = names(discord, user_ids).await;
+    // 画像は置いた人の設定で描く
+    let style = get_user_settings(discord, user_id).await.board_style();
+
+    // 手を進めて返信に要るものを写すまで、この試合だけをロックしておく
+    let mut session = shared_session.lock().await;
+
+    let played = session.play(user_id, row, column, action)?;
+
+    // 画像は描くのに要るものだけを写しておき、ロックを離してから描く
+    let report_render = played
+        .report
+        .as_ref()
+        .map(|report| session.game.render_report(report, &names, locale, style));
+    let time_over_text = played
+        .time_over
+        .map(|player| Text::TimeOver.format(locale, &[("name", names.of(player))]));
+
+    let (ai_text, ai_render) = match &played.ai_move {
+        Some(AiMove::Placed {
+            player,
+            label,
+            report,
+        }) => (
+            Some(Text::AiPlaced.format(locale, &[("name", names.of(*player)), ("square", label)])),
+            Some(session.game.render_report(report, &names, locale, style)),
+        ),
+        Some(AiMove::NoSquare { player }) => (
+            Some(Text::AiNoSquare.format(locale, &[("name", names.of(*player))])),
+            None,
+        ),
+        None => (None, None),
     };
 
-    let (push_row, push_column) = match (push_row, push_column) {
-        (Some(row), Some(column)) => (row, column),
-        _ => {
-            cant_notice(ctx, interaction, "行と列を選択してください。").await;
-            return;
-        }
+    let status = match played.outcome {
+        Some(outcome) => session.game.outcome_text(outcome, &names, locale),
+        None => turn_info(&session.game, &names, locale),
     };
 
-    {
-        let mut data = ctx.data.write().await;
+    let board_render = session.game.render(style);
 
-        let reversi_stats = data
-            .get_mut::<GlobalReversiStats>()
-            .expect("Expected GlobalReversiStats in TypeMap.");
-        let mut reversi_stats = reversi_stats.lock().await;
+    let components = match played.outcome {
+        Some(_) => {
+            remove_session(&sessions, channel_id, &shared_session).await;
 
-        let channel_id_u64 = interaction.channel_id.get();
-        let channel_stats = reversi_stats.get_mut(&channel_id_u64).unwrap();
-        let reversi = &mut channel_stats.reversi;
+            // 決着した試合は「解析」ボタンを押したときのために残しておく
+            match G::HAS_ANALYSIS {
+                true => {
+                    let finished = get_finished_sessions::<G>(discord).await?;
+                    let id = finished
+                        .lock()
+                        .await
+                        .push(channel_id, shared_session.clone());
 
-        match reversi.do_place(push_row as i32, push_column as i32, reversi.turn_stone) {
-            Ok(_) => (),
-            Err(_) => {
-                cant_notice(ctx, interaction, "そのマスには置けません。").await;
-                return;
+                    analysis_components::<G>(id, locale)
+                }
+                false => vec![],
             }
         }
+        None => session.components(locale),
+    };
 
-        if reversi.is_game_end() {
-            let board = reversi.print_board();
-
-            let mut black_count = 0;
-            let mut white_count = 0;
+    drop(session);
 
-            for row in 0..8 {
-                for column in 0..8 {
-                    match reversi.board[row][column] {
-                        Stone::BLACK => black_count += 1,
-                        Stone::WHITE => white_count += 1,
-                        Stone::NONE => (),
-                    }
-                }
+    let (rendered, ai_rendered) = tokio::join!(
+        async {
+            match report_render {
+                Some(report_render) => Some(report_render.await),
+                None => None,
             }
-
-            let winner = if black_count > white_count {
-                format!(
-                    "Black: {}",
-                    get_username(ctx, &channel_stats.black_user.as_ref().unwrap().id).await
-                )
-            } else if black_count < white_count {
-                format!(
-                    "White: {}",
-                    get_username(ctx, &channel_stats.white_user.as_ref().unwrap().id).await
-                )
-            } else {
-                "Draw".to_string()
-            };
-
-            let content = "試合が終了しました。".to_string();
-            let winnerinfo = format!(
-                "Black: {}\nWhite: {}\nWinner: {}",
-                black_count, white_count, winner
-            );
-
-            let data = CreateInteractionResponseMessage::new()
-                .content(format!("{}\n{}\n{}", content, board, winnerinfo))
-                .components(vec![]);
-
-            let builder = CreateInteractionResponse::UpdateMessage(data);
-            if let Err(why) = interaction.create_response(&ctx.http, builder).await {
-                println!("Cannot respond to slash command: {why}");
+        },
+        async {
+            match ai_render {
+                Some(ai_render) => Some(ai_render.await),
+                None => None,
             }
-
-            reversi_stats.remove(&channel_id_u64);
-            return;
         }
+    );
 
-        reversi.switch_turn();
-        let mut board = reversi.print_board();
+    // 最後の一手の画像 (観測の前後を並べたものなど) に今の盤面が描いてあれば、盤面の画像は描かない
+    let replaces_board = match (&rendered, &ai_rendered) {
+        (_, Some(ai_rendered)) => ai_rendered.replaces_board,
+        (Some(rendered), None) => rendered.replaces_board,
+        (None, None) => false,
+    };
+    let board = match replaces_board {
+        true => Rendered::default(),
+        false => board_render.await,
+    };
 
-        let mut content = String::new();
+    let mut texts = vec![];
+    let mut files = vec![];
 
-        if !(reversi.player_can_place(reversi.turn_stone)) {
-            reversi.switch_turn();
-            board = reversi.print_board();
-            content =
-                "置けるマスがなかったためもう一度同じ人のターンです。\n".to_string() + &content;
-        }
+    texts.extend(time_over_text);
+    if let Some(rendered) = rendered {
+        push_rendered(&mut texts, &mut files, rendered, "");
+    }
+    texts.extend(ai_text);
+    if let Some(rendered) = ai_rendered {
+        push_rendered(&mut texts, &mut files, rendered, "ai_");
+    }
+    texts.push(status);
 
-        let turn_info = match reversi.turn_stone {
-            Stone::BLACK => format!(
-                "現在 🔵 : {} の番です。",
-                get_username(ctx, &channel_stats.black_user.as_ref().unwrap().id).await
-            ),
-            Stone::WHITE => format!(
-                "現在 ⚪ : {} の番です。",
-                get_username(ctx, &channel_stats.white_user.as_ref().unwrap().id).await
-            ),
-            Stone::NONE => panic!("NoneError"),
-        };
-
-        content += &turn_info;
-
-        let data = CreateInteractionResponseMessage::new().content(content + "\n\n" + &board);
-
-        let builder = CreateInteractionResponse::UpdateMessage(data);
-        if let Err(why) = interaction.create_response(&ctx.http, builder).await {
-            println!("Cannot respond to slash command: {why}");
-        }
+    let mut content = texts.join("\n");
+    if let Some(text) = board.text {
+        content = content + "\n\n" + &text;
     }
-}
 
-async fn cant_notice(ctx: &Context, interaction: &ComponentInteraction, content: &str) {
-    let data = CreateInteractionResponseMessage::new()
+    let mut data = CreateInteractionResponseMessage::new()
         .content(content)
-        .ephemeral(true);
+        .components(components);
 
-    let builder = CreateInteractionResponse::Message(data);
-    if let Err(why) = interaction.create_response(&ctx.http, builder).await {
-        if why.to_string() == "Cannot send an empty message" {
-            return;
-        }
-        println!("Cannot respond to slash command: {why}");
+    for (filename, bytes) in board.files.into_iter().chain(files) {
+        data = data.add_file(CreateAttachment::bytes(Cow::from(bytes), filename));
     }
+
+    return Ok(data);
 }
 
-async fn get_username(ctx: &Context, user_id: &UserId) -> String {
-    match user_id.to_user(&ctx.http).await {
-        Ok(user) => user.name,
-        Err(_) => "None".to_string(),
+/// 「現在 🔵 : name の番です。」と手番の説明
+pub fn turn_info<G: Game>(game: &G, names: &Names, locale: Locale) -> String {
+    let player = game.current_player();
+    let mut turn_info = Text::TurnInfo.format(
+        locale,
+        &[("emoji", player.emoji()), ("name", names.of(player))],
+    );
+
+    if let Some(note) = game.turn_note(locale) {
+        turn_info += "\n";
+        turn_info += &note;
+    }
+
+    return turn_info;
+}
+
+fn push_rendered(
+    texts: &mut Vec<String>,
+    files: &mut Vec<(String, Vec<u8>)>,
+    rendered: Rendered,
+    filename_prefix: &str,
+) {
+    if let Some(text) = rendered.text {
+        texts.push(text);
+    }
+
+    for (filename, bytes) in rendered.files {
+        files.push((format!("{}{}", filename_prefix, filename), bytes));
     }
 }
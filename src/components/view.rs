@@ -0,0 +1,48 @@
+use std::borrow::Cow;
+
+use serenity::all::{
+    CreateAttachment, CreateInteractionResponse, CreateInteractionResponseMessage,
+};
+
+use crate::discord::{Discord, Input};
+use crate::error::BotError;
+use crate::game::session::find_session;
+use crate::game::Game;
+use crate::global_data::{get_sessions, get_user_settings};
+
+/// 盤面を別の描き方で見るボタン (custom_id は `{ID}_view_{view}`) が押されたとき
+///
+/// 試合のメッセージは変えず、押した人にだけ見える返信で画像を送る。手番でなくても押せる。
+pub async fn run<G: Game>(
+    discord: &impl Discord,
+    input: &Input,
+    view: &str,
+) -> Result<CreateInteractionResponse, BotError> {
+    if !G::VIEWS.iter().any(|v| v.id == view) {
+        return Err(BotError::UnexpectedInteraction(input.custom_id.clone()));
+    }
+
+    // 画像は押した人の設定で描く
+    let style = get_user_settings(discord, input.user_id)
+        .await
+        .board_style();
+
+    let render = {
+        let sessions = get_sessions::<G>(discord).await?;
+        let session = find_session(&sessions, input.channel_id).await?;
+        let session = session.lock().await;
+
+        session.game.render_view(view, input.locale, style)
+    };
+    let rendered = render.await;
+
+    let mut data = CreateInteractionResponseMessage::new().ephemeral(true);
+    if let Some(text) = rendered.text {
+        data = data.content(text);
+    }
+    for (filename, bytes) in rendered.files {
+        data = data.add_file(CreateAttachment::bytes(Cow::from(bytes), filename));
+    }
+
+    return Ok(CreateInteractionResponse::Message(data));
+}
@@ -1,18 +1,273 @@
-use std::{fs::File, io::BufReader};
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
 
-use serde::{Deserialize, Serialize};
-use serde_json::Result;
+use serde::Deserialize;
 
-#[derive(Serialize, Deserialize)]
-struct Token {
-    token: String,
+use crate::i18n::Locale;
+use crate::render::encode::{ImageOutput, FULL_WIDTH, MIN_WIDTH};
+
+/// トークンを上書きする環境変数
+pub const TOKEN_ENV: &str = "DISCORD_TOKEN";
+
+/// `config.json` の設定
+///
+/// `token` 以外は省略でき、省略したときは既定値になる。
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// BOTのトークン (環境変数 `DISCORD_TOKEN` があればそちらを使う)
+    #[serde(default)]
+    pub token: String,
+    /// コマンドをすぐに反映させたいサーバーのID (開発用)
+    #[serde(default)]
+    pub guild_ids: Vec<u64>,
+    /// 試合の持ち時間 (すべての試合で使う)
+    #[serde(default)]
+    pub time_control: TimeControl,
+    /// 五目並べの画像の文字に使うフォント
+    #[serde(default = "default_font_path")]
+    pub font_path: PathBuf,
+    /// 利用者ごとの設定 (`settings.json`) を保存するフォルダ
+    #[serde(default = "default_storage_path")]
+    pub storage_path: PathBuf,
+    /// Discord の言語が日本語・英語以外の人に使う言語
+    #[serde(default)]
+    pub locale: Locale,
+    #[serde(default)]
+    pub games: GameToggles,
+    /// 盤面の画像の形式と幅
+    #[serde(default)]
+    pub image: ImageOutput,
 }
 
-pub fn get_token(file_path: &str) -> Result<String> {
-    let file = File::open(file_path).unwrap();
+/// 持ち時間 (None は無制限、切れた人の負けになる)
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct TimeControl {
+    /// 1手あたりの秒数
+    pub move_seconds: Option<u64>,
+    /// 1試合で1人が使える分数
+    pub game_minutes: Option<u64>,
+}
+
+/// ゲームごとの有効・無効 (無効なゲームのコマンドは登録しない)
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(deny_unknown_fields, default)]
+pub struct GameToggles {
+    pub reversi: bool,
+    pub quantum_gomoku: bool,
+    pub quantum_reversi: bool,
+    pub gomoku: bool,
+}
+
+impl Default for GameToggles {
+    fn default() -> Self {
+        GameToggles {
+            reversi: true,
+            quantum_gomoku: true,
+            quantum_reversi: true,
+            gomoku: true,
+        }
+    }
+}
+
+fn default_font_path() -> PathBuf {
+    PathBuf::from("font/font.ttf")
+}
+
+fn default_storage_path() -> PathBuf {
+    PathBuf::from("data")
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    /// ファイルを読めない
+    Read(PathBuf, std::io::Error),
+    /// JSON として正しくない、または値の型が違う
+    Parse(PathBuf, serde_json::Error),
+    /// ファイルにも環境変数にもトークンがない
+    MissingToken(PathBuf),
+    /// 値の範囲が正しくない
+    Invalid(PathBuf, &'static str, String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Read(path, why) => {
+                write!(f, "cannot read '{}': {}", path.display(), why)
+            }
+            ConfigError::Parse(path, why) => {
+                write!(f, "'{}' is invalid: {}", path.display(), why)
+            }
+            ConfigError::MissingToken(path) => write!(
+                f,
+                "'token' is empty in '{}'. Set it there or in the {} environment variable.",
+                path.display(),
+                TOKEN_ENV
+            ),
+            ConfigError::Invalid(path, field, reason) => {
+                write!(f, "'{}' in '{}' {}", field, path.display(), reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Config {
+    /// 設定ファイルを読み、環境変数のトークンがあれば上書きする
+    pub fn load(file_path: &str) -> Result<Config, ConfigError> {
+        let path = PathBuf::from(file_path);
+        let text = fs::read_to_string(&path).map_err(|why| ConfigError::Read(path.clone(), why))?;
+
+        Config::parse(&text, std::env::var(TOKEN_ENV).ok(), path)
+    }
+
+    fn parse(text: &str, env_token: Option<String>, path: PathBuf) -> Result<Config, ConfigError> {
+        let mut config: Config =
+            serde_json::from_str(text).map_err(|why| ConfigError::Parse(path.clone(), why))?;
+
+        if let Some(token) = env_token.filter(|token| !token.is_empty()) {
+            config.token = token;
+        }
+
+        if config.token.trim().is_empty() {
+            return Err(ConfigError::MissingToken(path));
+        }
+
+        if config.time_control.move_seconds == Some(0) {
+            return Err(ConfigError::Invalid(
+                path,
+                "time_control.move_seconds",
+                "must be greater than 0".to_string(),
+            ));
+        }
+        if config.time_control.game_minutes == Some(0) {
+            return Err(ConfigError::Invalid(
+                path,
+                "time_control.game_minutes",
+                "must be greater than 0".to_string(),
+            ));
+        }
+
+        if !(MIN_WIDTH..=FULL_WIDTH).contains(&config.image.width) {
+            return Err(ConfigError::Invalid(
+                path,
+                "image.width",
+                format!("must be between {} and {}", MIN_WIDTH, FULL_WIDTH),
+            ));
+        }
+
+        return Ok(config);
+    }
+
+    /// 起動時のログ用 (トークンは出さない)
+    pub fn summary(&self) -> String {
+        format!(
+            "guild_ids: {:?}, time_control: {:?}s/move {:?}min/game, font_path: {}, storage_path: {}, locale: {:?}, games: {:?}, image: {:?}",
+            self.guild_ids,
+            self.time_control.move_seconds,
+            self.time_control.game_minutes,
+            self.font_path.display(),
+            self.storage_path.display(),
+            self.locale,
+            self.games,
+            self.image
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::encode::OutputFormat;
+
+    fn parse(text: &str, env_token: Option<&str>) -> Result<Config, ConfigError> {
+        Config::parse(
+            text,
+            env_token.map(str::to_string),
+            PathBuf::from("config.json"),
+        )
+    }
+
+    #[test]
+    fn defaults() {
+        let config = parse(r#"{ "token": "abc" }"#, None).unwrap();
+
+        assert_eq!("abc", config.token);
+        assert!(config.guild_ids.is_empty());
+        assert_eq!(Locale::Ja, config.locale);
+        assert_eq!(PathBuf::from("font/font.ttf"), config.font_path);
+        assert!(config.games.reversi && config.games.quantum_gomoku && config.games.gomoku);
+        assert!(config.games.quantum_reversi);
+        assert_eq!(None, config.time_control.move_seconds);
+        assert_eq!(ImageOutput::default(), config.image);
+    }
+
+    #[test]
+    fn full() {
+        let config = parse(
+            r#"{
+                "token": "abc",
+                "guild_ids": [123],
+                "time_control": { "move_seconds": 60 },
+                "locale": "en",
+                "games": { "gomoku": false },
+                "image": { "format": "webp", "width": 600 }
+            }"#,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(vec![123], config.guild_ids);
+        assert_eq!(Some(60), config.time_control.move_seconds);
+        assert_eq!(Locale::En, config.locale);
+        assert!(config.games.reversi && !config.games.gomoku);
+        assert_eq!(OutputFormat::Webp, config.image.format);
+        assert_eq!(600, config.image.width);
+    }
+
+    #[test]
+    fn token_from_env() {
+        let config = parse(r#"{}"#, Some("from_env")).unwrap();
+        assert_eq!("from_env", config.token);
 
-    let reader = BufReader::new(file);
-    let config: Token = serde_json::from_reader(reader).unwrap();
+        let config = parse(r#"{ "token": "abc" }"#, Some("from_env")).unwrap();
+        assert_eq!("from_env", config.token);
+    }
 
-    Ok(config.token)
+    #[test]
+    fn errors() {
+        assert!(matches!(
+            parse(r#"{ "token": "" }"#, None),
+            Err(ConfigError::MissingToken(_))
+        ));
+        assert!(matches!(
+            parse(r#"{ "token": "abc", "locale": "fr" }"#, None),
+            Err(ConfigError::Parse(..))
+        ));
+        assert!(matches!(
+            parse(r#"{ "token": "abc", "tokn": "abc" }"#, None),
+            Err(ConfigError::Parse(..))
+        ));
+        assert!(matches!(
+            parse(
+                r#"{ "token": "abc", "time_control": { "move_seconds": 0 } }"#,
+                None
+            ),
+            Err(ConfigError::Invalid(_, "time_control.move_seconds", _))
+        ));
+        let error = parse(r#"{ "token": "abc", "image": { "width": 100 } }"#, None).unwrap_err();
+        assert!(matches!(error, ConfigError::Invalid(_, "image.width", _)));
+        assert_eq!(
+            "'image.width' in 'config.json' must be between 200 and 850",
+            error.to_string()
+        );
+        assert!(matches!(
+            parse(r#"{ "token": "abc", "image": { "format": "gif" } }"#, None),
+            Err(ConfigError::Parse(..))
+        ));
+    }
 }
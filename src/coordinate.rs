@@ -0,0 +1,38 @@
+/// "D3" のような座標の文字列を (アルファベットの番号, 数字 - 1) に変換する
+///
+/// `alphabets` は盤面で使うアルファベット ("ABCDEFGH" など) で、数字も同じ数まで使える。
+pub fn parse_coordinate(text: &str, alphabets: &str) -> Option<(usize, usize)> {
+    let text = text.trim().to_uppercase();
+
+    let mut chars = text.chars();
+    let alphabet = chars.next()?;
+    let number = chars.as_str().trim().parse::<usize>().ok()?;
+
+    let alphabet_index = alphabets.find(alphabet)?;
+
+    if !(1..=alphabets.len()).contains(&number) {
+        return None;
+    }
+
+    return Some((alphabet_index, number - 1));
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn parse_coordinate() {
+        assert_eq!(Some((3, 2)), super::parse_coordinate("d3", "ABCDEFGH"));
+        assert_eq!(Some((3, 2)), super::parse_coordinate(" D 3 ", "ABCDEFGH"));
+        assert_eq!(
+            Some((14, 14)),
+            super::parse_coordinate("O15", "ABCDEFGHIJKLMNO")
+        );
+
+        assert_eq!(None, super::parse_coordinate("", "ABCDEFGH"));
+        assert_eq!(None, super::parse_coordinate("D", "ABCDEFGH"));
+        assert_eq!(None, super::parse_coordinate("I3", "ABCDEFGH"));
+        assert_eq!(None, super::parse_coordinate("D9", "ABCDEFGH"));
+        assert_eq!(None, super::parse_coordinate("D0", "ABCDEFGH"));
+        assert_eq!(None, super::parse_coordinate("3D", "ABCDEFGH"));
+    }
+}
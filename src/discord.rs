@@ -0,0 +1,632 @@
+use std::future::Future;
+use std::sync::Arc;
+
+use serenity::all::{
+    ActionRowComponent, Command, ComponentInteraction, ComponentInteractionDataKind, Context,
+    CreateCommand, GuildId, ModalInteraction, UserId,
+};
+use serenity::prelude::{RwLock, TypeMap};
+
+use crate::i18n::Locale;
+
+/// コマンド・コンポーネントの処理が使う Discord 側の機能
+///
+/// 本番では serenity の `Context`、テストではネットワークを使わない `FakeDiscord` を使う。
+/// 返信は処理の戻り値 (`CreateInteractionResponse`) として返し、送るのは呼び出し側。
+pub trait Discord: Send + Sync {
+    /// 起動時に入れた試合の表など
+    fn data(&self) -> &Arc<RwLock<TypeMap>>;
+
+    /// このBOT自身のユーザーID
+    fn current_user_id(&self) -> UserId;
+
+    /// ユーザー名 (取得できなければ "None")
+    fn username(&self, user_id: UserId) -> impl Future<Output = String> + Send;
+
+    /// このBOTが入っているサーバー
+    fn guild_ids(&self) -> Vec<GuildId>;
+
+    /// 登録済みのコマンドの (コマンド名, バージョン) (`guild_id` が None ならグローバル)
+    fn commands(
+        &self,
+        guild_id: Option<GuildId>,
+    ) -> impl Future<Output = Result<Vec<(String, u64)>, serenity::Error>> + Send;
+
+    /// コマンドを一括で上書き登録し、登録後の (コマンド名, バージョン) を返す
+    fn set_commands(
+        &self,
+        guild_id: Option<GuildId>,
+        definitions: Vec<CreateCommand>,
+    ) -> impl Future<Output = Result<Vec<(String, u64)>, serenity::Error>> + Send;
+}
+
+fn versions(commands: &[Command]) -> Vec<(String, u64)> {
+    commands
+        .iter()
+        .map(|command| (command.name.clone(), command.version.get()))
+        .collect()
+}
+
+impl Discord for Context {
+    fn data(&self) -> &Arc<RwLock<TypeMap>> {
+        &self.data
+    }
+
+    fn current_user_id(&self) -> UserId {
+        self.cache.current_user().id
+    }
+
+    async fn username(&self, user_id: UserId) -> String {
+        match user_id.to_user(&self.http).await {
+            Ok(user) => user.name,
+            Err(_) => "None".to_string(),
+        }
+    }
+
+    fn guild_ids(&self) -> Vec<GuildId> {
+        self.cache.guilds()
+    }
+
+    async fn commands(
+        &self,
+        guild_id: Option<GuildId>,
+    ) -> Result<Vec<(String, u64)>, serenity::Error> {
+        let commands = match guild_id {
+            Some(guild_id) => guild_id.get_commands(&self.http).await?,
+            None => Command::get_global_commands(&self.http).await?,
+        };
+
+        Ok(versions(&commands))
+    }
+
+    async fn set_commands(
+        &self,
+        guild_id: Option<GuildId>,
+        definitions: Vec<CreateCommand>,
+    ) -> Result<Vec<(String, u64)>, serenity::Error> {
+        let commands = match guild_id {
+            Some(guild_id) => guild_id.set_commands(&self.http, definitions).await?,
+            None => Command::set_global_commands(&self.http, definitions).await?,
+        };
+
+        Ok(versions(&commands))
+    }
+}
+
+/// コンポーネント・入力欄のインタラクションのうち、処理に使う部分
+pub struct Input {
+    pub user_id: UserId,
+    pub channel_id: u64,
+    pub custom_id: String,
+    /// セレクトメニューで選んだ値、または入力欄に入力された文字列
+    pub values: Vec<String>,
+    /// 操作した人の言語
+    pub locale: Locale,
+}
+
+impl Input {
+    /// `default_locale` は日本語・英語以外の人に使う言語
+    pub fn from_component(interaction: &ComponentInteraction, default_locale: Locale) -> Self {
+        let values = match &interaction.data.kind {
+            ComponentInteractionDataKind::StringSelect { values } => values.clone(),
+            _ => vec![],
+        };
+
+        Input {
+            user_id: interaction.user.id,
+            channel_id: interaction.channel_id.get(),
+            custom_id: interaction.data.custom_id.clone(),
+            values,
+            locale: Locale::pick(&interaction.locale, default_locale),
+        }
+    }
+
+    pub fn from_modal(interaction: &ModalInteraction, default_locale: Locale) -> Self {
+        let values = interaction
+            .data
+            .components
+            .iter()
+            .flat_map(|row| row.components.iter())
+            .filter_map(|component| match component {
+                ActionRowComponent::InputText(input_text) => input_text.value.clone(),
+                _ => None,
+            })
+            .collect();
+
+        Input {
+            user_id: interaction.user.id,
+            channel_id: interaction.channel_id.get(),
+            custom_id: interaction.data.custom_id.clone(),
+            values,
+            locale: Locale::pick(&interaction.locale, default_locale),
+        }
+    }
+}
+
+/// ネットワークを使わない `Discord` (テスト用)
+#[cfg(test)]
+pub struct FakeDiscord {
+    data: Arc<RwLock<TypeMap>>,
+    bot_id: UserId,
+    names: std::collections::HashMap<UserId, String>,
+    guild_ids: Vec<GuildId>,
+    /// 登録済みのコマンド名 (`None` はグローバル)
+    commands: std::sync::Mutex<std::collections::HashMap<Option<GuildId>, Vec<String>>>,
+    /// `set_commands` を呼んだ順の登録先
+    set_calls: std::sync::Mutex<Vec<Option<GuildId>>>,
+}
+
+#[cfg(test)]
+impl FakeDiscord {
+    /// 4つのゲームの試合の表と、リバーシの決着した試合の表と、保存しない設定を入れた状態で作る
+    pub fn new(bot_id: UserId, names: &[(UserId, &str)]) -> Self {
+        use crate::global_data::{GlobalFinishedSessions, GlobalSessions, GlobalSettings};
+        use crate::gomoku::gomoku::Gomoku;
+        use crate::quantum_gomoku::gomoku::QuantumGomoku;
+        use crate::quantum_reversi::reversi::QuantumReversi;
+        use crate::reversi::reversi::Reversi;
+        use crate::settings::Settings;
+
+        let mut data = TypeMap::new();
+        data.insert::<GlobalSessions<Reversi>>(Default::default());
+        data.insert::<GlobalSessions<QuantumGomoku>>(Default::default());
+        data.insert::<GlobalSessions<QuantumReversi>>(Default::default());
+        data.insert::<GlobalSessions<Gomoku>>(Default::default());
+        data.insert::<GlobalFinishedSessions<Reversi>>(Default::default());
+        data.insert::<GlobalSettings>(Arc::new(RwLock::new(Settings::in_memory())));
+
+        FakeDiscord {
+            data: Arc::new(RwLock::new(data)),
+            bot_id,
+            names: names
+                .iter()
+                .map(|(id, name)| (*id, name.to_string()))
+                .collect(),
+            guild_ids: vec![],
+            commands: Default::default(),
+            set_calls: Default::default(),
+        }
+    }
+
+    /// BOTが `guild_ids` のサーバーに入っている状態にする
+    pub fn with_guilds(mut self, guild_ids: &[u64]) -> Self {
+        self.guild_ids = guild_ids.iter().map(|id| GuildId::new(*id)).collect();
+        self
+    }
+
+    /// 登録済みのコマンド名 (`guild_id` が None ならグローバル)
+    pub fn command_names(&self, guild_id: Option<GuildId>) -> Vec<String> {
+        let commands = self.commands.lock().unwrap();
+        commands.get(&guild_id).cloned().unwrap_or_default()
+    }
+
+    /// `set_commands` を呼んだ順の登録先を取り出す
+    pub fn take_set_calls(&self) -> Vec<Option<GuildId>> {
+        std::mem::take(&mut *self.set_calls.lock().unwrap())
+    }
+}
+
+#[cfg(test)]
+impl Discord for FakeDiscord {
+    fn data(&self) -> &Arc<RwLock<TypeMap>> {
+        &self.data
+    }
+
+    fn current_user_id(&self) -> UserId {
+        self.bot_id
+    }
+
+    async fn username(&self, user_id: UserId) -> String {
+        self.names
+            .get(&user_id)
+            .cloned()
+            .unwrap_or_else(|| "None".to_string())
+    }
+
+    fn guild_ids(&self) -> Vec<GuildId> {
+        self.guild_ids.clone()
+    }
+
+    async fn commands(
+        &self,
+        guild_id: Option<GuildId>,
+    ) -> Result<Vec<(String, u64)>, serenity::Error> {
+        let names = self.command_names(guild_id);
+        Ok(names.into_iter().map(|name| (name, 1)).collect())
+    }
+
+    async fn set_commands(
+        &self,
+        guild_id: Option<GuildId>,
+        definitions: Vec<CreateCommand>,
+    ) -> Result<Vec<(String, u64)>, serenity::Error> {
+        let names = definitions
+            .iter()
+            .map(|definition| {
+                let json = serde_json::to_value(definition).unwrap();
+                json["name"].as_str().unwrap().to_string()
+            })
+            .collect::<Vec<_>>();
+
+        self.set_calls.lock().unwrap().push(guild_id);
+        self.commands.lock().unwrap().insert(guild_id, names);
+
+        self.commands(guild_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+    use serenity::all::CreateInteractionResponse;
+
+    use super::*;
+    use crate::commands::{game_end, game_start, settings};
+    use crate::components;
+    use crate::game::session::InputMode;
+    use crate::global_data::{get_sessions, get_user_settings};
+    use crate::quantum_gomoku::gomoku::QuantumGomoku;
+    use crate::quantum_reversi::reversi::QuantumReversi;
+    use crate::render::theme::Theme;
+    use crate::reversi::reversi::Reversi;
+
+    const CHANNEL: u64 = 10;
+    const BOT: UserId = UserId::new(1);
+    const ALICE: UserId = UserId::new(2);
+    const BOB: UserId = UserId::new(3);
+
+    fn fake() -> FakeDiscord {
+        FakeDiscord::new(BOT, &[(BOT, "bot"), (ALICE, "alice"), (BOB, "bob")])
+    }
+
+    fn input(user_id: UserId, custom_id: &str, values: &[&str]) -> Input {
+        Input {
+            user_id,
+            channel_id: CHANNEL,
+            custom_id: custom_id.to_string(),
+            values: values.iter().map(|value| value.to_string()).collect(),
+            locale: Locale::Ja,
+        }
+    }
+
+    /// 送られるはずの JSON にして中身を確かめる
+    fn json(builder: CreateInteractionResponse) -> Value {
+        serde_json::to_value(builder).unwrap()
+    }
+
+    fn content(response: &Value) -> &str {
+        response["data"]["content"].as_str().unwrap_or_default()
+    }
+
+    fn custom_ids(response: &Value) -> Vec<String> {
+        let mut custom_ids = vec![];
+
+        for row in response["data"]["components"]
+            .as_array()
+            .into_iter()
+            .flatten()
+        {
+            for component in row["components"].as_array().into_iter().flatten() {
+                custom_ids.push(component["custom_id"].as_str().unwrap().to_string());
+            }
+        }
+
+        return custom_ids;
+    }
+
+    fn filenames(response: &Value) -> Vec<String> {
+        response["data"]["attachments"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|attachment| attachment["filename"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    async fn click(discord: &FakeDiscord, input: Input) -> Value {
+        json(components::component(discord, &input).await.unwrap())
+    }
+
+    #[tokio::test]
+    async fn reversi_full_game() {
+        let discord = fake();
+
+        let mut response = json(CreateInteractionResponse::Message(
+            game_start::start::<Reversi>(
+                &discord,
+                CHANNEL,
+                ALICE,
+                (BOB, false),
+                InputMode::Buttons,
+                &[],
+                Locale::Ja,
+            )
+            .await,
+        ));
+        assert!(content(&response).contains("現在 🔵 : alice の番です。"));
+
+        // 手番でない人のボタンは本人にだけエラーを返す
+        let wrong_turn = click(&discord, input(BOB, "reversi_place_D3", &[])).await;
+        assert_eq!(4, wrong_turn["type"]);
+        assert_eq!("手番ではないため置けません。", content(&wrong_turn));
+
+        // 1手目は入力欄から置く
+        let modal = click(&discord, input(ALICE, "reversi_input_push_stone", &[])).await;
+        assert_eq!(9, modal["type"]);
+        assert_eq!("reversi_input_push_stone_modal", modal["data"]["custom_id"]);
+
+        let submit = input(ALICE, "reversi_input_push_stone_modal", &["D3"]);
+        response = json(components::modal(&discord, &submit).await.unwrap());
+        assert_eq!(7, response["type"]);
+        assert!(content(&response).contains("現在 ⚪ : bob の番です。"));
+
+        // あとはお互いに最初のボタンを押し続ける
+        for _ in 0..64 {
+            if content(&response).contains("試合が終了しました。") {
+                break;
+            }
+
+            let user_id = if content(&response).contains("現在 🔵") {
+                ALICE
+            } else {
+                BOB
+            };
+            let square = custom_ids(&response)
+                .into_iter()
+                .find(|custom_id| custom_id.starts_with("reversi_place_"))
+                .expect("no square button");
+
+            response = click(&discord, input(user_id, &square, &[])).await;
+            assert_eq!(7, response["type"], "{}", content(&response));
+        }
+
+        assert!(content(&response).contains("試合が終了しました。"));
+
+        // 決着したメッセージには「解析」ボタンだけが残る
+        let analyze = custom_ids(&response);
+        assert_eq!(1, analyze.len());
+        assert!(analyze[0].starts_with("reversi_analyze_"));
+
+        let after_end = click(&discord, input(ALICE, "reversi_place_D3", &[])).await;
+        assert_eq!("試合は行われていません。", content(&after_end));
+
+        // 対戦者でなくても解析できる
+        let analysis = click(&discord, input(BOT, &analyze[0], &[])).await;
+        assert_eq!(4, analysis["type"]);
+        let embed = &analysis["data"]["embeds"][0];
+        assert_eq!("試合の解析", embed["title"]);
+        assert_eq!("attachment://analysis.png", embed["image"]["url"]);
+        assert_eq!("🔵 alice", embed["fields"][0]["name"]);
+        assert!(embed["fields"][0]["value"]
+            .as_str()
+            .unwrap()
+            .starts_with("正確さ: "));
+        assert_eq!("⚪ bob", embed["fields"][1]["name"]);
+        assert_eq!(vec!["analysis.png"], filenames(&analysis));
+
+        let expired = click(&discord, input(ALICE, "reversi_analyze_0", &[])).await;
+        assert_eq!(64, expired["data"]["flags"]);
+        assert!(content(&expired).starts_with("解析できる試合が見つかりません。"));
+    }
+
+    #[tokio::test]
+    async fn quantum_gomoku_against_ai() {
+        let discord = fake();
+
+        let response = json(CreateInteractionResponse::Message(
+            game_start::start::<QuantumGomoku>(
+                &discord,
+                CHANNEL,
+                ALICE,
+                (BOT, true),
+                InputMode::Menus,
+                &[],
+                Locale::Ja,
+            )
+            .await,
+        ));
+        assert_eq!(vec!["board.png"], filenames(&response));
+        assert!(custom_ids(&response).contains(&"qgomoku_push_stone_observe".to_string()));
+
+        let mut ended = false;
+
+        'squares: for alphabet in "ABCDEFGHIJKLMNO".chars() {
+            for number in 1..=15 {
+                let alphabet = alphabet.to_string();
+                let number = number.to_string();
+
+                let choiced = [
+                    ("qgomoku_choice_alphabet", alphabet.as_str()),
+                    ("qgomoku_choice_number", number.as_str()),
+                ];
+                for (custom_id, value) in choiced {
+                    let ack = click(&discord, input(ALICE, custom_id, &[value])).await;
+                    assert_eq!(6, ack["type"]);
+                }
+
+                let response =
+                    click(&discord, input(ALICE, "qgomoku_push_stone_observe", &[])).await;
+
+                // BOTが置いたマスなど
+                if response["type"] == 4 {
+                    assert_eq!("そのマスには置けません。", content(&response));
+                    continue;
+                }
+
+                // 観測の前後を並べた画像
+                assert!(filenames(&response).contains(&"observation.png".to_string()));
+
+                if custom_ids(&response).is_empty() {
+                    assert!(content(&response).contains("の勝利です。"));
+                    // 揃った盤面は観測の画像に描いてあるので、盤面の画像は付けない
+                    assert!(!filenames(&response).contains(&"board.png".to_string()));
+                    ended = true;
+                    break 'squares;
+                }
+
+                assert!(content(&response).contains("🤖 bot は "));
+                assert!(content(&response).contains("現在 🔵 : alice の番です。"));
+            }
+        }
+
+        assert!(ended);
+
+        let end = game_end::end::<QuantumGomoku>(&discord, CHANNEL, Locale::Ja).await;
+        assert_eq!(
+            "試合は行われていません。",
+            content(&json(CreateInteractionResponse::Message(end)))
+        );
+    }
+
+    #[tokio::test]
+    async fn english_messages() {
+        let discord = fake();
+
+        let response = json(CreateInteractionResponse::Message(
+            game_start::start::<Reversi>(
+                &discord,
+                CHANNEL,
+                ALICE,
+                (BOB, false),
+                InputMode::Menus,
+                &[],
+                Locale::En,
+            )
+            .await,
+        ));
+        assert!(content(&response).contains("Now 🔵 : alice's turn."));
+        assert_eq!(
+            "Select a row",
+            response["data"]["components"][0]["components"][0]["placeholder"]
+        );
+
+        // 同じ試合でも、押した人の言語で返す
+        let wrong_turn = Input {
+            locale: Locale::En,
+            ..input(BOB, "reversi_push_stone", &[])
+        };
+        let wrong_turn = click(&discord, wrong_turn).await;
+        assert_eq!("It is not your turn.", content(&wrong_turn));
+
+        let not_choiced = click(&discord, input(ALICE, "reversi_push_stone", &[])).await;
+        assert_eq!("行と列を選択してください。", content(&not_choiced));
+    }
+
+    #[tokio::test]
+    async fn settings_theme() {
+        let discord = fake();
+
+        let response = settings::set_theme(&discord, ALICE, Theme::Colorblind, Locale::Ja)
+            .await
+            .unwrap();
+        let response = json(CreateInteractionResponse::Message(response));
+        assert!(content(&response).contains("色覚配慮"));
+
+        assert_eq!(
+            Theme::Colorblind,
+            get_user_settings(&discord, ALICE).await.theme
+        );
+        assert_eq!(Theme::Wood, get_user_settings(&discord, BOB).await.theme);
+
+        let response = settings::set_stone_labels(&discord, ALICE, true, Locale::En)
+            .await
+            .unwrap();
+        let response = json(CreateInteractionResponse::Message(response));
+        assert!(content(&response).contains("show their probability"));
+        let style = get_user_settings(&discord, ALICE).await.board_style();
+        assert_eq!(Theme::Colorblind, style.theme, "テーマは変わらない");
+        assert!(style.stone_labels);
+
+        // テーマを変えても試合はいつもどおり始められる
+        let response = json(CreateInteractionResponse::Message(
+            game_start::start::<QuantumGomoku>(
+                &discord,
+                CHANNEL,
+                ALICE,
+                (BOB, false),
+                InputMode::Menus,
+                &[],
+                Locale::Ja,
+            )
+            .await,
+        ));
+        assert_eq!(vec!["board.png"], filenames(&response));
+    }
+
+    #[tokio::test]
+    async fn quantum_reversi_observe() {
+        let discord = fake();
+
+        let response = json(CreateInteractionResponse::Message(
+            game_start::start::<QuantumReversi>(
+                &discord,
+                CHANNEL,
+                ALICE,
+                (BOB, false),
+                InputMode::Menus,
+                &[],
+                Locale::Ja,
+            )
+            .await,
+        ));
+        assert_eq!(vec!["board.png"], filenames(&response));
+        assert!(content(&response).contains("なりやすい方の色"));
+        assert!(custom_ids(&response).contains(&"qreversi_push_stone_observe".to_string()));
+
+        let choice = |alphabet: &'static str, number: &'static str| {
+            [
+                ("qreversi_choice_alphabet", alphabet),
+                ("qreversi_choice_number", number),
+            ]
+        };
+
+        // 挟めないマスには置けない
+        for (custom_id, value) in choice("A", "1") {
+            click(&discord, input(ALICE, custom_id, &[value])).await;
+        }
+        let response = click(&discord, input(ALICE, "qreversi_push_stone", &[])).await;
+        assert_eq!("そのマスには置けません。", content(&response));
+
+        // 置いて観測すると、観測の前後を並べた画像だけが付く
+        for (custom_id, value) in choice("C", "4") {
+            click(&discord, input(ALICE, custom_id, &[value])).await;
+        }
+        let response = click(&discord, input(ALICE, "qreversi_push_stone_observe", &[])).await;
+        assert_eq!(vec!["observation.png"], filenames(&response));
+        assert!(content(&response).contains("観測した結果、黒 "));
+        assert!(content(&response).contains("石: 90%白 - 10%黒"));
+
+        let sessions = get_sessions::<QuantumReversi>(&discord).await.unwrap();
+        let session = sessions.lock().await[&CHANNEL].clone();
+        assert!(!session.lock().await.game.has_quantum());
+    }
+
+    #[tokio::test]
+    async fn quantum_gomoku_heatmap() {
+        let discord = fake();
+
+        let response = json(CreateInteractionResponse::Message(
+            game_start::start::<QuantumGomoku>(
+                &discord,
+                CHANNEL,
+                ALICE,
+                (BOB, false),
+                InputMode::Menus,
+                &[],
+                Locale::Ja,
+            )
+            .await,
+        ));
+        assert!(custom_ids(&response).contains(&"qgomoku_view_heatmap".to_string()));
+
+        // 手番でない人も押せて、本人にだけ画像が届く
+        let heatmap = click(&discord, input(BOB, "qgomoku_view_heatmap", &[])).await;
+        assert_eq!(4, heatmap["type"]);
+        assert_eq!(64, heatmap["data"]["flags"]);
+        assert_eq!(vec!["heatmap.png"], filenames(&heatmap));
+        assert!(content(&heatmap).contains("5つ揃う確率"));
+
+        let unknown = click(&discord, input(BOB, "qgomoku_view_pink", &[])).await;
+        assert!(filenames(&unknown).is_empty());
+    }
+}
@@ -0,0 +1,144 @@
+use std::fmt;
+
+use serenity::all::CreateInteractionResponseMessage;
+
+use crate::i18n::{Locale, Text};
+use crate::{gomoku, quantum_gomoku, quantum_reversi, reversi};
+
+/// コマンド・コンポーネントの処理で起きたエラー
+///
+/// `message` (`Display` は既定の言語) は利用者に見せるメッセージ、`cause` はログに出す原因。
+#[derive(Debug)]
+pub enum BotError {
+    /// 起動時に TypeMap へ入れていないデータ
+    MissingData(&'static str),
+    /// 想定していない形のインタラクション
+    UnexpectedInteraction(String),
+    /// spawn_blocking で動かした処理が終わらなかった (panic した・止められた)
+    Task(String),
+
+    NoGame,
+    NotYourTurn,
+    NotChoiced,
+    InvalidCoordinate,
+    /// 「解析」ボタンの試合が残っていない (古い・再起動した)
+    NoFinishedGame,
+
+    Reversi(reversi::reversi::PlaceError),
+    QuantumGomoku(quantum_gomoku::gomoku::PlaceError),
+    QuantumReversi(quantum_reversi::reversi::PlaceError),
+    Gomoku(gomoku::gomoku::PlaceError),
+}
+
+impl fmt::Display for BotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message().get(Locale::default()))
+    }
+}
+
+impl std::error::Error for BotError {}
+
+impl BotError {
+    /// 利用者に見せるメッセージ
+    pub fn message(&self) -> Text {
+        match self {
+            BotError::MissingData(_) | BotError::UnexpectedInteraction(_) | BotError::Task(_) => {
+                Text::InternalError
+            }
+
+            BotError::NoGame => Text::NoGame,
+            BotError::NotYourTurn => Text::NotYourTurn,
+            BotError::NotChoiced => Text::NotChoiced,
+            BotError::InvalidCoordinate => Text::InvalidCoordinate,
+            BotError::NoFinishedGame => Text::NoFinishedGame,
+
+            BotError::Gomoku(gomoku::gomoku::PlaceError::Forbidden(_)) => Text::Forbidden,
+            BotError::Reversi(_)
+            | BotError::QuantumGomoku(_)
+            | BotError::QuantumReversi(_)
+            | BotError::Gomoku(_) => Text::CannotPlace,
+        }
+    }
+
+    /// ログに出す原因
+    pub fn cause(&self) -> String {
+        match self {
+            BotError::MissingData(key) => format!("{} is not found in TypeMap", key),
+            BotError::UnexpectedInteraction(custom_id) => {
+                format!("unexpected interaction: {}", custom_id)
+            }
+            BotError::Task(why) => format!("blocking task failed: {}", why),
+            BotError::Reversi(why) => format!("reversi: {:?}", why),
+            BotError::QuantumGomoku(why) => format!("quantum gomoku: {:?}", why),
+            BotError::QuantumReversi(why) => format!("quantum reversi: {:?}", why),
+            BotError::Gomoku(why) => format!("gomoku: {:?}", why),
+            why => format!("{:?}", why),
+        }
+    }
+}
+
+impl From<reversi::reversi::PlaceError> for BotError {
+    fn from(why: reversi::reversi::PlaceError) -> Self {
+        BotError::Reversi(why)
+    }
+}
+
+impl From<quantum_gomoku::gomoku::PlaceError> for BotError {
+    fn from(why: quantum_gomoku::gomoku::PlaceError) -> Self {
+        BotError::QuantumGomoku(why)
+    }
+}
+
+impl From<quantum_reversi::reversi::PlaceError> for BotError {
+    fn from(why: quantum_reversi::reversi::PlaceError) -> Self {
+        BotError::QuantumReversi(why)
+    }
+}
+
+impl From<gomoku::gomoku::PlaceError> for BotError {
+    fn from(why: gomoku::gomoku::PlaceError) -> Self {
+        BotError::Gomoku(why)
+    }
+}
+
+/// 原因をログに出す
+pub fn log_error(why: &BotError) {
+    println!("BotError: {}", why.cause());
+}
+
+/// 原因をログに出し、押した人だけに見えるメッセージを返す
+pub fn error_message(why: &BotError, locale: Locale) -> CreateInteractionResponseMessage {
+    log_error(why);
+
+    CreateInteractionResponseMessage::new()
+        .content(why.message().get(locale))
+        .ephemeral(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn user_message() {
+        assert_eq!(
+            "禁じ手のため置けません。",
+            BotError::from(gomoku::gomoku::PlaceError::Forbidden(
+                gomoku::gomoku::Forbidden::Overline
+            ))
+            .to_string()
+        );
+        assert_eq!(
+            "そのマスには置けません。",
+            BotError::from(reversi::reversi::PlaceError::NoFlip).to_string()
+        );
+        assert_eq!(
+            "That move is forbidden.",
+            BotError::from(gomoku::gomoku::PlaceError::Forbidden(
+                gomoku::gomoku::Forbidden::Overline
+            ))
+            .message()
+            .get(Locale::En)
+        );
+    }
+}
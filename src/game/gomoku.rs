@@ -0,0 +1,145 @@
+use std::future::Future;
+
+use serenity::all::{CommandOptionType, CreateCommandOption, ResolvedOption, ResolvedValue};
+
+use super::{Game, Names, Outcome, Player, Rendered};
+use crate::error::BotError;
+use crate::gomoku::gomoku::{Gomoku, Rule};
+use crate::i18n::{self, Locale, Text};
+use crate::quantum_gomoku::gen_image::gen_observedboard_image;
+use crate::render::{encode::file_name, theme::BoardStyle};
+
+pub fn rule_name(rule: Rule) -> Text {
+    match rule {
+        Rule::FreeStyle => Text::RuleFreeStyleDetail,
+        Rule::Standard => Text::RuleStandardDetail,
+        Rule::Renju => Text::RuleRenjuDetail,
+    }
+}
+
+impl Game for Gomoku {
+    type Move = (usize, usize);
+    type Report = ();
+
+    const ID: &'static str = "gomoku";
+    const COMMAND: &'static str = "gomoku";
+    const NAME: Text = Text::GomokuName;
+
+    fn start_options() -> Vec<CreateCommandOption> {
+        let option = i18n::option(
+            CommandOptionType::String,
+            "rule",
+            Text::RuleOption,
+            Text::RuleOptionDescription,
+        );
+        let option = i18n::string_choice(option, Text::RuleStandard, "standard");
+        let option = i18n::string_choice(option, Text::RuleFreeStyle, "freestyle");
+        let option = i18n::string_choice(option, Text::RuleRenju, "renju");
+
+        vec![option]
+    }
+
+    fn new(options: &[ResolvedOption]) -> Self {
+        let rule = match options.iter().find(|option| option.name == "rule") {
+            Some(ResolvedOption {
+                value: ResolvedValue::String("freestyle"),
+                ..
+            }) => Rule::FreeStyle,
+            Some(ResolvedOption {
+                value: ResolvedValue::String("renju"),
+                ..
+            }) => Rule::Renju,
+            _ => Rule::Standard,
+        };
+
+        Gomoku::new(rule)
+    }
+
+    fn intro(&self, locale: Locale) -> Option<String> {
+        Some(Text::RuleIntro.format(locale, &[("rule", rule_name(self.rule).get(locale))]))
+    }
+
+    fn alphabets(&self) -> &'static str {
+        "ABCDEFGHIJKLMNO"
+    }
+
+    fn current_player(&self) -> Player {
+        Player::from(self.current_turn)
+    }
+
+    fn parse_move(&self, row: usize, column: usize, _action: &str) -> Result<Self::Move, BotError> {
+        Ok((row, column))
+    }
+
+    fn apply_move(&mut self, (row, column): Self::Move) -> Result<Self::Report, BotError> {
+        self.do_place(row, column)?;
+
+        if self.judge_winner().is_none() && !self.is_board_full() {
+            self.switch_turn();
+        }
+
+        return Ok(());
+    }
+
+    fn pass_turn(&mut self) {
+        self.switch_turn();
+    }
+
+    fn outcome(&self, _report: &Self::Report) -> Option<Outcome> {
+        // そろえた手のあとは手番を渡さないので、勝ったのは手番の人
+        match self.judge_winner() {
+            Some(_) => Some(Outcome::Winner(self.current_player())),
+            None if self.is_board_full() => Some(Outcome::Draw),
+            None => None,
+        }
+    }
+
+    fn render(&self, style: BoardStyle) -> impl Future<Output = Rendered> + Send + 'static {
+        let board = self.board;
+        let last_move = self.last_move;
+        let connected_mass = self
+            .judge_winner()
+            .map(|(_, connected_mass)| connected_mass);
+
+        async move {
+            let img_vec = gen_observedboard_image(board, last_move, connected_mass, style).await;
+
+            Rendered {
+                text: None,
+                files: vec![(file_name("board"), img_vec)],
+                replaces_board: false,
+            }
+        }
+    }
+
+    fn outcome_text(&self, outcome: Outcome, names: &Names, locale: Locale) -> String {
+        match outcome {
+            Outcome::Winner(Player::Black) => {
+                Text::BlackAligned.format(locale, &[("name", &names.black)])
+            }
+            Outcome::Winner(Player::White) => {
+                Text::WhiteAligned.format(locale, &[("name", &names.white)])
+            }
+            Outcome::Draw => Text::BoardFullDraw.get(locale).to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn winner_keeps_turn() {
+        let mut gomoku = Gomoku::new(Rule::Standard);
+
+        for column in 0..4 {
+            gomoku.apply_move((7, column)).unwrap();
+            gomoku.apply_move((8, column)).unwrap();
+        }
+        gomoku.apply_move((7, 4)).unwrap();
+
+        assert_eq!(Some(Outcome::Winner(Player::Black)), gomoku.outcome(&()));
+        assert_eq!(Player::Black, gomoku.current_player());
+    }
+}
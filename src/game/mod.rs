@@ -0,0 +1,222 @@
+/*
+リバーシ・量子リバーシ・量子五目並べ・五目並べに共通する試合の進め方です。
+
+新しいゲームを追加するときは `Game` を実装し、main.rs でコマンドと custom_id を振り分けます。
+セッション (対戦者・選択中のマス) やコマンド・コンポーネントの処理はすべてのゲームで共通です。
+*/
+
+use std::future::Future;
+
+use serenity::all::{CreateCommandOption, CreateEmbed, ResolvedOption};
+
+use crate::color::Color;
+use crate::error::BotError;
+use crate::i18n::{Locale, Text};
+use crate::render::theme::BoardStyle;
+
+pub mod gomoku;
+pub mod quantum_gomoku;
+pub mod quantum_reversi;
+pub mod reversi;
+pub mod session;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Player {
+    Black,
+    White,
+}
+
+impl Player {
+    pub fn emoji(&self) -> &'static str {
+        match self {
+            Player::Black => "🔵",
+            Player::White => "⚪",
+        }
+    }
+}
+
+impl From<Color> for Player {
+    fn from(color: Color) -> Self {
+        match color {
+            Color::Black => Player::Black,
+            Color::White => Player::White,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Winner(Player),
+    Draw,
+}
+
+/// 石を置くボタン
+pub struct Action {
+    /// custom_id の末尾
+    pub id: &'static str,
+    pub label: Text,
+    /// 座標を入力して置くボタンのラベル
+    pub input_label: Text,
+}
+
+impl Action {
+    pub const PLACE: Action = Action {
+        id: "push_stone",
+        label: Text::Place,
+        input_label: Text::InputCoordinate,
+    };
+}
+
+/// 盤面を別の描き方で見るボタン (custom_id は `{ID}_view_{id}`、押した人にだけ見える)
+pub struct View {
+    pub id: &'static str,
+    pub label: Text,
+}
+
+/// 対戦者の名前
+#[derive(Clone)]
+pub struct Names {
+    pub black: String,
+    pub white: String,
+}
+
+impl Names {
+    pub fn of(&self, player: Player) -> &str {
+        match player {
+            Player::Black => &self.black,
+            Player::White => &self.white,
+        }
+    }
+}
+
+/// メッセージに載せる文章と添付画像
+#[derive(Default)]
+pub struct Rendered {
+    pub text: Option<String>,
+    pub files: Vec<(String, Vec<u8>)>,
+    /// 今の盤面も描いてあるので、盤面の画像 (`render`) は付けない
+    pub replaces_board: bool,
+}
+
+/// 決着した試合の解析 (「解析」ボタンの返信に載せる埋め込みと添付画像)
+#[derive(Default)]
+pub struct Analysis {
+    pub embed: CreateEmbed,
+    pub files: Vec<(String, Vec<u8>)>,
+}
+
+pub trait Game: Sized + Send + Sync + 'static {
+    type Move: Send;
+    /// 一手ごとの結果 (パスや観測の結果など)
+    type Report: Send + Sync;
+
+    /// custom_id の接頭辞
+    const ID: &'static str;
+    /// コマンド名の接頭辞 (`{COMMAND}_start`, `{COMMAND}_end`)
+    const COMMAND: &'static str;
+    const NAME: Text;
+    /// 1つ目が通常の置き方
+    const ACTIONS: &'static [Action] = &[Action::PLACE];
+    /// このBOT自身を対戦相手に指定できるか
+    const HAS_AI: bool = false;
+    /// 盤面を別の描き方で見るボタン
+    const VIEWS: &'static [View] = &[];
+    /// 決着したメッセージに「解析」ボタン (custom_id は `{ID}_analyze_{番号}`) を付けるか
+    const HAS_ANALYSIS: bool = false;
+
+    /// `{COMMAND}_start` に追加するオプション
+    fn start_options() -> Vec<CreateCommandOption> {
+        vec![]
+    }
+
+    fn new(options: &[ResolvedOption]) -> Self;
+
+    /// 試合開始時のメッセージに載せるルールの説明など
+    fn intro(&self, _locale: Locale) -> Option<String> {
+        None
+    }
+
+    /// 座標に使うアルファベット (盤面の大きさ)
+    fn alphabets(&self) -> &'static str;
+
+    /// セレクトメニューの (アルファベット, 数字) の説明
+    fn menu_placeholders(&self) -> (Text, Text) {
+        (Text::ColumnPlaceholder, Text::RowPlaceholder)
+    }
+
+    /// 手番の人が置けるマス (少なければマスごとのボタンにする)
+    fn legal_squares(&self) -> Option<Vec<(usize, usize)>> {
+        None
+    }
+
+    fn current_player(&self) -> Player;
+
+    /// 手番の表示に付け加える説明
+    fn turn_note(&self, _locale: Locale) -> Option<String> {
+        None
+    }
+
+    /// (アルファベットの番号, 数字 - 1) と押されたボタンから手を作る
+    fn parse_move(&self, row: usize, column: usize, action: &str) -> Result<Self::Move, BotError>;
+
+    /// 手を打ち、必要なら手番を進める
+    fn apply_move(&mut self, mv: Self::Move) -> Result<Self::Report, BotError>;
+
+    fn outcome(&self, report: &Self::Report) -> Option<Outcome>;
+
+    /// BOTの手番で選ぶ (アルファベットの番号, 数字 - 1, ボタン)
+    fn ai_choice(&self) -> Option<(usize, usize, &'static str)> {
+        None
+    }
+
+    /// 手番を相手に渡す (BOTが置けなかったとき、人の番に戻すのに使う)
+    fn pass_turn(&mut self);
+
+    /// 盤面 (画像は `style` の色・表示で描く)
+    ///
+    /// 描くのに要るものは呼んだときに写しておき、返す Future は `self` を借りない。
+    /// 呼び出し側は試合のロックを離してから描き終わるのを待つ。
+    fn render(&self, style: BoardStyle) -> impl Future<Output = Rendered> + Send + 'static;
+
+    fn render_report(
+        &self,
+        _report: &Self::Report,
+        _names: &Names,
+        _locale: Locale,
+        _style: BoardStyle,
+    ) -> impl Future<Output = Rendered> + Send + 'static {
+        async { Rendered::default() }
+    }
+
+    /// `VIEWS` のボタンが押されたときの画像と説明
+    fn render_view(
+        &self,
+        _view: &str,
+        _locale: Locale,
+        _style: BoardStyle,
+    ) -> impl Future<Output = Rendered> + Send + 'static {
+        async { Rendered::default() }
+    }
+
+    /// 決着した試合の解析 (`HAS_ANALYSIS` のゲームだけ呼ばれる、画像は `style` で描く)
+    ///
+    /// `render` と同じく、返す Future は `self` を借りない。
+    fn analyze(
+        &self,
+        _names: &Names,
+        _locale: Locale,
+        _style: BoardStyle,
+    ) -> impl Future<Output = Result<Analysis, BotError>> + Send + 'static {
+        async { Ok(Analysis::default()) }
+    }
+
+    fn outcome_text(&self, outcome: Outcome, names: &Names, locale: Locale) -> String {
+        match outcome {
+            Outcome::Winner(player) => Text::Winner.format(
+                locale,
+                &[("emoji", player.emoji()), ("name", names.of(player))],
+            ),
+            Outcome::Draw => Text::Draw.get(locale).to_string(),
+        }
+    }
+}
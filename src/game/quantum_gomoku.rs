@@ -0,0 +1,292 @@
+use std::future::Future;
+
+use serenity::all::ResolvedOption;
+
+use super::{Action, Game, Names, Outcome, Player, Rendered, View};
+use crate::error::BotError;
+use crate::i18n::{Locale, Text};
+use crate::quantum_gomoku::{
+    ai,
+    gen_image::{gen_observation_image, gen_quantum_board_image, gen_quantum_heatmap_image},
+    gomoku::{ConnectedMass, ObservedStone, PlaceError, QuantumGomoku, Stone, Turn},
+};
+use crate::render::{encode::file_name, theme::BoardStyle};
+
+const ALPHABETS: &str = "ABCDEFGHIJKLMNO";
+
+/// 置いて観測するボタン (量子リバーシと共通)
+pub const OBSERVE: Action = Action {
+    id: "push_stone_observe",
+    label: Text::Observe,
+    input_label: Text::InputObserve,
+};
+
+const HEATMAP: View = View {
+    id: "heatmap",
+    label: Text::Heatmap,
+};
+
+/// 手番の石の確率の説明 (量子リバーシと共通)
+pub fn stone_note(turn: Turn) -> Text {
+    match turn {
+        Turn::Black90 => Text::StoneBlack90,
+        Turn::Black70 => Text::StoneBlack70,
+        Turn::White90 => Text::StoneWhite90,
+        Turn::White70 => Text::StoneWhite70,
+    }
+}
+
+pub struct QuantumMove {
+    pub row: usize,
+    pub column: usize,
+    pub observe: bool,
+}
+
+/// 観測した結果
+pub struct Observation {
+    /// 観測したときの量子の石の盤面
+    pub board: [[Stone; 19]; 19],
+    pub last_move: Option<(usize, usize)>,
+    pub winner: ObservedStone,
+    pub observed_board: [[ObservedStone; 19]; 19],
+    pub connected_mass: Option<ConnectedMass>,
+}
+
+impl Game for QuantumGomoku {
+    type Move = QuantumMove;
+    type Report = Option<Observation>;
+
+    const ID: &'static str = "qgomoku";
+    const COMMAND: &'static str = "q_gomoku";
+    const NAME: Text = Text::QuantumGomokuName;
+    const ACTIONS: &'static [Action] = &[Action::PLACE, OBSERVE];
+    const HAS_AI: bool = true;
+    const VIEWS: &'static [View] = &[HEATMAP];
+
+    fn new(_options: &[ResolvedOption]) -> Self {
+        QuantumGomoku::new()
+    }
+
+    fn intro(&self, locale: Locale) -> Option<String> {
+        Some(Text::QuantumGomokuIntro.get(locale).to_string())
+    }
+
+    fn alphabets(&self) -> &'static str {
+        ALPHABETS
+    }
+
+    fn current_player(&self) -> Player {
+        Player::from(self.current_turn.color())
+    }
+
+    fn turn_note(&self, locale: Locale) -> Option<String> {
+        Some(stone_note(self.current_turn).get(locale).to_string())
+    }
+
+    fn parse_move(&self, row: usize, column: usize, action: &str) -> Result<Self::Move, BotError> {
+        if !(row < ALPHABETS.len() && column < ALPHABETS.len()) {
+            return Err(PlaceError::OutOfBoardRange.into());
+        }
+
+        Ok(QuantumMove {
+            row,
+            column,
+            observe: action == OBSERVE.id,
+        })
+    }
+
+    fn apply_move(&mut self, mv: Self::Move) -> Result<Self::Report, BotError> {
+        self.do_place(mv.row, mv.column)?;
+
+        self.switch_turn();
+
+        // 盤面が埋まるとどちらも置けないので、観測して決着をつける
+        if !(mv.observe || self.is_full(ALPHABETS.len())) {
+            return Ok(None);
+        }
+
+        let (winner, observed_board, connected_mass) = self.judge_winner();
+
+        return Ok(Some(Observation {
+            board: self.board,
+            last_move: self.last_move,
+            winner,
+            observed_board,
+            connected_mass,
+        }));
+    }
+
+    fn pass_turn(&mut self) {
+        self.switch_turn();
+    }
+
+    fn outcome(&self, report: &Self::Report) -> Option<Outcome> {
+        match report.as_ref()?.winner {
+            ObservedStone::Black => Some(Outcome::Winner(Player::Black)),
+            ObservedStone::White => Some(Outcome::Winner(Player::White)),
+            // 埋まった盤面で揃わなければ引き分け
+            ObservedStone::None => self.is_full(ALPHABETS.len()).then_some(Outcome::Draw),
+        }
+    }
+
+    fn ai_choice(&self) -> Option<(usize, usize, &'static str)> {
+        let ai_color = self.current_turn.majority();
+        let (row, column) = ai::choose_place(self)?;
+
+        // 置いたあとの盤面で観測するかを決める
+        let mut placed = self.clone();
+        placed.do_place(row, column).ok()?;
+        placed.switch_turn();
+
+        let action = if ai::should_observe(&placed, ai_color) {
+            OBSERVE.id
+        } else {
+            Action::PLACE.id
+        };
+
+        Some((row, column, action))
+    }
+
+    fn render(&self, style: BoardStyle) -> impl Future<Output = Rendered> + Send + 'static {
+        let board = self.board;
+        let last_move = self.last_move;
+
+        async move {
+            let img_vec = gen_quantum_board_image(board, last_move, style).await;
+
+            Rendered {
+                text: None,
+                files: vec![(file_name("board"), img_vec)],
+                replaces_board: false,
+            }
+        }
+    }
+
+    fn render_report(
+        &self,
+        report: &Self::Report,
+        _names: &Names,
+        locale: Locale,
+        style: BoardStyle,
+    ) -> impl Future<Output = Rendered> + Send + 'static {
+        let observation = report.as_ref().map(|observation| {
+            let text = (observation.winner == ObservedStone::None)
+                .then(|| Text::NotAligned.get(locale).to_string());
+
+            (
+                observation.board,
+                observation.last_move,
+                observation.observed_board,
+                observation.connected_mass,
+                text,
+            )
+        });
+
+        async move {
+            let (board, last_move, observed_board, connected_mass, text) = match observation {
+                Some(observation) => observation,
+                None => return Rendered::default(),
+            };
+
+            // 観測する前と後の盤面を1枚に並べる
+            let img_vec =
+                gen_observation_image(board, last_move, observed_board, connected_mass, style)
+                    .await;
+
+            Rendered {
+                text,
+                files: vec![(file_name("observation"), img_vec)],
+                replaces_board: true,
+            }
+        }
+    }
+
+    fn render_view(
+        &self,
+        view: &str,
+        locale: Locale,
+        style: BoardStyle,
+    ) -> impl Future<Output = Rendered> + Send + 'static {
+        let gomoku = (view == HEATMAP.id).then(|| self.clone());
+
+        async move {
+            let gomoku = match gomoku {
+                Some(gomoku) => gomoku,
+                None => return Rendered::default(),
+            };
+
+            let img_vec = gen_quantum_heatmap_image(&gomoku, style).await;
+
+            Rendered {
+                text: Some(Text::HeatmapNote.get(locale).to_string()),
+                files: vec![(file_name("heatmap"), img_vec)],
+                replaces_board: false,
+            }
+        }
+    }
+
+    fn outcome_text(&self, outcome: Outcome, names: &Names, locale: Locale) -> String {
+        match outcome {
+            Outcome::Winner(Player::Black) => {
+                Text::BlackAligned.format(locale, &[("name", &names.black)])
+            }
+            Outcome::Winner(Player::White) => {
+                Text::WhiteAligned.format(locale, &[("name", &names.white)])
+            }
+            Outcome::Draw => Text::Draw.get(locale).to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn place_without_observation() {
+        let mut gomoku = QuantumGomoku::new();
+
+        let mv = gomoku.parse_move(7, 7, "push_stone").unwrap();
+        let report = gomoku.apply_move(mv).unwrap();
+
+        assert!(report.is_none());
+        assert!(gomoku.outcome(&report).is_none());
+        assert_eq!(Player::White, gomoku.current_player());
+    }
+
+    #[test]
+    fn full_board_ends_game() {
+        // 最後の1マスを残して埋める
+        let mut gomoku = QuantumGomoku::new();
+        for row in 0..ALPHABETS.len() {
+            for column in 0..ALPHABETS.len() {
+                gomoku.board[row][column] = match (row + column) % 4 {
+                    0 => Stone::Black90,
+                    1 => Stone::White90,
+                    2 => Stone::Black70,
+                    _ => Stone::White70,
+                };
+            }
+        }
+        gomoku.board[14][14] = Stone::None;
+
+        let mv = gomoku.parse_move(14, 14, "push_stone").unwrap();
+        let report = gomoku.apply_move(mv).unwrap();
+
+        // 観測するボタンを押していなくても観測し、揃わなければ引き分けにする
+        let observation = report.as_ref().unwrap();
+        match gomoku.outcome(&report) {
+            Some(Outcome::Draw) => assert_eq!(ObservedStone::None, observation.winner),
+            Some(Outcome::Winner(_)) => assert_ne!(ObservedStone::None, observation.winner),
+            None => panic!("the game must end on a full board"),
+        }
+        assert!(gomoku.ai_choice().is_none());
+    }
+
+    #[test]
+    fn outside_of_play_area() {
+        let gomoku = QuantumGomoku::new();
+
+        assert!(gomoku.parse_move(15, 0, "push_stone").is_err());
+    }
+}
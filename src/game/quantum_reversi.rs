@@ -0,0 +1,314 @@
+use std::future::Future;
+
+use rand::Rng;
+use serenity::all::ResolvedOption;
+
+use super::quantum_gomoku::{stone_note, QuantumMove, OBSERVE};
+use super::reversi::result_text;
+use super::{Action, Game, Names, Outcome, Player, Rendered};
+use crate::error::BotError;
+use crate::i18n::{Locale, Text};
+use crate::quantum_gomoku::gomoku::ObservedStone;
+use crate::quantum_reversi::{
+    gen_image::{gen_board_image, gen_observation_image},
+    reversi::{Disc, ObservedBoard, PlaceError, QuantumReversi, ALPHABETS, SIZE},
+};
+use crate::render::{encode::file_name, theme::BoardStyle};
+
+/// 観測した結果
+pub struct Observation {
+    /// 観測したときの量子の石の盤面
+    pub board: [[Disc; SIZE]; SIZE],
+    pub last_move: Option<(usize, usize)>,
+    pub observed_board: ObservedBoard,
+}
+
+pub struct QuantumReversiReport {
+    /// 次の人が置けるマスがなく、同じ人の番になった
+    pub passed: bool,
+    /// 観測するボタンを押したか、どちらも置けなくなって観測した
+    pub observation: Option<Observation>,
+}
+
+impl Game for QuantumReversi {
+    type Move = QuantumMove;
+    type Report = QuantumReversiReport;
+
+    const ID: &'static str = "qreversi";
+    const COMMAND: &'static str = "q_reversi";
+    const NAME: Text = Text::QuantumReversiName;
+    const ACTIONS: &'static [Action] = &[Action::PLACE, OBSERVE];
+
+    fn new(_options: &[ResolvedOption]) -> Self {
+        QuantumReversi::new()
+    }
+
+    fn intro(&self, locale: Locale) -> Option<String> {
+        Some(Text::QuantumReversiIntro.get(locale).to_string())
+    }
+
+    fn alphabets(&self) -> &'static str {
+        ALPHABETS
+    }
+
+    fn menu_placeholders(&self) -> (Text, Text) {
+        (Text::RowPlaceholder, Text::ColumnPlaceholder)
+    }
+
+    // 置けるマスは画像に描き、マスごとのボタンにはしない (観測するボタンを残すため)
+
+    fn current_player(&self) -> Player {
+        Player::from(self.current_turn.color())
+    }
+
+    fn turn_note(&self, locale: Locale) -> Option<String> {
+        Some(stone_note(self.current_turn).get(locale).to_string())
+    }
+
+    fn parse_move(&self, row: usize, column: usize, action: &str) -> Result<Self::Move, BotError> {
+        if !(row < SIZE && column < SIZE) {
+            return Err(PlaceError::OutOfRange.into());
+        }
+
+        Ok(QuantumMove {
+            row,
+            column,
+            observe: action == OBSERVE.id,
+        })
+    }
+
+    fn apply_move(&mut self, mv: Self::Move) -> Result<Self::Report, BotError> {
+        apply_move_with(self, mv, &mut rand::thread_rng())
+    }
+
+    fn pass_turn(&mut self) {
+        self.switch_turn();
+    }
+
+    fn outcome(&self, _report: &Self::Report) -> Option<Outcome> {
+        if !self.is_game_end() {
+            return None;
+        }
+
+        let black = self.count(ObservedStone::Black);
+        let white = self.count(ObservedStone::White);
+
+        let outcome = if black > white {
+            Outcome::Winner(Player::Black)
+        } else if white > black {
+            Outcome::Winner(Player::White)
+        } else {
+            Outcome::Draw
+        };
+
+        Some(outcome)
+    }
+
+    fn render(&self, style: BoardStyle) -> impl Future<Output = Rendered> + Send + 'static {
+        let board = self.board;
+        let last_move = self.last_move;
+        let hints = self.legal_squares(self.turn_color());
+
+        async move {
+            let img_vec = gen_board_image(board, last_move, hints, style).await;
+
+            Rendered {
+                text: None,
+                files: vec![(file_name("board"), img_vec)],
+                replaces_board: false,
+            }
+        }
+    }
+
+    fn render_report(
+        &self,
+        report: &Self::Report,
+        _names: &Names,
+        locale: Locale,
+        style: BoardStyle,
+    ) -> impl Future<Output = Rendered> + Send + 'static {
+        let mut lines = vec![];
+
+        if report.observation.is_some() {
+            lines.push(Text::QuantumReversiObserved.format(
+                locale,
+                &[
+                    ("black", &self.count(ObservedStone::Black).to_string()),
+                    ("white", &self.count(ObservedStone::White).to_string()),
+                ],
+            ));
+        }
+        if report.passed {
+            lines.push(Text::ReversiPass.get(locale).to_string());
+        }
+
+        let text = (!lines.is_empty()).then(|| lines.join("\n"));
+        let observation = report.observation.as_ref().map(|observation| {
+            (
+                observation.board,
+                observation.last_move,
+                observation.observed_board,
+            )
+        });
+
+        async move {
+            let (board, last_move, observed_board) = match observation {
+                Some(observation) => observation,
+                None => {
+                    return Rendered {
+                        text,
+                        files: vec![],
+                        replaces_board: false,
+                    }
+                }
+            };
+
+            // 観測する前と後の盤面を1枚に並べる
+            let img_vec = gen_observation_image(board, last_move, observed_board, style).await;
+
+            Rendered {
+                text,
+                files: vec![(file_name("observation"), img_vec)],
+                replaces_board: true,
+            }
+        }
+    }
+
+    fn outcome_text(&self, outcome: Outcome, names: &Names, locale: Locale) -> String {
+        result_text(
+            (
+                self.count(ObservedStone::Black),
+                self.count(ObservedStone::White),
+            ),
+            outcome,
+            names,
+            locale,
+        )
+    }
+}
+
+/// `rng` で観測する `apply_move` (テストでは種を決めた乱数を渡す)
+fn apply_move_with(
+    reversi: &mut QuantumReversi,
+    mv: QuantumMove,
+    rng: &mut impl Rng,
+) -> Result<QuantumReversiReport, BotError> {
+    reversi.do_place(mv.row, mv.column)?;
+
+    // どちらも置けなくなったら、観測していない石の色を決めてから決着を調べる
+    let observe = mv.observe || (reversi.is_game_end() && reversi.has_quantum());
+    let observation = observe.then(|| {
+        let board = reversi.board;
+        let observed_board = reversi.observe(rng);
+
+        Observation {
+            board,
+            last_move: reversi.last_move,
+            observed_board,
+        }
+    });
+
+    if reversi.is_game_end() {
+        return Ok(QuantumReversiReport {
+            passed: false,
+            observation,
+        });
+    }
+
+    // 同じ色の番に戻すときは、次の同じ色の確率の石まで2回進める
+    reversi.switch_turn();
+
+    let passed = !reversi.can_place(reversi.turn_color());
+    if passed {
+        reversi.switch_turn();
+    }
+
+    return Ok(QuantumReversiReport {
+        passed,
+        observation,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::quantum_gomoku::gomoku::Stone;
+
+    #[test]
+    fn place_without_observation() {
+        let mut reversi = QuantumReversi::new();
+
+        let mv = reversi.parse_move(2, 3, "push_stone").unwrap();
+        let report = reversi.apply_move(mv).unwrap();
+
+        assert!(report.observation.is_none());
+        assert!(!report.passed);
+        assert!(reversi.outcome(&report).is_none());
+        assert_eq!(Disc::Quantum(Stone::Black90), reversi.board[3][3]);
+        assert_eq!(Player::White, reversi.current_player());
+        assert_eq!(
+            Some("石: 90%白 - 10%黒".to_string()),
+            reversi.turn_note(Locale::Ja)
+        );
+
+        assert!(reversi.parse_move(8, 0, "push_stone").is_err());
+        let mv = reversi.parse_move(0, 0, "push_stone").unwrap();
+        assert!(reversi.apply_move(mv).is_err());
+    }
+
+    #[test]
+    fn observe_button_collapses_board() {
+        let mut reversi = QuantumReversi::new();
+
+        let mv = reversi.parse_move(2, 3, "push_stone_observe").unwrap();
+        let report = reversi.apply_move(mv).unwrap();
+
+        // 観測する前の盤面には置いた石と挟んだ石が量子の石のまま残る
+        let observation = report.observation.unwrap();
+        assert_eq!(Disc::Quantum(Stone::Black90), observation.board[2][3]);
+        assert_eq!(Disc::Quantum(Stone::Black90), observation.board[3][3]);
+        assert_eq!(Some((2, 3)), observation.last_move);
+
+        assert!(!reversi.has_quantum());
+        for (row, column) in [(2, 3), (3, 3)] {
+            assert_eq!(
+                Disc::Observed(observation.observed_board[row][column]),
+                reversi.board[row][column]
+            );
+        }
+        assert_eq!(
+            5,
+            reversi.count(ObservedStone::Black) + reversi.count(ObservedStone::White)
+        );
+    }
+
+    #[test]
+    fn game_end_observes_remaining_discs() {
+        // 黒が A3 に置くと、なりやすい方の色ではすべて黒になってどちらも置けなくなるので、
+        // 観測するボタンを押していなくても観測する
+        let mut reversi = QuantumReversi::new();
+        reversi.board = [[Disc::None; SIZE]; SIZE];
+        reversi.board[0][0] = Disc::Observed(ObservedStone::Black);
+        reversi.board[0][1] = Disc::Quantum(Stone::White70);
+
+        let mv = reversi.parse_move(0, 2, "push_stone").unwrap();
+        let report = apply_move_with(&mut reversi, mv, &mut StdRng::seed_from_u64(0)).unwrap();
+
+        let observation = report.observation.as_ref().unwrap();
+        assert_eq!(Disc::Quantum(Stone::Black90), observation.board[0][1]);
+        assert!(!reversi.has_quantum());
+
+        // この種では 90% の方が出てすべて黒になり、黒の勝ちで決着する
+        assert_eq!(
+            [ObservedStone::Black; 3],
+            observation.observed_board[0][..3]
+        );
+        assert_eq!(3, reversi.count(ObservedStone::Black));
+        assert_eq!(
+            Some(Outcome::Winner(Player::Black)),
+            reversi.outcome(&report)
+        );
+    }
+}
@@ -0,0 +1,505 @@
+use std::future::{ready, Future};
+
+use serenity::all::{
+    CommandOptionType, CreateCommandOption, CreateEmbed, ResolvedOption, ResolvedValue,
+};
+
+use super::{Analysis, Game, Names, Outcome, Player, Rendered};
+use crate::color::Color;
+use crate::error::BotError;
+use crate::i18n::{self, Locale, Text};
+use crate::render::encode::file_name;
+use crate::render::theme::BoardStyle;
+use crate::reversi::analysis::{self, MoveAnalysis, Tag, BLUNDER_LOSS, MISTAKE_LOSS};
+use crate::reversi::gen_image::draw_analysis_graph;
+use crate::reversi::opening::{self, Opening};
+use crate::reversi::reversi::{Layout, Reversi, Rule, Stone, SIZES};
+use crate::reversi::search::{ENDGAME_EMPTIES, MIDGAME_DEPTH};
+
+/// 解析の埋め込みに載せる悪手・疑問手の数 (多いときは「ほか N 手」にする)
+const LISTED_MOVES: usize = 15;
+
+pub fn layout_name(layout: Layout) -> Text {
+    match layout {
+        Layout::Cross => Text::LayoutCross,
+        Layout::Parallel => Text::LayoutParallel,
+    }
+}
+
+pub fn rule_name(rule: Rule) -> Text {
+    match rule {
+        Rule::Standard => Text::RuleStandard,
+        Rule::Anti => Text::RuleAntiDetail,
+    }
+}
+
+/// 定石の名前 (言語に合わせる)
+pub fn opening_name(opening: &Opening, locale: Locale) -> &'static str {
+    match locale {
+        Locale::Ja => opening.ja,
+        Locale::En => opening.en,
+    }
+}
+
+/// 決着したときの石の数と勝者 (`counts` は (黒, 白)、量子リバーシと共通)
+pub fn result_text(
+    counts: (usize, usize),
+    outcome: Outcome,
+    names: &Names,
+    locale: Locale,
+) -> String {
+    let winner = match outcome {
+        Outcome::Winner(Player::Black) => {
+            Text::ReversiWinnerBlack.format(locale, &[("name", &names.black)])
+        }
+        Outcome::Winner(Player::White) => {
+            Text::ReversiWinnerWhite.format(locale, &[("name", &names.white)])
+        }
+        Outcome::Draw => Text::ReversiWinnerDraw.get(locale).to_string(),
+    };
+
+    Text::ReversiResult.format(
+        locale,
+        &[
+            ("black", &counts.0.to_string()),
+            ("white", &counts.1.to_string()),
+            ("winner", &winner),
+        ],
+    )
+}
+
+pub struct ReversiReport {
+    /// 次の人が置けるマスがなく、同じ人の番になった
+    pub passed: bool,
+}
+
+impl Game for Reversi {
+    type Move = (i32, i32);
+    type Report = ReversiReport;
+
+    const ID: &'static str = "reversi";
+    const COMMAND: &'static str = "reversi";
+    const NAME: Text = Text::ReversiName;
+    const HAS_ANALYSIS: bool = true;
+
+    fn start_options() -> Vec<CreateCommandOption> {
+        let option = i18n::option(
+            CommandOptionType::String,
+            "input",
+            Text::InputOption,
+            Text::InputOptionDescription,
+        );
+        let option = i18n::string_choice(option, Text::InputButtons, "buttons");
+        let option = i18n::string_choice(option, Text::InputMenus, "menus");
+
+        let mut size = i18n::option(
+            CommandOptionType::Integer,
+            "size",
+            Text::SizeOption,
+            Text::SizeOptionDescription,
+        );
+        for value in SIZES {
+            size = size.add_int_choice(format!("{0}x{0}", value), value as i32);
+        }
+
+        let layout = i18n::option(
+            CommandOptionType::String,
+            "layout",
+            Text::LayoutOption,
+            Text::LayoutOptionDescription,
+        );
+        let layout = i18n::string_choice(layout, Text::LayoutCross, "cross");
+        let layout = i18n::string_choice(layout, Text::LayoutParallel, "parallel");
+
+        let rule = i18n::option(
+            CommandOptionType::String,
+            "rule",
+            Text::RuleOption,
+            Text::RuleOptionDescription,
+        );
+        let rule = i18n::string_choice(rule, Text::RuleStandard, "standard");
+        let rule = i18n::string_choice(rule, Text::RuleAnti, "anti");
+
+        vec![option, size, layout, rule]
+    }
+
+    fn new(options: &[ResolvedOption]) -> Self {
+        let size = match options.iter().find(|option| option.name == "size") {
+            Some(ResolvedOption {
+                value: ResolvedValue::Integer(value),
+                ..
+            }) if SIZES.contains(&(*value as usize)) => *value as usize,
+            _ => 8,
+        };
+        let layout = match options.iter().find(|option| option.name == "layout") {
+            Some(ResolvedOption {
+                value: ResolvedValue::String("parallel"),
+                ..
+            }) => Layout::Parallel,
+            _ => Layout::Cross,
+        };
+        let rule = match options.iter().find(|option| option.name == "rule") {
+            Some(ResolvedOption {
+                value: ResolvedValue::String("anti"),
+                ..
+            }) => Rule::Anti,
+            _ => Rule::Standard,
+        };
+
+        Reversi::with_layout(size, layout).with_rule(rule)
+    }
+
+    /// ふつうのリバーシでなければ、盤の大きさと初期配置・ルール
+    fn intro(&self, locale: Locale) -> Option<String> {
+        let mut lines = vec![];
+
+        if !self.is_standard() {
+            lines.push(Text::BoardIntro.format(
+                locale,
+                &[
+                    ("size", &format!("{0}x{0}", self.size)),
+                    ("layout", layout_name(self.layout).get(locale)),
+                ],
+            ));
+        }
+        if self.rule != Rule::Standard {
+            lines.push(
+                Text::RuleIntro.format(locale, &[("rule", rule_name(self.rule).get(locale))]),
+            );
+        }
+
+        if lines.is_empty() {
+            return None;
+        }
+
+        Some(lines.join("\n"))
+    }
+
+    fn alphabets(&self) -> &'static str {
+        Reversi::alphabets(self)
+    }
+
+    fn menu_placeholders(&self) -> (Text, Text) {
+        (Text::RowPlaceholder, Text::ColumnPlaceholder)
+    }
+
+    fn legal_squares(&self) -> Option<Vec<(usize, usize)>> {
+        let squares = self
+            .legal_squares(self.turn)
+            .into_iter()
+            .map(|(row, column)| (row as usize, column as usize))
+            .collect();
+
+        Some(squares)
+    }
+
+    fn current_player(&self) -> Player {
+        Player::from(self.turn)
+    }
+
+    /// ここまでの手順が定石なら、その名前 (定石はふつうのリバーシだけ)
+    fn turn_note(&self, locale: Locale) -> Option<String> {
+        if !self.is_standard() {
+            return None;
+        }
+
+        let opening = opening::find(&self.moves)?;
+
+        Some(Text::Opening.format(locale, &[("name", opening_name(opening, locale))]))
+    }
+
+    fn parse_move(&self, row: usize, column: usize, _action: &str) -> Result<Self::Move, BotError> {
+        Ok((row as i32, column as i32))
+    }
+
+    fn apply_move(&mut self, (row, column): Self::Move) -> Result<Self::Report, BotError> {
+        self.do_place(row, column, self.turn)?;
+
+        if self.is_game_end() {
+            return Ok(ReversiReport { passed: false });
+        }
+
+        self.switch_turn();
+
+        let passed = !(self.player_can_place(self.turn));
+        if passed {
+            self.switch_turn();
+        }
+
+        return Ok(ReversiReport { passed });
+    }
+
+    fn pass_turn(&mut self) {
+        self.switch_turn();
+    }
+
+    fn outcome(&self, _report: &Self::Report) -> Option<Outcome> {
+        if !self.is_game_end() {
+            return None;
+        }
+
+        let outcome = match self.winner() {
+            Some(color) => Outcome::Winner(Player::from(color)),
+            None => Outcome::Draw,
+        };
+
+        Some(outcome)
+    }
+
+    fn render(&self, _style: BoardStyle) -> impl Future<Output = Rendered> + Send + 'static {
+        ready(Rendered {
+            text: Some(self.print_board()),
+            files: vec![],
+            replaces_board: false,
+        })
+    }
+
+    fn render_report(
+        &self,
+        report: &Self::Report,
+        _names: &Names,
+        locale: Locale,
+        _style: BoardStyle,
+    ) -> impl Future<Output = Rendered> + Send + 'static {
+        ready(Rendered {
+            text: report
+                .passed
+                .then(|| Text::ReversiPass.get(locale).to_string()),
+            files: vec![],
+            replaces_board: false,
+        })
+    }
+
+    fn analyze(
+        &self,
+        names: &Names,
+        locale: Locale,
+        style: BoardStyle,
+    ) -> impl Future<Output = Result<Analysis, BotError>> + Send + 'static {
+        let game = self.clone();
+        let names = names.clone();
+
+        async move {
+            // 全部の局面を読むので、描くのと同じく非同期の処理の外で行う
+            let (analyses, graph) = tokio::task::spawn_blocking(move || {
+                let analyses = analysis::analyze(&game);
+                let graph = draw_analysis_graph(&analyses, style);
+
+                (analyses, graph)
+            })
+            .await
+            .map_err(|why| BotError::Task(why.to_string()))?;
+
+            let graph_name = file_name("analysis");
+            let embed = analysis_embed(&analyses, &names, locale)
+                .image(format!("attachment://{}", graph_name));
+
+            Ok(Analysis {
+                embed,
+                files: vec![(graph_name, graph)],
+            })
+        }
+    }
+
+    fn outcome_text(&self, outcome: Outcome, names: &Names, locale: Locale) -> String {
+        result_text(
+            (
+                self.count_stones(Stone::BLACK),
+                self.count_stones(Stone::WHITE),
+            ),
+            outcome,
+            names,
+            locale,
+        )
+    }
+}
+
+/// 対戦者ごとの正確さと悪手・疑問手の数、悪手・疑問手の一覧
+fn analysis_embed(analyses: &[MoveAnalysis], names: &Names, locale: Locale) -> CreateEmbed {
+    let description = Text::AnalysisDescription.format(
+        locale,
+        &[
+            ("mistake", &MISTAKE_LOSS.to_string()),
+            ("blunder", &BLUNDER_LOSS.to_string()),
+            ("depth", &MIDGAME_DEPTH.to_string()),
+            ("empties", &ENDGAME_EMPTIES.to_string()),
+        ],
+    );
+
+    let mut embed = CreateEmbed::new()
+        .title(Text::AnalysisTitle.get(locale))
+        .description(description);
+
+    for color in [Color::Black, Color::White] {
+        let player = Player::from(color);
+        let count = |tag| {
+            analyses
+                .iter()
+                .filter(|analysis| analysis.color == color && analysis.tag() == Some(tag))
+                .count()
+                .to_string()
+        };
+        let accuracy = analysis::accuracy(analyses, color)
+            .map(|accuracy| format!("{:.1}", accuracy))
+            .unwrap_or_else(|| "-".to_string());
+
+        let value = Text::AnalysisPlayer.format(
+            locale,
+            &[
+                ("accuracy", &accuracy),
+                ("blunders", &count(Tag::Blunder)),
+                ("mistakes", &count(Tag::Mistake)),
+            ],
+        );
+        embed = embed.field(
+            format!("{} {}", player.emoji(), names.of(player)),
+            value,
+            true,
+        );
+    }
+
+    let tagged: Vec<(usize, &MoveAnalysis, Tag)> = analyses
+        .iter()
+        .enumerate()
+        .filter_map(|(index, analysis)| Some((index, analysis, analysis.tag()?)))
+        .collect();
+
+    let mut lines: Vec<String> = tagged
+        .iter()
+        .take(LISTED_MOVES)
+        .map(|(index, analysis, tag)| {
+            let tag = match tag {
+                Tag::Blunder => Text::Blunder,
+                Tag::Mistake => Text::Mistake,
+            };
+
+            Text::AnalysisMove.format(
+                locale,
+                &[
+                    ("number", &(index + 1).to_string()),
+                    ("emoji", Player::from(analysis.color).emoji()),
+                    ("square", &opening::notation(&[analysis.square])),
+                    ("tag", tag.get(locale)),
+                    ("best", &opening::notation(&[analysis.best_square])),
+                    ("loss", &analysis.loss.to_string()),
+                ],
+            )
+        })
+        .collect();
+
+    if tagged.len() > LISTED_MOVES {
+        lines.push(Text::AnalysisMoreMoves.format(
+            locale,
+            &[("count", &(tagged.len() - LISTED_MOVES).to_string())],
+        ));
+    }
+    if lines.is_empty() {
+        lines.push(Text::AnalysisNoMistakes.get(locale).to_string());
+    }
+
+    return embed.field(Text::AnalysisMoves.get(locale), lines.join("\n"), false);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reversi::reversi::MAX_SIZE;
+
+    #[test]
+    fn localized_result() {
+        let names = Names {
+            black: "alice".to_string(),
+            white: "bob".to_string(),
+        };
+
+        assert_eq!(
+            "試合が終了しました。\n黒: 40\n白: 24\n勝者: 黒: alice",
+            result_text((40, 24), Outcome::Winner(Player::Black), &names, Locale::Ja)
+        );
+        assert_eq!(
+            "The game is over.\nBlack: 32\nWhite: 32\nWinner: Draw",
+            result_text((32, 32), Outcome::Draw, &names, Locale::En)
+        );
+    }
+
+    #[test]
+    fn pass_keeps_turn() {
+        // 白が A3 に置くと A1-A3 が白になり、黒はどこにも置けなくなる
+        let mut reversi = Reversi::new();
+        reversi.board = [[Stone::NONE; MAX_SIZE]; MAX_SIZE];
+        reversi.board[0][0] = Stone::WHITE;
+        reversi.board[0][1] = Stone::BLACK;
+        reversi.board[1][1] = Stone::BLACK;
+        reversi.turn = Color::White;
+
+        let report = reversi.apply_move((0, 2)).unwrap();
+
+        assert!(reversi.outcome(&report).is_none());
+        assert!(report.passed);
+        assert_eq!(Player::White, reversi.current_player());
+    }
+
+    #[test]
+    fn opening_note() {
+        let mut reversi = Reversi::new();
+        assert_eq!(None, reversi.turn_note(Locale::Ja));
+
+        // C4 から始めた虎 (F5 D6 C3 D3 C4 を180°回したもの)
+        for (row, column) in [(2, 3), (4, 2), (5, 5), (4, 5), (5, 4)] {
+            reversi.apply_move((row, column)).unwrap();
+        }
+        assert_eq!(Some("定石: 虎".to_string()), reversi.turn_note(Locale::Ja));
+        assert_eq!(
+            Some("Opening: Tiger".to_string()),
+            reversi.turn_note(Locale::En)
+        );
+    }
+
+    #[test]
+    fn sizes_and_layouts() {
+        let reversi = Reversi::new();
+        assert_eq!(None, reversi.intro(Locale::Ja));
+        assert_eq!("ABCDEFGH", Game::alphabets(&reversi));
+
+        let reversi = Reversi::with_layout(10, Layout::Parallel);
+        assert_eq!(
+            Some("盤: 10x10、初期配置: 平行 (横に並べる)".to_string()),
+            reversi.intro(Locale::Ja)
+        );
+        assert_eq!("ABCDEFGHIJ", Game::alphabets(&reversi));
+        assert_eq!(
+            Some(vec![(3, 3), (3, 4), (3, 5), (3, 6)]),
+            Game::legal_squares(&reversi)
+        );
+
+        // 定石と同じ手順でも、ふつうのリバーシでなければ定石にしない
+        let mut reversi = Reversi::with_layout(8, Layout::Parallel);
+        reversi.moves = opening::parse_moves("F5 D6").unwrap();
+        assert_eq!(None, reversi.turn_note(Locale::Ja));
+    }
+
+    #[test]
+    fn outcome_counts_stones() {
+        let mut reversi = Reversi::new();
+        for row in 0..8 {
+            reversi.board[row][..8].fill(Stone::BLACK);
+        }
+        reversi.board[0][0] = Stone::WHITE;
+
+        assert_eq!(
+            Some(Outcome::Winner(Player::Black)),
+            reversi.outcome(&ReversiReport { passed: false })
+        );
+
+        // アンチリバーシでは石が少ないほうが勝ち
+        let reversi = reversi.with_rule(Rule::Anti);
+        assert_eq!(
+            Some(Outcome::Winner(Player::White)),
+            reversi.outcome(&ReversiReport { passed: false })
+        );
+        assert_eq!(
+            Some("ルール: アンチ (石が少ないほうが勝ち)".to_string()),
+            reversi.intro(Locale::Ja)
+        );
+    }
+}
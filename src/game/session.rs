@@ -0,0 +1,785 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serenity::all::{
+    ButtonStyle, CreateActionRow, CreateButton, CreateSelectMenu, CreateSelectMenuKind,
+    CreateSelectMenuOption, UserId,
+};
+use tokio::sync::Mutex;
+
+use super::{Game, Names, Outcome, Player};
+use crate::config::TimeControl;
+use crate::discord::Discord;
+use crate::error::{log_error, BotError};
+use crate::i18n::{Locale, Text};
+
+/// 試合ごとのロック (確認から石を置くまでをこのロックの中で行う)
+pub type SharedSession<G> = Arc<Mutex<Session<G>>>;
+/// チャンネルIDと試合の対応 (試合を取り出すときだけロックする)
+pub type SessionMap<G> = Arc<Mutex<HashMap<u64, SharedSession<G>>>>;
+/// 決着した試合 (「解析」ボタンを押したときに取り出す)
+pub type SharedFinishedSessions<G> = Arc<Mutex<FinishedSessions<G>>>;
+
+/// 決着した試合を残しておく数 (古いものから解析できなくなる)
+const FINISHED_CAPACITY: usize = 32;
+
+#[derive(Debug)]
+pub struct SessionUser {
+    pub id: UserId,
+    pub choiced_alphabet: Option<String>,
+    pub choiced_number: Option<String>,
+    pub is_ai: bool,
+}
+
+impl SessionUser {
+    pub fn new(user_id: UserId) -> Self {
+        Self {
+            id: user_id,
+            choiced_alphabet: None,
+            choiced_number: None,
+            is_ai: false,
+        }
+    }
+
+    pub fn new_ai(user_id: UserId) -> Self {
+        Self {
+            is_ai: true,
+            ..Self::new(user_id)
+        }
+    }
+}
+
+/// 試合のメッセージでのマスの選び方
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InputMode {
+    /// 置けるマスごとのボタン (多すぎるときはメニュー)
+    Buttons,
+    /// 行と列のセレクトメニュー
+    Menus,
+}
+
+/// 試合の持ち時間
+pub struct Clock {
+    time_control: TimeControl,
+    /// 今の手番が始まった時刻
+    turn_started: Instant,
+    /// 黒・白がこれまでに使った時間
+    used: [Duration; 2],
+}
+
+impl Clock {
+    pub fn new(time_control: TimeControl) -> Self {
+        Self {
+            time_control,
+            turn_started: Instant::now(),
+            used: [Duration::ZERO; 2],
+        }
+    }
+
+    fn index(player: Player) -> usize {
+        match player {
+            Player::Black => 0,
+            Player::White => 1,
+        }
+    }
+
+    /// 手番の player の持ち時間が now までに切れたか
+    fn is_over(&self, player: Player, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.turn_started);
+
+        let move_over = self
+            .time_control
+            .move_seconds
+            .is_some_and(|seconds| elapsed > Duration::from_secs(seconds));
+        let game_over = self.time_control.game_minutes.is_some_and(|minutes| {
+            self.used[Self::index(player)] + elapsed > Duration::from_secs(minutes * 60)
+        });
+
+        move_over || game_over
+    }
+
+    /// player が now に置き終わったので、使った時間に足して次の手番を始める
+    fn record(&mut self, player: Player, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.turn_started);
+
+        self.used[Self::index(player)] += elapsed;
+        self.turn_started = now;
+    }
+}
+
+/// 1つのチャンネルで行われている試合
+pub struct Session<G: Game> {
+    pub game: G,
+    pub input_mode: InputMode,
+    pub clock: Clock,
+    /// 決着した・終了した (ロックを待っている間に表から外された場合に使う)
+    pub ended: bool,
+
+    pub black_user: SessionUser,
+    pub white_user: SessionUser,
+}
+
+/// BOTが続けて打った手
+pub enum AiMove<R> {
+    Placed {
+        player: Player,
+        label: String,
+        report: R,
+    },
+    NoSquare {
+        player: Player,
+    },
+}
+
+/// `Session::play` で進んだ結果
+pub struct Played<G: Game> {
+    /// 置いた手の結果 (持ち時間が切れていたときは置かずに None)
+    pub report: Option<G::Report>,
+    pub ai_move: Option<AiMove<G::Report>>,
+    pub outcome: Option<Outcome>,
+    /// 持ち時間が切れて負けた人
+    pub time_over: Option<Player>,
+}
+
+impl<G: Game> Session<G> {
+    pub fn new(game: G, black_user: SessionUser, white_user: SessionUser) -> Self {
+        Self {
+            game,
+            input_mode: InputMode::Buttons,
+            clock: Clock::new(TimeControl::default()),
+            ended: false,
+
+            black_user,
+            white_user,
+        }
+    }
+
+    pub fn user(&self, player: Player) -> &SessionUser {
+        match player {
+            Player::Black => &self.black_user,
+            Player::White => &self.white_user,
+        }
+    }
+
+    pub fn user_mut(&mut self, player: Player) -> &mut SessionUser {
+        match player {
+            Player::Black => &mut self.black_user,
+            Player::White => &mut self.white_user,
+        }
+    }
+
+    pub fn turn_user(&self) -> &SessionUser {
+        self.user(self.game.current_player())
+    }
+
+    pub fn turn_user_mut(&mut self) -> &mut SessionUser {
+        self.user_mut(self.game.current_player())
+    }
+
+    /// user_id の人が今置けるか
+    pub fn check_turn(&self, user_id: UserId) -> Result<(), BotError> {
+        if self.ended {
+            return Err(BotError::NoGame);
+        }
+
+        if user_id != self.turn_user().id {
+            return Err(BotError::NotYourTurn);
+        }
+
+        Ok(())
+    }
+
+    /// 手番の確認から石を置くまでを行い、次がBOTの手番ならBOTも同じ流れで置く
+    pub fn play(
+        &mut self,
+        user_id: UserId,
+        row: usize,
+        column: usize,
+        action: &str,
+    ) -> Result<Played<G>, BotError> {
+        return self.play_at(user_id, row, column, action, Instant::now());
+    }
+
+    fn play_at(
+        &mut self,
+        user_id: UserId,
+        row: usize,
+        column: usize,
+        action: &str,
+        now: Instant,
+    ) -> Result<Played<G>, BotError> {
+        if self.ended {
+            return Err(BotError::NoGame);
+        }
+
+        // 手番の人の持ち時間が切れていれば、どちらの対戦者が押しても置かずにその人の負けにする
+        let player = self.game.current_player();
+        let (black, white) = self.user_ids();
+        if (user_id == black || user_id == white) && self.clock.is_over(player, now) {
+            self.ended = true;
+            let winner = match player {
+                Player::Black => Player::White,
+                Player::White => Player::Black,
+            };
+
+            return Ok(Played {
+                report: None,
+                ai_move: None,
+                outcome: Some(Outcome::Winner(winner)),
+                time_over: Some(player),
+            });
+        }
+
+        self.check_turn(user_id)?;
+
+        let mv = self.game.parse_move(row, column, action)?;
+        let report = self.game.apply_move(mv)?;
+        self.clock.record(player, now);
+
+        let mut outcome = self.game.outcome(&report);
+        let mut ai_move = None;
+
+        if outcome.is_none() && self.turn_user().is_ai {
+            let player = self.game.current_player();
+
+            ai_move = Some(match self.game.ai_choice() {
+                Some((row, column, action)) => {
+                    let label = square_label(self.game.alphabets(), row, column);
+
+                    // 人が選んだときと同じように、選んだマスを記録してから置く
+                    {
+                        let ai_user = self.turn_user_mut();
+                        ai_user.choiced_alphabet = Some(label[..1].to_string());
+                        ai_user.choiced_number = Some(label[1..].to_string());
+                    }
+
+                    // 人の手はもう置いたので、BOTが置けないマスを選んでもエラーにはせず置けなかったことにする
+                    let applied = self
+                        .game
+                        .parse_move(row, column, action)
+                        .and_then(|mv| self.game.apply_move(mv));
+
+                    match applied {
+                        Ok(report) => {
+                            outcome = self.game.outcome(&report);
+
+                            AiMove::Placed {
+                                player,
+                                label,
+                                report,
+                            }
+                        }
+                        Err(why) => {
+                            log_error(&why);
+                            // 人の番に戻し、試合が止まらないようにする
+                            self.game.pass_turn();
+
+                            AiMove::NoSquare { player }
+                        }
+                    }
+                }
+                None => {
+                    self.game.pass_turn();
+
+                    AiMove::NoSquare { player }
+                }
+            });
+            self.clock.record(player, now);
+        }
+
+        if outcome.is_some() {
+            self.ended = true;
+        }
+
+        Ok(Played {
+            report: Some(report),
+            ai_move,
+            outcome,
+            time_over: None,
+        })
+    }
+
+    /// 対戦者のユーザーID (黒, 白)
+    pub fn user_ids(&self) -> (UserId, UserId) {
+        (self.black_user.id, self.white_user.id)
+    }
+
+    /// 手番の人の選んだ (アルファベットの番号, 数字 - 1)
+    pub fn choiced_square(&self) -> Option<(usize, usize)> {
+        let user = self.turn_user();
+
+        if let (Some(alphabet), Some(number)) = (&user.choiced_alphabet, &user.choiced_number) {
+            let row = self.game.alphabets().find(alphabet.as_str())?;
+            let column = number.parse::<usize>().ok()?.checked_sub(1)?;
+
+            return Some((row, column));
+        }
+
+        None
+    }
+
+    /// 手番の人が置けるマスのボタン、または行と列のセレクトメニューとボタン
+    pub fn components(&self, locale: Locale) -> Vec<CreateActionRow> {
+        let alphabets = self.game.alphabets();
+
+        // ボタンは1メッセージに 5x5 個まで (最後は「座標を入力」と盤面を見るボタンに使う)
+        if let (InputMode::Buttons, Some(legal_squares)) =
+            (self.input_mode, self.game.legal_squares())
+        {
+            if legal_squares.len() + 1 + G::VIEWS.len() <= 25 {
+                let mut buttons = vec![];
+
+                for (row, column) in legal_squares {
+                    let label = square_label(alphabets, row, column);
+
+                    buttons
+                        .push(CreateButton::new(format!("{}_place_{}", G::ID, label)).label(label));
+                }
+                buttons.push(
+                    CreateButton::new(format!("{}_input_{}", G::ID, G::ACTIONS[0].id))
+                        .label(G::ACTIONS[0].input_label.get(locale)),
+                );
+                buttons.extend(view_buttons::<G>(locale));
+
+                return buttons
+                    .chunks(5)
+                    .map(|buttons| CreateActionRow::Buttons(buttons.to_vec()))
+                    .collect();
+            }
+        }
+
+        let mut rows = choice_menus::<G>(&self.game, locale);
+
+        let mut buttons = vec![];
+        for action in G::ACTIONS {
+            buttons.push(
+                CreateButton::new(format!("{}_{}", G::ID, action.id))
+                    .label(action.label.get(locale)),
+            );
+        }
+        for action in G::ACTIONS {
+            buttons.push(
+                CreateButton::new(format!("{}_input_{}", G::ID, action.id))
+                    .label(action.input_label.get(locale)),
+            );
+        }
+        buttons.extend(view_buttons::<G>(locale));
+        rows.push(CreateActionRow::Buttons(buttons));
+
+        return rows;
+    }
+}
+
+fn view_buttons<G: Game>(locale: Locale) -> Vec<CreateButton> {
+    G::VIEWS
+        .iter()
+        .map(|view| {
+            CreateButton::new(format!("{}_view_{}", G::ID, view.id))
+                .label(view.label.get(locale))
+                .style(ButtonStyle::Secondary)
+        })
+        .collect()
+}
+
+/// 盤面のアルファベット・数字を選ぶセレクトメニュー (custom_id は `{ID}_choice_alphabet` など)
+fn choice_menus<G: Game>(game: &G, locale: Locale) -> Vec<CreateActionRow> {
+    let alphabets = game.alphabets();
+    let (alphabet_placeholder, number_placeholder) = game.menu_placeholders();
+
+    let kind = CreateSelectMenuKind::String {
+        options: {
+            let mut vec = vec![];
+            for i in alphabets.chars() {
+                vec.push(CreateSelectMenuOption::new(i, i));
+            }
+
+            vec
+        },
+    };
+    let select_choice_alphabet = CreateSelectMenu::new(format!("{}_choice_alphabet", G::ID), kind)
+        .placeholder(alphabet_placeholder.get(locale));
+
+    let kind = CreateSelectMenuKind::String {
+        options: {
+            let mut vec = vec![];
+            for i in 1..=alphabets.len() {
+                let string = i.to_string();
+                vec.push(CreateSelectMenuOption::new(string.clone(), string.clone()));
+            }
+
+            vec
+        },
+    };
+    let select_choice_number = CreateSelectMenu::new(format!("{}_choice_number", G::ID), kind)
+        .placeholder(number_placeholder.get(locale));
+
+    let row_0 = CreateActionRow::SelectMenu(select_choice_alphabet);
+    let row_1 = CreateActionRow::SelectMenu(select_choice_number);
+
+    return vec![row_0, row_1];
+}
+
+/// 対戦者の名前を調べる (`user_ids` は (黒, 白))
+///
+/// 試合のロックを持ったまま Discord に問い合わせないよう、ユーザーIDを写してから呼ぶ。
+pub async fn names(discord: &impl Discord, user_ids: (UserId, UserId)) -> Names {
+    let (black, white) = user_ids;
+
+    Names {
+        black: discord.username(black).await,
+        white: discord.username(white).await,
+    }
+}
+
+/// チャンネルの試合を取り出す (表のロックはすぐに離す)
+pub async fn find_session<G: Game>(
+    sessions: &SessionMap<G>,
+    channel_id: u64,
+) -> Result<SharedSession<G>, BotError> {
+    let sessions = sessions.lock().await;
+
+    sessions.get(&channel_id).cloned().ok_or(BotError::NoGame)
+}
+
+/// 決着した試合を表から外す (同じチャンネルで次の試合が始まっていたら外さない)
+pub async fn remove_session<G: Game>(
+    sessions: &SessionMap<G>,
+    channel_id: u64,
+    session: &SharedSession<G>,
+) {
+    let mut sessions = sessions.lock().await;
+
+    if let Some(current) = sessions.get(&channel_id) {
+        if Arc::ptr_eq(current, session) {
+            sessions.remove(&channel_id);
+        }
+    }
+}
+
+/// 決着した試合 (新しいものから FINISHED_CAPACITY 試合まで)
+pub struct FinishedSessions<G: Game> {
+    /// (番号, チャンネルID, 試合)
+    sessions: VecDeque<(u32, u64, SharedSession<G>)>,
+}
+
+impl<G: Game> Default for FinishedSessions<G> {
+    fn default() -> Self {
+        Self {
+            sessions: VecDeque::new(),
+        }
+    }
+}
+
+impl<G: Game> FinishedSessions<G> {
+    /// 試合を残し、「解析」ボタンに付ける番号を返す
+    pub fn push(&mut self, channel_id: u64, session: SharedSession<G>) -> u32 {
+        // 再起動する前のボタンで別の試合を解析しないよう、番号は乱数にする
+        let id = rand::random();
+
+        if self.sessions.len() >= FINISHED_CAPACITY {
+            self.sessions.pop_front();
+        }
+        self.sessions.push_back((id, channel_id, session));
+
+        return id;
+    }
+
+    /// channel_id のチャンネルで決着した、番号が id の試合
+    pub fn get(&self, channel_id: u64, id: u32) -> Option<SharedSession<G>> {
+        self.sessions
+            .iter()
+            .find(|(session_id, session_channel_id, _)| {
+                *session_id == id && *session_channel_id == channel_id
+            })
+            .map(|(_, _, session)| session.clone())
+    }
+}
+
+/// 決着したメッセージの「解析」ボタン
+pub fn analysis_components<G: Game>(id: u32, locale: Locale) -> Vec<CreateActionRow> {
+    let button = CreateButton::new(format!("{}_analyze_{}", G::ID, id))
+        .label(Text::Analyze.get(locale))
+        .style(ButtonStyle::Secondary);
+
+    vec![CreateActionRow::Buttons(vec![button])]
+}
+
+/// (アルファベットの番号, 数字 - 1) を "D3" のような文字列にする
+pub fn square_label(alphabets: &str, row: usize, column: usize) -> String {
+    let alphabet = alphabets.chars().nth(row).unwrap();
+
+    format!("{}{}", alphabet, column + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Rendered;
+    use crate::render::theme::BoardStyle;
+    use crate::reversi::reversi::{Reversi, Stone};
+    use serenity::all::ResolvedOption;
+    use std::future::Future;
+    use tokio::sync::Barrier;
+
+    fn reversi_sessions(black: UserId, white: UserId) -> SessionMap<Reversi> {
+        let session = Session::new(
+            Reversi::new(),
+            SessionUser::new(black),
+            SessionUser::new(white),
+        );
+
+        Arc::new(Mutex::new(HashMap::from([(
+            0,
+            Arc::new(Mutex::new(session)),
+        )])))
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn simultaneous_moves() {
+        let black = UserId::new(1);
+        let sessions = reversi_sessions(black, UserId::new(2));
+
+        // 黒が置ける4マスのボタンを同時に押す
+        let squares = [(2, 3), (3, 2), (4, 5), (5, 4)];
+        let barrier = Arc::new(Barrier::new(squares.len()));
+
+        let mut handles = vec![];
+        for (row, column) in squares {
+            let sessions = sessions.clone();
+            let barrier = barrier.clone();
+
+            handles.push(tokio::spawn(async move {
+                barrier.wait().await;
+
+                let session = find_session(&sessions, 0).await?;
+                let mut session = session.lock().await;
+
+                session.play(black, row, column, "push_stone").map(|_| ())
+            }));
+        }
+
+        let mut results = vec![];
+        for handle in handles {
+            results.push(handle.await.unwrap());
+        }
+
+        assert_eq!(1, results.iter().filter(|result| result.is_ok()).count());
+        assert!(results
+            .iter()
+            .filter_map(|result| result.as_ref().err())
+            .all(|why| matches!(why, BotError::NotYourTurn)));
+
+        let session = find_session(&sessions, 0).await.unwrap();
+        let session = session.lock().await;
+        assert_eq!(4, session.game.count_stones(Stone::BLACK));
+        assert_eq!(1, session.game.count_stones(Stone::WHITE));
+    }
+
+    #[test]
+    fn finished_sessions() {
+        let mut finished = FinishedSessions::<Reversi>::default();
+        let session = |user_id| {
+            let user_id = UserId::new(user_id);
+            Arc::new(Mutex::new(Session::new(
+                Reversi::new(),
+                SessionUser::new(user_id),
+                SessionUser::new(user_id),
+            )))
+        };
+
+        let first = session(1);
+        let first_id = finished.push(0, first.clone());
+        assert!(Arc::ptr_eq(&first, &finished.get(0, first_id).unwrap()));
+        assert!(
+            finished.get(1, first_id).is_none(),
+            "ほかのチャンネルのボタン"
+        );
+
+        for _ in 0..FINISHED_CAPACITY {
+            finished.push(0, session(2));
+        }
+        assert!(finished.get(0, first_id).is_none(), "古い試合から消える");
+        assert_eq!(FINISHED_CAPACITY, finished.sessions.len());
+    }
+
+    /// 置けないマスを選ぶBOT
+    struct IllegalAi(Reversi);
+
+    impl Game for IllegalAi {
+        type Move = <Reversi as Game>::Move;
+        type Report = <Reversi as Game>::Report;
+
+        const ID: &'static str = "illegal_ai";
+        const COMMAND: &'static str = "illegal_ai";
+        const NAME: Text = Text::ReversiName;
+        const HAS_AI: bool = true;
+
+        fn new(_options: &[ResolvedOption]) -> Self {
+            IllegalAi(Reversi::new())
+        }
+
+        fn alphabets(&self) -> &'static str {
+            self.0.alphabets()
+        }
+
+        fn current_player(&self) -> Player {
+            self.0.current_player()
+        }
+
+        fn parse_move(
+            &self,
+            row: usize,
+            column: usize,
+            action: &str,
+        ) -> Result<Self::Move, BotError> {
+            self.0.parse_move(row, column, action)
+        }
+
+        fn apply_move(&mut self, mv: Self::Move) -> Result<Self::Report, BotError> {
+            self.0.apply_move(mv)
+        }
+
+        fn outcome(&self, report: &Self::Report) -> Option<Outcome> {
+            self.0.outcome(report)
+        }
+
+        fn pass_turn(&mut self) {
+            self.0.pass_turn()
+        }
+
+        fn ai_choice(&self) -> Option<(usize, usize, &'static str)> {
+            // 初期配置の角には置けない
+            Some((0, 0, "push_stone"))
+        }
+
+        fn render(&self, style: BoardStyle) -> impl Future<Output = Rendered> + Send + 'static {
+            self.0.render(style)
+        }
+    }
+
+    #[test]
+    fn illegal_ai_move() {
+        let black = UserId::new(1);
+        let mut session = Session::new(
+            IllegalAi(Reversi::new()),
+            SessionUser::new(black),
+            SessionUser::new_ai(UserId::new(2)),
+        );
+
+        // BOTが置けなくても、人の手は置いたまま Ok を返す
+        let played = session.play(black, 2, 3, "push_stone").unwrap();
+        assert!(matches!(
+            played.ai_move,
+            Some(AiMove::NoSquare {
+                player: Player::White
+            })
+        ));
+        assert!(played.outcome.is_none());
+        assert_eq!(4, session.game.0.count_stones(Stone::BLACK));
+        assert_eq!(1, session.game.0.count_stones(Stone::WHITE));
+
+        // BOTはパスし、人の番に戻る
+        assert_eq!(black, session.turn_user().id);
+        let played = session.play(black, 4, 5, "push_stone").unwrap();
+        assert_eq!(6, session.game.0.count_stones(Stone::BLACK));
+        assert!(matches!(
+            played.outcome,
+            Some(Outcome::Winner(Player::Black))
+        ));
+    }
+
+    fn timed_session(time_control: TimeControl) -> Session<Reversi> {
+        let mut session = Session::new(
+            Reversi::new(),
+            SessionUser::new(UserId::new(1)),
+            SessionUser::new(UserId::new(2)),
+        );
+        session.clock = Clock::new(time_control);
+
+        session
+    }
+
+    #[test]
+    fn move_time_over() {
+        let (black, white) = (UserId::new(1), UserId::new(2));
+        let mut session = timed_session(TimeControl {
+            move_seconds: Some(60),
+            game_minutes: None,
+        });
+        let start = session.clock.turn_started;
+
+        let played = session
+            .play_at(black, 2, 3, "push_stone", start + Duration::from_secs(50))
+            .unwrap();
+        assert!(played.report.is_some() && played.time_over.is_none());
+
+        // 白が 61 秒置かなかったので、黒が押しても白の負けになる
+        let played = session
+            .play_at(black, 2, 2, "push_stone", start + Duration::from_secs(111))
+            .unwrap();
+        assert!(played.report.is_none());
+        assert_eq!(Some(Player::White), played.time_over);
+        assert_eq!(Some(Outcome::Winner(Player::Black)), played.outcome);
+        assert!(session.ended);
+        assert!(matches!(
+            session.play_at(white, 2, 2, "push_stone", start + Duration::from_secs(112)),
+            Err(BotError::NoGame)
+        ));
+    }
+
+    #[test]
+    fn game_time_over() {
+        let (black, white) = (UserId::new(1), UserId::new(2));
+        let mut session = timed_session(TimeControl {
+            move_seconds: None,
+            game_minutes: Some(1),
+        });
+        let start = session.clock.turn_started;
+        let at = |seconds| start + Duration::from_secs(seconds);
+
+        // 黒が 40 秒、白が 10 秒使う
+        session.play_at(black, 2, 3, "push_stone", at(40)).unwrap();
+        session.play_at(white, 2, 2, "push_stone", at(50)).unwrap();
+
+        // 1手ごとには間に合っていても、合わせて 60 秒を超えると黒の負けになる
+        let played = session.play_at(black, 2, 1, "push_stone", at(71)).unwrap();
+        assert_eq!(Some(Player::Black), played.time_over);
+        assert_eq!(Some(Outcome::Winner(Player::White)), played.outcome);
+
+        // 対戦者でない人が押しても決着させない
+        let mut session = timed_session(TimeControl {
+            move_seconds: Some(1),
+            game_minutes: None,
+        });
+        let start = session.clock.turn_started;
+        assert!(matches!(
+            session.play_at(
+                UserId::new(3),
+                2,
+                3,
+                "push_stone",
+                start + Duration::from_secs(5)
+            ),
+            Err(BotError::NotYourTurn)
+        ));
+        assert!(!session.ended);
+    }
+
+    #[tokio::test]
+    async fn move_after_end() {
+        let black = UserId::new(1);
+        let sessions = reversi_sessions(black, UserId::new(2));
+
+        // `/reversi_end` と同じく、表から外したあとに終了済みにする
+        let session = sessions.lock().await.remove(&0).unwrap();
+        session.lock().await.ended = true;
+
+        let result = session.lock().await.play(black, 2, 3, "push_stone");
+        assert!(matches!(result, Err(BotError::NoGame)));
+        assert!(matches!(
+            find_session(&sessions, 0).await,
+            Err(BotError::NoGame)
+        ));
+    }
+}
@@ -1,18 +1,74 @@
+use serenity::all::UserId;
 use serenity::prelude::*;
-use std::collections::HashMap;
+use std::marker::PhantomData;
 use std::sync::Arc;
-use tokio::sync::Mutex;
 
-use crate::{quantum_gomoku, reversi};
+use crate::config::TimeControl;
+use crate::discord::Discord;
+use crate::error::BotError;
+use crate::game::session::{SessionMap, SharedFinishedSessions};
+use crate::game::Game;
+use crate::settings::{Settings, UserSettings};
 
-pub struct GlobalReversiStats;
+/// ゲームごとの、チャンネルIDと試合の対応
+pub struct GlobalSessions<G: Game>(PhantomData<G>);
 
-impl TypeMapKey for GlobalReversiStats {
-    type Value = Arc<Mutex<HashMap<u64, reversi::stats::RStats>>>;
+impl<G: Game> TypeMapKey for GlobalSessions<G> {
+    type Value = SessionMap<G>;
 }
 
-pub struct GlobalQuantumGomokuStats;
+pub async fn get_sessions<G: Game>(discord: &impl Discord) -> Result<SessionMap<G>, BotError> {
+    let data = discord.data().read().await;
 
-impl TypeMapKey for GlobalQuantumGomokuStats {
-    type Value = Arc<Mutex<HashMap<u64, quantum_gomoku::stats::QGStats>>>;
+    data.get::<GlobalSessions<G>>()
+        .cloned()
+        .ok_or(BotError::MissingData("GlobalSessions"))
+}
+
+/// ゲームごとの、決着した試合
+pub struct GlobalFinishedSessions<G: Game>(PhantomData<G>);
+
+impl<G: Game> TypeMapKey for GlobalFinishedSessions<G> {
+    type Value = SharedFinishedSessions<G>;
+}
+
+pub async fn get_finished_sessions<G: Game>(
+    discord: &impl Discord,
+) -> Result<SharedFinishedSessions<G>, BotError> {
+    let data = discord.data().read().await;
+
+    data.get::<GlobalFinishedSessions<G>>()
+        .cloned()
+        .ok_or(BotError::MissingData("GlobalFinishedSessions"))
+}
+
+/// 利用者ごとの設定
+pub struct GlobalSettings;
+
+impl TypeMapKey for GlobalSettings {
+    type Value = Arc<RwLock<Settings>>;
+}
+
+/// `user_id` の設定 (設定の表がなければ既定値)
+pub async fn get_user_settings(discord: &impl Discord, user_id: UserId) -> UserSettings {
+    let data = discord.data().read().await;
+
+    match data.get::<GlobalSettings>() {
+        Some(settings) => settings.read().await.get(user_id),
+        None => UserSettings::default(),
+    }
+}
+
+/// 試合の持ち時間 (`config.json` の `time_control`)
+pub struct GlobalTimeControl;
+
+impl TypeMapKey for GlobalTimeControl {
+    type Value = TimeControl;
+}
+
+/// 試合の持ち時間 (入っていなければ無制限)
+pub async fn get_time_control(discord: &impl Discord) -> TimeControl {
+    let data = discord.data().read().await;
+
+    data.get::<GlobalTimeControl>().copied().unwrap_or_default()
 }
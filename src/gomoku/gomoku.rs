@@ -0,0 +1,499 @@
+/*
+通常の五目並べ (自由・標準・連珠ルール) のプログラムです。
+
+盤面と石は量子五目並べの観測後の盤面と同じ型を使い、描画も共通化しています。
+*/
+
+use crate::color::Color;
+use crate::quantum_gomoku::gomoku::{ConnectedMass, ObservedStone, LINE_MOVES};
+
+// 盤面は 15x15 (配列は量子五目並べと同じ 19x19)
+pub const BOARD_SIZE: i32 = 15;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rule {
+    /// 5つ以上並べれば勝ち
+    FreeStyle,
+    /// ちょうど5つ並べたときのみ勝ち (長連は勝ちにならない)
+    Standard,
+    /// 黒のみ三三・四四・長連が禁じ手
+    Renju,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Forbidden {
+    DoubleThree,
+    DoubleFour,
+    Overline,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlaceError {
+    OutOfBoardRange,
+    AlreadyPlaced,
+    /// 連珠ルールの黒の禁じ手
+    Forbidden(Forbidden),
+}
+
+#[derive(Debug)]
+pub struct Gomoku {
+    pub board: [[ObservedStone; 19]; 19],
+    pub current_turn: Color,
+    pub rule: Rule,
+    pub last_move: Option<(usize, usize)>,
+}
+
+impl Gomoku {
+    pub fn new(rule: Rule) -> Self {
+        Self {
+            board: [[ObservedStone::None; 19]; 19],
+            current_turn: Color::Black,
+            rule,
+            last_move: None,
+        }
+    }
+
+    pub fn switch_turn(&mut self) {
+        self.current_turn = self.current_turn.rival();
+    }
+
+    pub fn do_place(&mut self, row: usize, column: usize) -> Result<(), PlaceError> {
+        if !(row < BOARD_SIZE as usize && column < BOARD_SIZE as usize) {
+            return Err(PlaceError::OutOfBoardRange);
+        }
+
+        if self.board[row][column] != ObservedStone::None {
+            return Err(PlaceError::AlreadyPlaced);
+        }
+
+        if self.current_turn == Color::Black && self.rule == Rule::Renju {
+            if let Some(forbidden) = self.forbidden_move(row, column) {
+                return Err(PlaceError::Forbidden(forbidden));
+            }
+        }
+
+        self.board[row][column] = self.current_turn.into();
+        self.last_move = Some((row, column));
+        return Ok(());
+    }
+
+    /// 最後に置いた石で勝敗が決まったか調べる
+    pub fn judge_winner(&self) -> Option<(ObservedStone, ConnectedMass)> {
+        let (row, column) = self.last_move?;
+        let (row, column) = (row as i32, column as i32);
+        let color = self.board[row as usize][column as usize];
+
+        let allow_overline = match (self.rule, color) {
+            (Rule::FreeStyle, _) => true,
+            (Rule::Standard, _) => false,
+            (Rule::Renju, ObservedStone::White) => true,
+            (Rule::Renju, _) => false,
+        };
+
+        for move_ in LINE_MOVES {
+            let (start, length) = Self::run(&self.board, row, column, move_, color);
+
+            if length == 5 || (allow_overline && length > 5) {
+                let mut mass = [(0, 0); 5];
+                for (i, pos) in mass.iter_mut().enumerate() {
+                    *pos = (
+                        (start.0 + move_.0 * i as i32) as usize,
+                        (start.1 + move_.1 * i as i32) as usize,
+                    );
+                }
+
+                return Some((color, mass));
+            }
+        }
+
+        return None;
+    }
+
+    pub fn is_board_full(&self) -> bool {
+        (0..BOARD_SIZE as usize).all(|row| {
+            (0..BOARD_SIZE as usize).all(|column| self.board[row][column] != ObservedStone::None)
+        })
+    }
+
+    /// 黒がそのマスに置いたときの禁じ手を調べる
+    ///
+    /// 五連ができる場合は禁じ手より優先される。
+    /// 三の判定は達四になる空きマスがあるかどうかのみで、その空きマス自体が禁じ手かどうかは見ていない。
+    pub fn forbidden_move(&self, row: usize, column: usize) -> Option<Forbidden> {
+        let (row, column) = (row as i32, column as i32);
+
+        if Self::stone_at(&self.board, row, column) != ObservedStone::None {
+            return None;
+        }
+
+        let mut board = self.board;
+        board[row as usize][column as usize] = ObservedStone::Black;
+
+        let lengths =
+            LINE_MOVES.map(|move_| Self::run(&board, row, column, move_, ObservedStone::Black).1);
+
+        if lengths.contains(&5) {
+            return None;
+        }
+
+        if lengths.iter().any(|length| *length > 5) {
+            return Some(Forbidden::Overline);
+        }
+
+        let fours: usize = LINE_MOVES
+            .iter()
+            .map(|move_| Self::count_fours(&board, row, column, *move_))
+            .sum();
+
+        if fours >= 2 {
+            return Some(Forbidden::DoubleFour);
+        }
+
+        let threes = LINE_MOVES
+            .iter()
+            .filter(|move_| Self::is_open_three(&board, row, column, **move_))
+            .count();
+
+        if threes >= 2 {
+            return Some(Forbidden::DoubleThree);
+        }
+
+        return None;
+    }
+
+    fn stone_at(board: &[[ObservedStone; 19]; 19], row: i32, column: i32) -> ObservedStone {
+        if (0..BOARD_SIZE).contains(&row) && (0..BOARD_SIZE).contains(&column) {
+            board[row as usize][column as usize]
+        } else {
+            ObservedStone::None
+        }
+    }
+
+    fn is_empty(board: &[[ObservedStone; 19]; 19], row: i32, column: i32) -> bool {
+        (0..BOARD_SIZE).contains(&row)
+            && (0..BOARD_SIZE).contains(&column)
+            && board[row as usize][column as usize] == ObservedStone::None
+    }
+
+    /// (row, column) を含む同色の連の始点と長さ
+    fn run(
+        board: &[[ObservedStone; 19]; 19],
+        row: i32,
+        column: i32,
+        move_: (i32, i32),
+        color: ObservedStone,
+    ) -> ((i32, i32), usize) {
+        let (move_row, move_column) = move_;
+
+        let mut start = (row, column);
+        while Self::stone_at(board, start.0 - move_row, start.1 - move_column) == color {
+            start = (start.0 - move_row, start.1 - move_column);
+        }
+
+        let mut length = 0;
+        let mut cur = start;
+        while Self::stone_at(board, cur.0, cur.1) == color {
+            length += 1;
+            cur = (cur.0 + move_row, cur.1 + move_column);
+        }
+
+        return (start, length);
+    }
+
+    /// 黒を置くと (row, column) を含むちょうど五連になる空きマス
+    fn five_points(
+        board: &[[ObservedStone; 19]; 19],
+        row: i32,
+        column: i32,
+        move_: (i32, i32),
+    ) -> Vec<(i32, i32)> {
+        let (move_row, move_column) = move_;
+        let mut points = vec![];
+
+        for i in -4..=4 {
+            let point = (row + move_row * i, column + move_column * i);
+
+            if !Self::is_empty(board, point.0, point.1) {
+                continue;
+            }
+
+            let mut board = *board;
+            board[point.0 as usize][point.1 as usize] = ObservedStone::Black;
+
+            if Self::run(&board, row, column, move_, ObservedStone::Black).1 == 5 {
+                points.push(point);
+            }
+        }
+
+        return points;
+    }
+
+    fn count_fours(
+        board: &[[ObservedStone; 19]; 19],
+        row: i32,
+        column: i32,
+        move_: (i32, i32),
+    ) -> usize {
+        let points = Self::five_points(board, row, column, move_);
+
+        // 達四 (両端が空いた四) は五にできるマスが2つあっても1つの四として数える
+        if Self::is_straight_four(&points, move_) {
+            return 1;
+        }
+
+        return points.len();
+    }
+
+    fn is_straight_four(points: &[(i32, i32)], move_: (i32, i32)) -> bool {
+        if let [first, second] = points {
+            let distance = if move_.0 != 0 {
+                (second.0 - first.0) / move_.0
+            } else {
+                (second.1 - first.1) / move_.1
+            };
+
+            distance.abs() == 5
+        } else {
+            false
+        }
+    }
+
+    fn is_open_three(
+        board: &[[ObservedStone; 19]; 19],
+        row: i32,
+        column: i32,
+        move_: (i32, i32),
+    ) -> bool {
+        let (move_row, move_column) = move_;
+
+        for i in -4..=4 {
+            let point = (row + move_row * i, column + move_column * i);
+
+            if !Self::is_empty(board, point.0, point.1) {
+                continue;
+            }
+
+            let mut board = *board;
+            board[point.0 as usize][point.1 as usize] = ObservedStone::Black;
+
+            let points = Self::five_points(&board, row, column, move_);
+            if Self::is_straight_four(&points, move_) {
+                return true;
+            }
+        }
+
+        return false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn place_all(gomoku: &mut Gomoku, stones: &[(usize, usize)], color: ObservedStone) {
+        for (row, column) in stones {
+            gomoku.board[*row][*column] = color;
+        }
+    }
+
+    #[test]
+    fn turn_change() {
+        let mut gomoku = Gomoku::new(Rule::FreeStyle);
+        assert_eq!(gomoku.current_turn, Color::Black);
+
+        gomoku.switch_turn();
+        assert_eq!(gomoku.current_turn, Color::White);
+
+        gomoku.switch_turn();
+        assert_eq!(gomoku.current_turn, Color::Black);
+    }
+
+    #[test]
+    fn do_place() {
+        let mut gomoku = Gomoku::new(Rule::FreeStyle);
+        gomoku.board[0][0] = ObservedStone::Black;
+
+        assert_eq!(Err(PlaceError::OutOfBoardRange), gomoku.do_place(15, 0));
+        assert_eq!(Err(PlaceError::AlreadyPlaced), gomoku.do_place(0, 0));
+        assert_eq!(Ok(()), gomoku.do_place(0, 1));
+        assert_eq!(Some((0, 1)), gomoku.last_move);
+    }
+
+    #[test]
+    fn judge_winner_freestyle() {
+        let mut gomoku = Gomoku::new(Rule::FreeStyle);
+        place_all(
+            &mut gomoku,
+            &[(7, 2), (7, 3), (7, 4), (7, 6), (7, 7)],
+            ObservedStone::Black,
+        );
+
+        gomoku.do_place(7, 5).unwrap();
+        assert_eq!(
+            Some((
+                ObservedStone::Black,
+                [(7, 2), (7, 3), (7, 4), (7, 5), (7, 6)]
+            )),
+            gomoku.judge_winner()
+        );
+    }
+
+    #[test]
+    fn judge_winner_standard() {
+        let mut gomoku = Gomoku::new(Rule::Standard);
+        place_all(
+            &mut gomoku,
+            &[(7, 2), (7, 3), (7, 4), (7, 6), (7, 7)],
+            ObservedStone::Black,
+        );
+
+        // 長連は勝ちにならない
+        gomoku.do_place(7, 5).unwrap();
+        assert_eq!(None, gomoku.judge_winner());
+
+        let mut gomoku = Gomoku::new(Rule::Standard);
+        place_all(
+            &mut gomoku,
+            &[(3, 3), (4, 4), (5, 5), (6, 6)],
+            ObservedStone::Black,
+        );
+
+        gomoku.do_place(7, 7).unwrap();
+        assert_eq!(
+            Some((
+                ObservedStone::Black,
+                [(3, 3), (4, 4), (5, 5), (6, 6), (7, 7)]
+            )),
+            gomoku.judge_winner()
+        );
+    }
+
+    #[test]
+    fn judge_winner_renju_white_overline() {
+        let mut gomoku = Gomoku::new(Rule::Renju);
+        gomoku.switch_turn();
+        place_all(
+            &mut gomoku,
+            &[(2, 7), (3, 7), (4, 7), (6, 7), (7, 7)],
+            ObservedStone::White,
+        );
+
+        gomoku.do_place(5, 7).unwrap();
+        assert_eq!(
+            Some(ObservedStone::White),
+            gomoku.judge_winner().map(|x| x.0)
+        );
+    }
+
+    #[test]
+    fn forbidden_double_three() {
+        let mut gomoku = Gomoku::new(Rule::Renju);
+        place_all(
+            &mut gomoku,
+            &[(7, 5), (7, 6), (5, 8), (6, 8)],
+            ObservedStone::Black,
+        );
+
+        assert_eq!(Some(Forbidden::DoubleThree), gomoku.forbidden_move(7, 8));
+        assert_eq!(
+            Err(PlaceError::Forbidden(Forbidden::DoubleThree)),
+            gomoku.do_place(7, 8)
+        );
+
+        // 片方の三が白で止められていれば禁じ手ではない
+        gomoku.board[7][4] = ObservedStone::White;
+        assert_eq!(None, gomoku.forbidden_move(7, 8));
+    }
+
+    #[test]
+    fn forbidden_double_three_split() {
+        let mut gomoku = Gomoku::new(Rule::Renju);
+        place_all(
+            &mut gomoku,
+            &[(7, 4), (7, 6), (4, 5), (5, 5)],
+            ObservedStone::Black,
+        );
+
+        // 横の三と縦の飛び三 (●●・●)
+        assert_eq!(Some(Forbidden::DoubleThree), gomoku.forbidden_move(7, 5));
+        assert_eq!(None, gomoku.forbidden_move(8, 8));
+    }
+
+    #[test]
+    fn forbidden_double_four() {
+        let mut gomoku = Gomoku::new(Rule::Renju);
+        place_all(
+            &mut gomoku,
+            &[(7, 4), (7, 5), (7, 6), (4, 7), (5, 7), (6, 7)],
+            ObservedStone::Black,
+        );
+        gomoku.board[7][3] = ObservedStone::White;
+        gomoku.board[3][7] = ObservedStone::White;
+
+        assert_eq!(Some(Forbidden::DoubleFour), gomoku.forbidden_move(7, 7));
+    }
+
+    #[test]
+    fn forbidden_double_four_in_line() {
+        let mut gomoku = Gomoku::new(Rule::Renju);
+        place_all(
+            &mut gomoku,
+            &[(7, 3), (7, 5), (7, 6), (7, 9)],
+            ObservedStone::Black,
+        );
+
+        // ●・●●◎・● は一直線の四四
+        assert_eq!(Some(Forbidden::DoubleFour), gomoku.forbidden_move(7, 7));
+    }
+
+    #[test]
+    fn forbidden_overline() {
+        let mut gomoku = Gomoku::new(Rule::Renju);
+        place_all(
+            &mut gomoku,
+            &[(7, 2), (7, 3), (7, 4), (7, 6), (7, 7)],
+            ObservedStone::Black,
+        );
+
+        assert_eq!(Some(Forbidden::Overline), gomoku.forbidden_move(7, 5));
+    }
+
+    #[test]
+    fn five_beats_forbidden() {
+        let mut gomoku = Gomoku::new(Rule::Renju);
+        place_all(
+            &mut gomoku,
+            &[(7, 3), (7, 4), (7, 5), (7, 6), (4, 7), (5, 7), (6, 7)],
+            ObservedStone::Black,
+        );
+        gomoku.board[3][7] = ObservedStone::White;
+
+        assert_eq!(None, gomoku.forbidden_move(7, 7));
+        gomoku.do_place(7, 7).unwrap();
+        assert_eq!(
+            Some(ObservedStone::Black),
+            gomoku.judge_winner().map(|x| x.0)
+        );
+    }
+
+    #[test]
+    fn straight_four_is_not_double_four() {
+        let mut gomoku = Gomoku::new(Rule::Renju);
+        place_all(&mut gomoku, &[(7, 4), (7, 5), (7, 6)], ObservedStone::Black);
+
+        assert_eq!(None, gomoku.forbidden_move(7, 7));
+    }
+
+    #[test]
+    fn white_has_no_forbidden() {
+        let mut gomoku = Gomoku::new(Rule::Renju);
+        gomoku.switch_turn();
+        place_all(
+            &mut gomoku,
+            &[(7, 5), (7, 6), (5, 8), (6, 8)],
+            ObservedStone::White,
+        );
+
+        assert_eq!(Ok(()), gomoku.do_place(7, 8));
+    }
+}
@@ -0,0 +1,694 @@
+/*
+利用者に見せるメッセージの日本語・英語の対応です。
+
+メッセージは `Text` の値で指定し、操作した人の Discord の言語 (interaction の `locale`) で選びます。
+対応していない言語の人には `config.json` の `locale` を使います。
+コマンドの名前・説明は日本語を基本にして、英語の名前・説明も一緒に登録します。
+*/
+
+use serde::Deserialize;
+use serenity::all::{CommandOptionType, CreateCommand, CreateCommandOption};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    Ja,
+    En,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::Ja, Locale::En];
+
+    /// Discord の locale ("ja", "en-US" など) から選ぶ
+    pub fn from_discord(locale: &str) -> Option<Locale> {
+        match locale {
+            "ja" => Some(Locale::Ja),
+            locale if locale.starts_with("en") => Some(Locale::En),
+            _ => None,
+        }
+    }
+
+    /// 対応していない言語なら `default` にする
+    pub fn pick(discord_locale: &str, default: Locale) -> Locale {
+        Locale::from_discord(discord_locale).unwrap_or(default)
+    }
+
+    /// コマンドの名前・説明を登録する Discord の locale
+    fn discord_codes(self) -> &'static [&'static str] {
+        match self {
+            Locale::Ja => &["ja"],
+            Locale::En => &["en-US", "en-GB"],
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Text {
+    // エラー
+    InternalError,
+    NoGame,
+    NotYourTurn,
+    NotChoiced,
+    InvalidCoordinate,
+    Forbidden,
+    CannotPlace,
+    NoFinishedGame,
+
+    // 試合の進行
+    TurnInfo,
+    AiPlaced,
+    AiNoSquare,
+    TimeOver,
+    TimeControlMove,
+    TimeControlGame,
+    Winner,
+    Draw,
+    Place,
+    InputCoordinate,
+    ColumnPlaceholder,
+    RowPlaceholder,
+    CoordinateField,
+
+    // コマンド
+    InvalidRival,
+    BotRival,
+    AlreadyPlaying,
+    GameEnded,
+    MultipleGames,
+    StartCommand,
+    StartDescription,
+    EndCommand,
+    EndDescription,
+    RivalOption,
+    RivalOptionDescription,
+    MoveCommand,
+    MoveDescription,
+    CoordinateOption,
+    CoordinateOptionDescription,
+    ObserveOption,
+    ObserveOptionDescription,
+    GameOption,
+    GameOptionDescription,
+
+    // リバーシ
+    ReversiName,
+    InputOption,
+    InputOptionDescription,
+    InputButtons,
+    InputMenus,
+    ReversiPass,
+    ReversiResult,
+    ReversiWinnerBlack,
+    ReversiWinnerWhite,
+    ReversiWinnerDraw,
+    Opening,
+    OpeningCommand,
+    OpeningDescription,
+    MovesOption,
+    MovesOptionDescription,
+    InvalidMoves,
+    IllegalMoveInSequence,
+    MovesNote,
+    NoOpening,
+    OpeningContinuations,
+    Analyze,
+    AnalysisTitle,
+    AnalysisDescription,
+    AnalysisPlayer,
+    AnalysisMoves,
+    AnalysisMove,
+    AnalysisMoreMoves,
+    AnalysisNoMistakes,
+    Blunder,
+    Mistake,
+
+    // 量子五目並べ
+    QuantumGomokuName,
+    QuantumGomokuIntro,
+    Observe,
+    InputObserve,
+    Heatmap,
+    HeatmapNote,
+    StoneBlack90,
+    StoneBlack70,
+    StoneWhite90,
+    StoneWhite70,
+    NotAligned,
+    BlackAligned,
+    WhiteAligned,
+
+    // 量子リバーシ
+    QuantumReversiName,
+    QuantumReversiIntro,
+    QuantumReversiObserved,
+
+    // 五目並べ
+    GomokuName,
+    RuleOption,
+    RuleOptionDescription,
+    RuleStandard,
+    RuleFreeStyle,
+    RuleRenju,
+    RuleStandardDetail,
+    RuleFreeStyleDetail,
+    RuleRenjuDetail,
+    RuleIntro,
+    RuleAnti,
+    RuleAntiDetail,
+    BoardFullDraw,
+    SizeOption,
+    SizeOptionDescription,
+    LayoutOption,
+    LayoutOptionDescription,
+    LayoutCross,
+    LayoutParallel,
+    BoardIntro,
+
+    // 設定
+    SettingsCommand,
+    SettingsDescription,
+    ThemeOption,
+    ThemeSubcommandDescription,
+    ThemeOptionDescription,
+    ThemeWood,
+    ThemeDark,
+    ThemeHighContrast,
+    ThemeColorblind,
+    ThemeChanged,
+    LabelsOption,
+    LabelsSubcommandDescription,
+    ShowOption,
+    ShowOptionDescription,
+    LabelsShown,
+    LabelsHidden,
+}
+
+impl Text {
+    pub fn get(self, locale: Locale) -> &'static str {
+        match locale {
+            Locale::Ja => ja(self),
+            Locale::En => en(self),
+        }
+    }
+
+    /// `{name}` などを `args` の値で置き換える
+    pub fn format(self, locale: Locale, args: &[(&str, &str)]) -> String {
+        let mut text = self.get(locale).to_string();
+
+        for (key, value) in args {
+            text = text.replace(&format!("{{{}}}", key), value);
+        }
+
+        return text;
+    }
+}
+
+fn ja(text: Text) -> &'static str {
+    match text {
+        Text::InternalError => "エラーが発生しました。",
+        Text::NoGame => "試合は行われていません。",
+        Text::NotYourTurn => "手番ではないため置けません。",
+        Text::NotChoiced => "行と列を選択してください。",
+        Text::InvalidCoordinate => "座標が正しくありません。(例: D3)",
+        Text::Forbidden => "禁じ手のため置けません。",
+        Text::CannotPlace => "そのマスには置けません。",
+        Text::NoFinishedGame => "解析できる試合が見つかりません。(古い試合やBOTの再起動前の試合は解析できません)",
+
+        Text::TurnInfo => "現在 {emoji} : {name} の番です。",
+        Text::AiPlaced => "🤖 {name} は {square} に置きました。",
+        Text::AiNoSquare => "🤖 {name} は置ける場所がないため、パスします。",
+        Text::TimeOver => "⏰ {name} の持ち時間が切れました。",
+        Text::TimeControlMove => "⏱ 1手の持ち時間: {seconds} 秒",
+        Text::TimeControlGame => "⏱ 1人の持ち時間: {minutes} 分",
+        Text::Winner => "{emoji} {name} の勝利です。",
+        Text::Draw => "引き分けです。",
+        Text::Place => "置く",
+        Text::InputCoordinate => "座標を入力",
+        Text::ColumnPlaceholder => "列を指定してください",
+        Text::RowPlaceholder => "行を指定してください",
+        Text::CoordinateField => "座標 (例: D3)",
+
+        Text::InvalidRival => "その相手は選択できません。",
+        Text::BotRival => "Botは対戦対手に指定できません。",
+        Text::AlreadyPlaying => "すでに試合が行われています。",
+        Text::GameEnded => "試合を終了しました。",
+        Text::MultipleGames => "複数の試合が行われています。`game` で指定してください。",
+        Text::StartCommand => "{game}_開始",
+        Text::StartDescription => "{game}の試合を開始します。",
+        Text::EndCommand => "{game}_終了",
+        Text::EndDescription => "{game}の試合を終了します。",
+        Text::RivalOption => "相手",
+        Text::RivalOptionDescription => "対戦相手を指定します。",
+        Text::MoveCommand => "置く",
+        Text::MoveDescription => "座標を入力して石を置きます。",
+        Text::CoordinateOption => "座標",
+        Text::CoordinateOptionDescription => "置く座標を指定します。(例: D3)",
+        Text::ObserveOption => "観測",
+        Text::ObserveOptionDescription => "量子五目並べ・量子リバーシで置いたあとに観測します。",
+        Text::GameOption => "ゲーム",
+        Text::GameOptionDescription => "同じチャンネルで複数の試合が行われているときに指定します。",
+
+        Text::ReversiName => "リバーシ",
+        Text::InputOption => "入力方法",
+        Text::InputOptionDescription => "マスの選び方を指定します。",
+        Text::InputButtons => "置けるマスのボタン",
+        Text::InputMenus => "行と列のメニュー",
+        Text::ReversiPass => "置けるマスがなかったためもう一度同じ人のターンです。",
+        Text::ReversiResult => "試合が終了しました。\n黒: {black}\n白: {white}\n勝者: {winner}",
+        Text::ReversiWinnerBlack => "黒: {name}",
+        Text::ReversiWinnerWhite => "白: {name}",
+        Text::ReversiWinnerDraw => "引き分け",
+        Text::Opening => "定石: {name}",
+        Text::OpeningCommand => "定石",
+        Text::OpeningDescription => "リバーシの手順から定石を調べます。",
+        Text::MovesOption => "手順",
+        Text::MovesOptionDescription => "調べる手順を指定します。(例: F5 D6 C3)",
+        Text::InvalidMoves => "手順が正しくありません。(例: F5 D6 C3)",
+        Text::IllegalMoveInSequence => "{number}手目の {square} には置けません。",
+        Text::MovesNote => "手順: {moves}",
+        Text::NoOpening => "名前の付いた定石ではありません。",
+        Text::OpeningContinuations => "この先の定石:",
+        Text::Analyze => "解析",
+        Text::AnalysisTitle => "試合の解析",
+        Text::AnalysisDescription => "一手ごとに最善手と評価値を比べ、{mistake} 以上損した手を疑問手、{blunder} 以上損した手を悪手としています。中盤は {depth} 手先まで読んでマスの重みと置ける場所の数で評価し、空きマスが {empties} 以下になってからは最後まで読んだ石差で評価しています。\nグラフは打ったあとの評価値で、上が黒、下が白の有利です。",
+        Text::AnalysisPlayer => "正確さ: {accuracy}%\n悪手: {blunders}\n疑問手: {mistakes}",
+        Text::AnalysisMoves => "悪手・疑問手",
+        Text::AnalysisMove => "{number}手目 {emoji} {square} ({tag}、最善手 {best}、-{loss})",
+        Text::AnalysisMoreMoves => "ほか {count} 手",
+        Text::AnalysisNoMistakes => "悪手・疑問手はありませんでした。",
+        Text::Blunder => "悪手",
+        Text::Mistake => "疑問手",
+
+        Text::QuantumGomokuName => "量子五目並べ",
+        Text::QuantumGomokuIntro => "> ルール・元ネタ: \n> QuizKnock [【理解不能】何色になるか分からない量子で五目並べやってみた【でも楽しそう】](https://www.youtube.com/watch?v=mitAxA3f4U4)",
+        Text::Observe => "置いて観測する",
+        Text::InputObserve => "座標を入力して観測する",
+        Text::Heatmap => "ヒートマップ",
+        Text::HeatmapNote => "石の色は黒になる確率、空いているマスの数字は今の手番の石を置いてすぐ観測したときに5つ揃う確率 (%) です。",
+        Text::StoneBlack90 => "石: 90%黒 - 10%白",
+        Text::StoneBlack70 => "石: 70%黒 - 30%白",
+        Text::StoneWhite90 => "石: 90%白 - 10%黒",
+        Text::StoneWhite70 => "石: 70%白 - 30%黒",
+        Text::NotAligned => "観測した結果、揃いませんでした。",
+        Text::BlackAligned => "黒がそろいました！ {name} の勝利です。",
+        Text::WhiteAligned => "白がそろいました！ {name} の勝利です。",
+
+        Text::QuantumReversiName => "量子リバーシ",
+        Text::QuantumReversiIntro => "> 置く石と挟んだ石は量子五目並べと同じ確率の石になり、返ったかどうかは観測するまで決まりません。\n> 置けるマスは、それぞれの石をなりやすい方の色とみなして決めます。どちらも置けなくなったら観測し、石が多いほうの勝ちです。",
+        Text::QuantumReversiObserved => "観測した結果、黒 {black}・白 {white} になりました。",
+
+        Text::GomokuName => "五目並べ",
+        Text::RuleOption => "ルール",
+        Text::RuleOptionDescription => "ルールを指定します。",
+        Text::RuleStandard => "標準",
+        Text::RuleFreeStyle => "自由",
+        Text::RuleRenju => "連珠",
+        Text::RuleStandardDetail => "標準 (ちょうど5つで勝ち)",
+        Text::RuleFreeStyleDetail => "自由 (5つ以上で勝ち)",
+        Text::RuleRenjuDetail => "連珠 (黒は三三・四四・長連が禁じ手)",
+        Text::RuleIntro => "ルール: {rule}",
+        Text::RuleAnti => "アンチ",
+        Text::RuleAntiDetail => "アンチ (石が少ないほうが勝ち)",
+        Text::BoardFullDraw => "盤面が埋まったため引き分けです。",
+        Text::SizeOption => "盤の大きさ",
+        Text::SizeOptionDescription => "盤の大きさを指定します。(既定: 8x8)",
+        Text::LayoutOption => "初期配置",
+        Text::LayoutOptionDescription => "初めに置いてある石の並べ方を指定します。",
+        Text::LayoutCross => "標準 (斜めに並べる)",
+        Text::LayoutParallel => "平行 (横に並べる)",
+        Text::BoardIntro => "盤: {size}、初期配置: {layout}",
+
+        Text::SettingsCommand => "設定",
+        Text::SettingsDescription => "自分の設定を変えます。",
+        Text::ThemeOption => "テーマ",
+        Text::ThemeSubcommandDescription => "盤面の画像の色合いを変えます。",
+        Text::ThemeOptionDescription => "使うテーマを指定します。",
+        Text::ThemeWood => "木の盤",
+        Text::ThemeDark => "ダーク",
+        Text::ThemeHighContrast => "ハイコントラスト",
+        Text::ThemeColorblind => "色覚配慮 (石に確率を表示)",
+        Text::ThemeChanged => "盤面のテーマを「{theme}」にしました。あなたが置いたときの画像に使われます。",
+        Text::LabelsOption => "確率表示",
+        Text::LabelsSubcommandDescription => "量子五目並べの石に確率 (90 / 70) を描くかを変えます。",
+        Text::ShowOption => "表示",
+        Text::ShowOptionDescription => "確率を描くかどうか",
+        Text::LabelsShown => "量子五目並べの石に確率を描きます。あなたが置いたときの画像に使われます。",
+        Text::LabelsHidden => "量子五目並べの石に確率を描かないようにしました。(色覚配慮のテーマでは常に描きます)",
+    }
+}
+
+fn en(text: Text) -> &'static str {
+    match text {
+        Text::InternalError => "An error occurred.",
+        Text::NoGame => "No game is in progress.",
+        Text::NotYourTurn => "It is not your turn.",
+        Text::NotChoiced => "Select a row and a column.",
+        Text::InvalidCoordinate => "Invalid coordinate. (e.g. D3)",
+        Text::Forbidden => "That move is forbidden.",
+        Text::CannotPlace => "You cannot place a stone there.",
+        Text::NoFinishedGame => "The game to analyze was not found. (Old games and games from before the bot restarted cannot be analyzed.)",
+
+        Text::TurnInfo => "Now {emoji} : {name}'s turn.",
+        Text::AiPlaced => "🤖 {name} placed a stone on {square}.",
+        Text::AiNoSquare => "🤖 {name} has no square to place on and passes.",
+        Text::TimeOver => "⏰ {name} ran out of time.",
+        Text::TimeControlMove => "⏱ Time per move: {seconds} s",
+        Text::TimeControlGame => "⏱ Time per player: {minutes} min",
+        Text::Winner => "{emoji} {name} wins!",
+        Text::Draw => "It's a draw.",
+        Text::Place => "Place",
+        Text::InputCoordinate => "Enter coordinate",
+        Text::ColumnPlaceholder => "Select a column",
+        Text::RowPlaceholder => "Select a row",
+        Text::CoordinateField => "Coordinate (e.g. D3)",
+
+        Text::InvalidRival => "You cannot choose that opponent.",
+        Text::BotRival => "Bots cannot be chosen as the opponent.",
+        Text::AlreadyPlaying => "A game is already in progress.",
+        Text::GameEnded => "The game has been ended.",
+        Text::MultipleGames => "Several games are in progress. Choose one with `game`.",
+        Text::StartCommand => "{command}_start",
+        Text::StartDescription => "Start a game of {game}.",
+        Text::EndCommand => "{command}_end",
+        Text::EndDescription => "End the game of {game}.",
+        Text::RivalOption => "user",
+        Text::RivalOptionDescription => "The opponent.",
+        Text::MoveCommand => "move",
+        Text::MoveDescription => "Place a stone by entering a coordinate.",
+        Text::CoordinateOption => "coordinate",
+        Text::CoordinateOptionDescription => "The coordinate to place on. (e.g. D3)",
+        Text::ObserveOption => "observe",
+        Text::ObserveOptionDescription => "Observe after placing in quantum gomoku or quantum reversi.",
+        Text::GameOption => "game",
+        Text::GameOptionDescription => "The game to play when several are in progress in this channel.",
+
+        Text::ReversiName => "Reversi",
+        Text::InputOption => "input",
+        Text::InputOptionDescription => "How to choose squares.",
+        Text::InputButtons => "Buttons for legal squares",
+        Text::InputMenus => "Row and column menus",
+        Text::ReversiPass => "The next player has no legal square, so it is the same player's turn again.",
+        Text::ReversiResult => "The game is over.\nBlack: {black}\nWhite: {white}\nWinner: {winner}",
+        Text::ReversiWinnerBlack => "Black: {name}",
+        Text::ReversiWinnerWhite => "White: {name}",
+        Text::ReversiWinnerDraw => "Draw",
+        Text::Opening => "Opening: {name}",
+        Text::OpeningCommand => "opening",
+        Text::OpeningDescription => "Look up a Reversi opening from a move sequence.",
+        Text::MovesOption => "moves",
+        Text::MovesOptionDescription => "The moves to look up. (e.g. F5 D6 C3)",
+        Text::InvalidMoves => "The moves are not valid. (e.g. F5 D6 C3)",
+        Text::IllegalMoveInSequence => "Move {number} ({square}) cannot be played.",
+        Text::MovesNote => "Moves: {moves}",
+        Text::NoOpening => "This is not a named opening.",
+        Text::OpeningContinuations => "Openings that continue from here:",
+        Text::Analyze => "Analyze",
+        Text::AnalysisTitle => "Game analysis",
+        Text::AnalysisDescription => "Each move is compared with the best move by evaluation. Moves that lose {mistake} or more are mistakes, and {blunder} or more are blunders. The midgame is searched {depth} moves ahead and scored by square weights and mobility; from {empties} empty squares on, it is searched to the end and scored by the final disc difference.\nThe graph shows the evaluation after each move: up favors black, down favors white.",
+        Text::AnalysisPlayer => "Accuracy: {accuracy}%\nBlunders: {blunders}\nMistakes: {mistakes}",
+        Text::AnalysisMoves => "Blunders and mistakes",
+        Text::AnalysisMove => "Move {number} {emoji} {square} ({tag}, best {best}, -{loss})",
+        Text::AnalysisMoreMoves => "{count} more",
+        Text::AnalysisNoMistakes => "There were no blunders or mistakes.",
+        Text::Blunder => "blunder",
+        Text::Mistake => "mistake",
+
+        Text::QuantumGomokuName => "Quantum Gomoku",
+        Text::QuantumGomokuIntro => "> Rules and original idea: \n> QuizKnock [【理解不能】何色になるか分からない量子で五目並べやってみた【でも楽しそう】](https://www.youtube.com/watch?v=mitAxA3f4U4)",
+        Text::Observe => "Place and observe",
+        Text::InputObserve => "Enter coordinate and observe",
+        Text::Heatmap => "Heatmap",
+        Text::HeatmapNote => "Stone colors show the chance of being black. Numbers on empty points show the chance (%) of making five if the current stone is placed there and observed right away.",
+        Text::StoneBlack90 => "Stone: 90% black - 10% white",
+        Text::StoneBlack70 => "Stone: 70% black - 30% white",
+        Text::StoneWhite90 => "Stone: 90% white - 10% black",
+        Text::StoneWhite70 => "Stone: 70% white - 30% black",
+        Text::NotAligned => "Observed, but nobody made five in a row.",
+        Text::BlackAligned => "Black made five in a row! {name} wins!",
+        Text::WhiteAligned => "White made five in a row! {name} wins!",
+
+        Text::QuantumReversiName => "Quantum Reversi",
+        Text::QuantumReversiIntro => "> Placed and flipped discs become quantum stones with the same chances as in Quantum Gomoku, so a flip is only settled when the board is observed.\n> Legal squares treat each disc as its more likely color. When neither player can move, the board is observed and more discs wins.",
+        Text::QuantumReversiObserved => "Observed: black {black}, white {white}.",
+
+        Text::GomokuName => "Gomoku",
+        Text::RuleOption => "rule",
+        Text::RuleOptionDescription => "The rule.",
+        Text::RuleStandard => "Standard",
+        Text::RuleFreeStyle => "Freestyle",
+        Text::RuleRenju => "Renju",
+        Text::RuleStandardDetail => "Standard (exactly five wins)",
+        Text::RuleFreeStyleDetail => "Freestyle (five or more wins)",
+        Text::RuleRenjuDetail => "Renju (double-three, double-four and overline are forbidden for black)",
+        Text::RuleIntro => "Rule: {rule}",
+        Text::RuleAnti => "Anti",
+        Text::RuleAntiDetail => "Anti (fewer discs wins)",
+        Text::BoardFullDraw => "The board is full, so it's a draw.",
+        Text::SizeOption => "size",
+        Text::SizeOptionDescription => "The board size. (default: 8x8)",
+        Text::LayoutOption => "layout",
+        Text::LayoutOptionDescription => "How the first four stones are placed.",
+        Text::LayoutCross => "Standard (diagonal)",
+        Text::LayoutParallel => "Parallel (side by side)",
+        Text::BoardIntro => "Board: {size}, layout: {layout}",
+
+        Text::SettingsCommand => "settings",
+        Text::SettingsDescription => "Change your settings.",
+        Text::ThemeOption => "theme",
+        Text::ThemeSubcommandDescription => "Change the colors of board images.",
+        Text::ThemeOptionDescription => "The theme to use.",
+        Text::ThemeWood => "Classic wood",
+        Text::ThemeDark => "Dark",
+        Text::ThemeHighContrast => "High contrast",
+        Text::ThemeColorblind => "Colorblind-friendly (probabilities on stones)",
+        Text::ThemeChanged => "Board theme set to \"{theme}\". It is used for images after your moves.",
+        Text::LabelsOption => "labels",
+        Text::LabelsSubcommandDescription => "Choose whether quantum gomoku stones show their probability (90 / 70).",
+        Text::ShowOption => "show",
+        Text::ShowOptionDescription => "Whether to show probabilities.",
+        Text::LabelsShown => "Quantum gomoku stones will show their probability in images after your moves.",
+        Text::LabelsHidden => "Quantum gomoku stones will no longer show their probability. (The colorblind-friendly theme always shows them.)",
+    }
+}
+
+/// 言語ごとの名前・説明を付けたコマンド (`name` は Discord から送られてくる基本の名前)
+pub fn command(
+    name: &str,
+    localized_name: Option<&dyn Fn(Locale) -> String>,
+    description: &dyn Fn(Locale) -> String,
+) -> CreateCommand {
+    let mut command = CreateCommand::new(name).description(description(Locale::default()));
+
+    for locale in Locale::ALL {
+        for code in locale.discord_codes() {
+            if let Some(localized_name) = localized_name {
+                command = command.name_localized(*code, localized_name(locale));
+            }
+            command = command.description_localized(*code, description(locale));
+        }
+    }
+
+    return command;
+}
+
+/// 言語ごとの名前・説明を付けたオプション (`name` の言語ごとの名前は `localized_name`)
+pub fn option(
+    kind: CommandOptionType,
+    name: &str,
+    localized_name: Text,
+    description: Text,
+) -> CreateCommandOption {
+    let mut option = CreateCommandOption::new(kind, name, description.get(Locale::default()));
+
+    for locale in Locale::ALL {
+        for code in locale.discord_codes() {
+            option = option
+                .name_localized(*code, localized_name.get(locale))
+                .description_localized(*code, description.get(locale));
+        }
+    }
+
+    return option;
+}
+
+/// 言語ごとの表示名を付けた選択肢を追加する
+pub fn string_choice(option: CreateCommandOption, name: Text, value: &str) -> CreateCommandOption {
+    let locales = Locale::ALL.into_iter().flat_map(|locale| {
+        locale
+            .discord_codes()
+            .iter()
+            .map(move |code| (*code, name.get(locale)))
+    });
+
+    option.add_string_choice_localized(name.get(Locale::default()), value, locales)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: &[Text] = &[
+        Text::InternalError,
+        Text::NoGame,
+        Text::NotYourTurn,
+        Text::NotChoiced,
+        Text::InvalidCoordinate,
+        Text::Forbidden,
+        Text::CannotPlace,
+        Text::NoFinishedGame,
+        Text::TurnInfo,
+        Text::AiPlaced,
+        Text::AiNoSquare,
+        Text::TimeOver,
+        Text::TimeControlMove,
+        Text::TimeControlGame,
+        Text::Winner,
+        Text::Draw,
+        Text::Place,
+        Text::InputCoordinate,
+        Text::ColumnPlaceholder,
+        Text::RowPlaceholder,
+        Text::CoordinateField,
+        Text::InvalidRival,
+        Text::BotRival,
+        Text::AlreadyPlaying,
+        Text::GameEnded,
+        Text::MultipleGames,
+        // StartCommand・EndCommand は言語ごとに使う値 ({game}・{command}) が違うので除く
+        Text::StartDescription,
+        Text::EndDescription,
+        Text::RivalOption,
+        Text::RivalOptionDescription,
+        Text::MoveCommand,
+        Text::MoveDescription,
+        Text::CoordinateOption,
+        Text::CoordinateOptionDescription,
+        Text::ObserveOption,
+        Text::ObserveOptionDescription,
+        Text::GameOption,
+        Text::GameOptionDescription,
+        Text::ReversiName,
+        Text::InputOption,
+        Text::InputOptionDescription,
+        Text::InputButtons,
+        Text::InputMenus,
+        Text::ReversiPass,
+        Text::ReversiResult,
+        Text::ReversiWinnerBlack,
+        Text::ReversiWinnerWhite,
+        Text::ReversiWinnerDraw,
+        Text::Opening,
+        Text::OpeningCommand,
+        Text::OpeningDescription,
+        Text::MovesOption,
+        Text::MovesOptionDescription,
+        Text::InvalidMoves,
+        Text::IllegalMoveInSequence,
+        Text::MovesNote,
+        Text::NoOpening,
+        Text::OpeningContinuations,
+        Text::Analyze,
+        Text::AnalysisTitle,
+        Text::AnalysisDescription,
+        Text::AnalysisPlayer,
+        Text::AnalysisMoves,
+        Text::AnalysisMove,
+        Text::AnalysisMoreMoves,
+        Text::AnalysisNoMistakes,
+        Text::Blunder,
+        Text::Mistake,
+        Text::QuantumGomokuName,
+        Text::QuantumGomokuIntro,
+        Text::Observe,
+        Text::InputObserve,
+        Text::Heatmap,
+        Text::HeatmapNote,
+        Text::StoneBlack90,
+        Text::StoneBlack70,
+        Text::StoneWhite90,
+        Text::StoneWhite70,
+        Text::NotAligned,
+        Text::BlackAligned,
+        Text::WhiteAligned,
+        Text::QuantumReversiName,
+        Text::QuantumReversiIntro,
+        Text::QuantumReversiObserved,
+        Text::GomokuName,
+        Text::RuleOption,
+        Text::RuleOptionDescription,
+        Text::RuleStandard,
+        Text::RuleFreeStyle,
+        Text::RuleRenju,
+        Text::RuleStandardDetail,
+        Text::RuleFreeStyleDetail,
+        Text::RuleRenjuDetail,
+        Text::RuleIntro,
+        Text::RuleAnti,
+        Text::RuleAntiDetail,
+        Text::BoardFullDraw,
+        Text::SizeOption,
+        Text::SizeOptionDescription,
+        Text::LayoutOption,
+        Text::LayoutOptionDescription,
+        Text::LayoutCross,
+        Text::LayoutParallel,
+        Text::BoardIntro,
+        Text::SettingsCommand,
+        Text::SettingsDescription,
+        Text::ThemeOption,
+        Text::ThemeSubcommandDescription,
+        Text::ThemeOptionDescription,
+        Text::ThemeWood,
+        Text::ThemeDark,
+        Text::ThemeHighContrast,
+        Text::ThemeColorblind,
+        Text::ThemeChanged,
+        Text::LabelsOption,
+        Text::LabelsSubcommandDescription,
+        Text::ShowOption,
+        Text::ShowOptionDescription,
+        Text::LabelsShown,
+        Text::LabelsHidden,
+    ];
+
+    fn placeholders(text: &str) -> Vec<&str> {
+        let mut placeholders: Vec<&str> = text
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(key, _)| key))
+            .collect();
+        placeholders.sort();
+
+        return placeholders;
+    }
+
+    #[test]
+    fn same_placeholders() {
+        for text in ALL {
+            assert_eq!(
+                placeholders(text.get(Locale::Ja)),
+                placeholders(text.get(Locale::En)),
+                "{:?}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn discord_locale() {
+        assert_eq!(Locale::Ja, Locale::pick("ja", Locale::En));
+        assert_eq!(Locale::En, Locale::pick("en-US", Locale::Ja));
+        assert_eq!(Locale::En, Locale::pick("en-GB", Locale::Ja));
+        assert_eq!(Locale::En, Locale::pick("fr", Locale::En));
+        assert_eq!(Locale::Ja, Locale::pick("fr", Locale::Ja));
+    }
+
+    #[test]
+    fn format() {
+        assert_eq!(
+            "Now 🔵 : alice's turn.",
+            Text::TurnInfo.format(Locale::En, &[("emoji", "🔵"), ("name", "alice")])
+        );
+    }
+}
@@ -0,0 +1,11 @@
+//! ゲームのエンジン (Discord に依存しない部分)
+//!
+//! BOT (`main.rs`) と端末で遊ぶための `cli` (`src/bin/cli.rs`) で共有する。
+
+pub mod color;
+pub mod coordinate;
+pub mod gomoku;
+pub mod quantum_gomoku;
+pub mod quantum_reversi;
+pub mod render;
+pub mod reversi;
@@ -11,37 +11,91 @@ use std::sync::Arc;
 mod commands;
 mod components;
 mod config;
+mod discord;
+mod error;
+mod game;
 mod global_data;
-mod quantum_gomoku;
-mod reversi;
+mod i18n;
+mod settings;
 
-use components::{
-    choice_pos, push_stone, qgomoku_choice_pos, qgomoku_push_stone, qgomoku_push_stone_observe,
+// エンジンはライブラリ側 (src/lib.rs) にあり、`cli` と共有している
+use discord_reversibot_rust::{
+    color, coordinate, gomoku, quantum_gomoku, quantum_reversi, render, reversi,
 };
-use global_data::{GlobalQuantumGomokuStats, GlobalReversiStats};
 
-struct Handler;
+use commands::{game_end, game_start, registration};
+use config::Config;
+use discord::Input;
+use global_data::{GlobalFinishedSessions, GlobalSessions, GlobalSettings, GlobalTimeControl};
+use gomoku::gomoku::Gomoku;
+use i18n::Locale;
+use quantum_gomoku::gomoku::QuantumGomoku;
+use quantum_reversi::reversi::QuantumReversi;
+use render::{encode, font};
+use reversi::reversi::Reversi;
+use settings::Settings;
+
+struct Handler {
+    config: Arc<Config>,
+}
 
 #[async_trait]
 impl EventHandler for Handler {
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
         if let Interaction::Command(command) = &interaction {
+            let locale = Locale::pick(&command.locale, self.config.locale);
+
             let data = match command.data.name.as_str() {
-                "ping" => commands::ping::run(&command.data.options(), &command, &ctx).await,
+                "ping" => commands::ping::run(&command.data.options(), command, &ctx).await,
                 "reversi_start" => {
-                    commands::reversi_start::run(&command.data.options(), &command, &ctx).await
+                    game_start::run::<Reversi>(&command.data.options(), command, &ctx, locale).await
                 }
 
                 "reversi_end" => {
-                    commands::reversi_end::run(&command.data.options(), &command, &ctx).await
+                    game_end::run::<Reversi>(&command.data.options(), command, &ctx, locale).await
                 }
 
                 "q_gomoku_start" => {
-                    commands::q_gomoku_start::run(&command.data.options(), &command, &ctx).await
+                    game_start::run::<QuantumGomoku>(&command.data.options(), command, &ctx, locale)
+                        .await
                 }
 
                 "q_gomoku_end" => {
-                    commands::q_gomoku_end::run(&command.data.options(), &command, &ctx).await
+                    game_end::run::<QuantumGomoku>(&command.data.options(), command, &ctx, locale)
+                        .await
+                }
+
+                "q_reversi_start" => {
+                    game_start::run::<QuantumReversi>(
+                        &command.data.options(),
+                        command,
+                        &ctx,
+                        locale,
+                    )
+                    .await
+                }
+
+                "q_reversi_end" => {
+                    game_end::run::<QuantumReversi>(&command.data.options(), command, &ctx, locale)
+                        .await
+                }
+
+                "gomoku_start" => {
+                    game_start::run::<Gomoku>(&command.data.options(), command, &ctx, locale).await
+                }
+
+                "gomoku_end" => {
+                    game_end::run::<Gomoku>(&command.data.options(), command, &ctx, locale).await
+                }
+
+                "move" => {
+                    commands::move_stone::run(&command.data.options(), command, &ctx, locale).await
+                }
+
+                "opening" => commands::opening::run(&command.data.options(), locale),
+
+                "settings" => {
+                    commands::settings::run(&command.data.options(), command, &ctx, locale).await
                 }
 
                 _ => CreateInteractionResponseMessage::new().content("not implemented"),
@@ -54,21 +108,25 @@ impl EventHandler for Handler {
         }
 
         if let Interaction::Component(interaction) = &interaction {
-            let custom_id = &interaction.data.custom_id;
-
-            match custom_id.as_str() {
-                "choice_alphabet" => choice_pos::alphabet(&ctx, interaction).await,
-                "choice_number" => choice_pos::number(&ctx, interaction).await,
-                "push_stone" => push_stone::run(&ctx, interaction).await,
-
-                "qgomoku_choice_alphabet" => qgomoku_choice_pos::alphabet(&ctx, interaction).await,
-                "qgomoku_choice_number" => qgomoku_choice_pos::number(&ctx, interaction).await,
-                "qgomoku_push_stone" => qgomoku_push_stone::run(&ctx, interaction).await,
-                "qgomoku_push_stone_observe" => {
-                    qgomoku_push_stone_observe::run(&ctx, interaction).await
+            if let Some(builder) = components::component(
+                &ctx,
+                &Input::from_component(interaction, self.config.locale),
+            )
+            .await
+            {
+                if let Err(why) = interaction.create_response(&ctx.http, builder).await {
+                    println!("Cannot respond to slash command: {why}");
                 }
+            }
+        }
 
-                _ => (),
+        if let Interaction::Modal(interaction) = &interaction {
+            if let Some(builder) =
+                components::modal(&ctx, &Input::from_modal(interaction, self.config.locale)).await
+            {
+                if let Err(why) = interaction.create_response(&ctx.http, builder).await {
+                    println!("Cannot respond to slash command: {why}");
+                }
             }
         }
     }
@@ -76,18 +134,30 @@ impl EventHandler for Handler {
     async fn ready(&self, ctx: Context, ready: Ready) {
         println!("login: {}", ready.user.name);
 
-        for i in [
-            commands::ping::register(),
-            commands::reversi_start::register(),
-            commands::reversi_end::register(),
-            commands::q_gomoku_start::register(),
-            commands::q_gomoku_end::register(),
-        ] {
-            match Command::create_global_command(&ctx.http, i).await {
-                Ok(result) => println!("SetGuildCommand: {}", result.name),
-                Err(result) => println!("{}", result),
-            }
+        let games = self.config.games;
+
+        let mut definitions = vec![commands::ping::register()];
+        if games.reversi {
+            definitions.push(game_start::register::<Reversi>());
+            definitions.push(game_end::register::<Reversi>());
+            definitions.push(commands::opening::register());
         }
+        if games.quantum_gomoku {
+            definitions.push(game_start::register::<QuantumGomoku>());
+            definitions.push(game_end::register::<QuantumGomoku>());
+        }
+        if games.quantum_reversi {
+            definitions.push(game_start::register::<QuantumReversi>());
+            definitions.push(game_end::register::<QuantumReversi>());
+        }
+        if games.gomoku {
+            definitions.push(game_start::register::<Gomoku>());
+            definitions.push(game_end::register::<Gomoku>());
+        }
+        definitions.push(commands::move_stone::register());
+        definitions.push(commands::settings::register());
+
+        registration::register_commands(&ctx, &self.config.guild_ids, definitions).await;
     }
 
     async fn message(&self, ctx: Context, msg: Message) {
@@ -99,17 +169,40 @@ impl EventHandler for Handler {
     }
 }
 
-#[tokio::main]
-async fn main() {
-    let token = config::get_token("config.json").expect("'config.json' file is not found.");
+/// 設定ファイルを読んでBOTを起動する
+async fn start_bot(config_file: &str) {
+    let config = match Config::load(config_file) {
+        Ok(config) => Arc::new(config),
+        Err(why) => {
+            println!("Config error: {why}");
+            std::process::exit(1);
+        }
+    };
+    println!("config: {}", config.summary());
+
+    if let Err(why) = font::load_font(&config.font_path) {
+        println!(
+            "Font: cannot load '{}' ({why}). Using the built-in bitmap labels.",
+            config.font_path.display()
+        );
+    }
+    if let Err(output) = encode::set_output(config.image) {
+        println!(
+            "Config error: image output {:?} cannot be set twice",
+            output
+        );
+        std::process::exit(1);
+    }
 
     let intents = GatewayIntents::GUILD_MESSAGES
         | GatewayIntents::DIRECT_MESSAGES
         | GatewayIntents::MESSAGE_CONTENT
         | GatewayIntents::GUILD_MESSAGE_REACTIONS;
 
-    let mut client = Client::builder(&token, intents)
-        .event_handler(Handler)
+    let mut client = Client::builder(&config.token, intents)
+        .event_handler(Handler {
+            config: config.clone(),
+        })
         .activity(ActivityData::custom(
             "/reversi_start でリバーシの試合を開始",
         ))
@@ -118,8 +211,14 @@ async fn main() {
 
     {
         let mut data = client.data.write().await;
-        data.insert::<GlobalReversiStats>(Arc::new(Mutex::new(HashMap::new())));
-        data.insert::<GlobalQuantumGomokuStats>(Arc::new(Mutex::new(HashMap::new())));
+        data.insert::<GlobalSessions<Reversi>>(Arc::new(Mutex::new(HashMap::new())));
+        data.insert::<GlobalSessions<QuantumGomoku>>(Arc::new(Mutex::new(HashMap::new())));
+        data.insert::<GlobalSessions<QuantumReversi>>(Arc::new(Mutex::new(HashMap::new())));
+        data.insert::<GlobalSessions<Gomoku>>(Arc::new(Mutex::new(HashMap::new())));
+        // 解析できるゲームだけ、決着した試合を残しておく
+        data.insert::<GlobalFinishedSessions<Reversi>>(Default::default());
+        data.insert::<GlobalSettings>(Arc::new(RwLock::new(Settings::load(&config.storage_path))));
+        data.insert::<GlobalTimeControl>(config.time_control);
     }
 
     if let Err(why) = client.start().await {
@@ -127,32 +226,13 @@ async fn main() {
     }
 }
 
+#[tokio::main]
+async fn main() {
+    start_bot("config.json").await;
+}
+
 #[tokio::test]
 #[ignore]
 async fn bot_test() {
-    let token =
-        config::get_token("config_test.json").expect("'config_test.json' file is not found.");
-
-    let intents = GatewayIntents::GUILD_MESSAGES
-        | GatewayIntents::DIRECT_MESSAGES
-        | GatewayIntents::MESSAGE_CONTENT
-        | GatewayIntents::GUILD_MESSAGE_REACTIONS;
-
-    let mut client = Client::builder(&token, intents)
-        .event_handler(Handler)
-        .activity(ActivityData::custom(
-            "/reversi_start でリバーシの試合を開始",
-        ))
-        .await
-        .expect("Err creating client");
-
-    {
-        let mut data = client.data.write().await;
-        data.insert::<GlobalReversiStats>(Arc::new(Mutex::new(HashMap::new())));
-        data.insert::<GlobalQuantumGomokuStats>(Arc::new(Mutex::new(HashMap::new())));
-    }
-
-    if let Err(why) = client.start().await {
-        println!("Client error: {why:?}");
-    }
+    start_bot("config_test.json").await;
 }
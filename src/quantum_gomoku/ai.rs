@@ -0,0 +1,188 @@
+/*
+量子五目並べのBOT (AI) です。
+
+各石の黒になる確率から5マスの並びごとの「揃う見込み」を計算して置く場所を選び、
+観測したときの勝率を何度か観測してみて見積もり、観測するかどうかを決めます。
+*/
+
+use super::gomoku::{ObservedStone, QuantumGomoku, Stone, LINE_MOVES};
+
+// 盤面の配列は 19x19 だが、メニューで選べるのは 15x15
+const PLAY_SIZE: i32 = 15;
+
+// まだ石のないマスがあとで自分の色になる見込み
+const EMPTY_WEIGHT: f64 = 0.35;
+
+// 相手の並びを崩すことをどれだけ重視するか
+const DEFENCE_WEIGHT: f64 = 0.9;
+
+// 観測するかどうかを決めるときに観測してみる回数
+const OBSERVE_SAMPLES: usize = 300;
+
+// この勝率以上で、負ける確率より十分高ければ観測する
+const OBSERVE_THRESHOLD: f64 = 0.3;
+
+/// 観測したときに color になる確率 (空いているマスはあとで自分の色になる見込み)
+fn color_possibility(stone: Stone, color: ObservedStone) -> f64 {
+    match stone {
+        Stone::None => EMPTY_WEIGHT,
+        stone => stone.color_possibility(color),
+    }
+}
+
+/// (row, column) を含む5マスの並びの始点 (盤面からはみ出すものは除く)
+fn lines_through(row: i32, column: i32) -> Vec<((i32, i32), (i32, i32))> {
+    let mut lines = vec![];
+
+    for (move_row, move_column) in LINE_MOVES {
+        for offset in 0..5 {
+            let start = (row - move_row * offset, column - move_column * offset);
+            let end = (start.0 + move_row * 4, start.1 + move_column * 4);
+
+            let in_board =
+                |(r, c): (i32, i32)| (0..PLAY_SIZE).contains(&r) && (0..PLAY_SIZE).contains(&c);
+            if in_board(start) && in_board(end) {
+                lines.push((start, (move_row, move_column)));
+            }
+        }
+    }
+
+    return lines;
+}
+
+/// その並びがすべて color で揃う見込み
+fn line_potential(
+    board: &[[Stone; 19]; 19],
+    start: (i32, i32),
+    move_: (i32, i32),
+    color: ObservedStone,
+) -> f64 {
+    (0..5)
+        .map(|i| {
+            let (row, column) = (start.0 + move_.0 * i, start.1 + move_.1 * i);
+            color_possibility(board[row as usize][column as usize], color)
+        })
+        .product()
+}
+
+fn evaluate_place(gomoku: &QuantumGomoku, row: i32, column: i32) -> f64 {
+    let self_color = gomoku.current_turn.majority();
+    let rival_color = match self_color {
+        ObservedStone::Black => ObservedStone::White,
+        _ => ObservedStone::Black,
+    };
+
+    let mut placed = gomoku.board;
+    placed[row as usize][column as usize] = gomoku.current_turn.stone();
+
+    let mut score = 0.0;
+    for (start, move_) in lines_through(row, column) {
+        let self_gain = line_potential(&placed, start, move_, self_color)
+            - line_potential(&gomoku.board, start, move_, self_color);
+        let rival_loss = line_potential(&gomoku.board, start, move_, rival_color)
+            - line_potential(&placed, start, move_, rival_color);
+
+        score += self_gain + DEFENCE_WEIGHT * rival_loss;
+    }
+
+    // 同じ評価なら中央に近いほうを選ぶ
+    let center = (PLAY_SIZE - 1) as f64 / 2.0;
+    let distance = (row as f64 - center).abs() + (column as f64 - center).abs();
+
+    return score - distance * 1e-6;
+}
+
+/// 現在の手番の石を置く場所を選ぶ
+pub fn choose_place(gomoku: &QuantumGomoku) -> Option<(usize, usize)> {
+    let mut best: Option<((usize, usize), f64)> = None;
+
+    for row in 0..PLAY_SIZE {
+        for column in 0..PLAY_SIZE {
+            if gomoku.board[row as usize][column as usize] != Stone::None {
+                continue;
+            }
+
+            let score = evaluate_place(gomoku, row, column);
+            match best {
+                Some((_, best_score)) if best_score >= score => (),
+                _ => best = Some(((row as usize, column as usize), score)),
+            }
+        }
+    }
+
+    return best.map(|(pos, _)| pos);
+}
+
+/// 今観測したときの (color が勝つ確率, 相手が勝つ確率) を見積もる
+pub fn estimate_win_possibility(gomoku: &QuantumGomoku, color: ObservedStone) -> (f64, f64) {
+    let mut win = 0;
+    let mut lose = 0;
+
+    for _ in 0..OBSERVE_SAMPLES {
+        match gomoku.judge_winner().0 {
+            ObservedStone::None => (),
+            winner if winner == color => win += 1,
+            _ => lose += 1,
+        }
+    }
+
+    return (
+        win as f64 / OBSERVE_SAMPLES as f64,
+        lose as f64 / OBSERVE_SAMPLES as f64,
+    );
+}
+
+/// 石を置いたあと (手番を切り替えたあと) の盤面で観測するかどうか
+pub fn should_observe(gomoku: &QuantumGomoku, color: ObservedStone) -> bool {
+    let (win, lose) = estimate_win_possibility(gomoku, color);
+
+    win >= OBSERVE_THRESHOLD && win > lose * 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn choose_place_empty_board() {
+        let gomoku = QuantumGomoku::new();
+
+        assert_eq!(Some((7, 7)), choose_place(&gomoku));
+    }
+
+    #[test]
+    fn choose_place_completes_line() {
+        let mut gomoku = QuantumGomoku::new();
+        for column in 3..7 {
+            gomoku.board[5][column] = Stone::Black90;
+        }
+        gomoku.board[5][2] = Stone::White90;
+
+        assert_eq!(Some((5, 7)), choose_place(&gomoku));
+    }
+
+    #[test]
+    fn choose_place_blocks_rival() {
+        let mut gomoku = QuantumGomoku::new();
+        for row in 4..8 {
+            gomoku.board[row][10] = Stone::White90;
+        }
+        gomoku.board[3][10] = Stone::Black90;
+
+        assert_eq!(Some((8, 10)), choose_place(&gomoku));
+    }
+
+    #[test]
+    fn should_observe() {
+        let gomoku = QuantumGomoku::new();
+        assert!(!super::should_observe(&gomoku, ObservedStone::Black));
+
+        let mut gomoku = QuantumGomoku::new();
+        for column in 3..8 {
+            gomoku.board[5][column] = Stone::Black90;
+        }
+
+        assert!(super::should_observe(&gomoku, ObservedStone::Black));
+        assert!(!super::should_observe(&gomoku, ObservedStone::White));
+    }
+}
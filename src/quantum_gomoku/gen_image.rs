@@ -1,90 +1,100 @@
-use std::io::Cursor;
+use std::sync::OnceLock;
 
-use ab_glyph::{FontRef, PxScale};
-use image::{ImageBuffer, ImageFormat, Rgb};
+use image::{ImageBuffer, Rgb};
 use imageproc::{
-    drawing::{draw_filled_circle_mut, draw_filled_rect_mut, draw_text_mut},
+    drawing::{draw_filled_circle_mut, draw_filled_rect_mut, draw_hollow_circle_mut},
     rect::Rect,
 };
-use tokio::sync::OnceCell;
 
-use super::gomoku::{ObservedStone, Stone};
+use super::gomoku::{ConnectedMass, ObservedStone, QuantumGomoku, Stone};
+use super::heatmap::five_chances;
+use crate::render::encode::{encode, output};
+use crate::render::font::{draw_label, draw_small_label, draw_small_text, SMALL_HEIGHT};
+use crate::render::legend::draw_quantum_legend;
+use crate::render::render_cache::render;
+use crate::render::theme::{text_color_on, BoardStyle, Palette, Theme};
 
 const IMAGE_SIZE: u32 = 850;
-const BOARD_COLOR: Rgb<u8> = Rgb([216, 179, 77]);
+/// 盤面の下の凡例の高さ
+const LEGEND_HEIGHT: u32 = 80;
+/// 観測の前後を並べた画像の、盤面の上の見出しの高さ
+const TITLE_HEIGHT: u32 = 50;
 
 const RECT_WIDTH: u32 = 3;
-const RECT_COLOR: Rgb<u8> = Rgb([0, 0, 0]);
 
-static BOARD_TEMPLATE: OnceCell<ImageBuffer<Rgb<u8>, Vec<u8>>> = OnceCell::const_new();
+const STONE_RADIUS: i32 = 24;
+
+/// 観測して、なりやすい方と違う色になった石の印
+const FLIPPED_COLOR: Rgb<u8> = Rgb([0, 158, 115]);
+
+/// ヒートマップの、黒になる確率の色 (白になりやすい・半々・黒になりやすい)
+const HEAT_WHITE: Rgb<u8> = Rgb([215, 48, 39]);
+const HEAT_EVEN: Rgb<u8> = Rgb([255, 255, 191]);
+const HEAT_BLACK: Rgb<u8> = Rgb([49, 54, 149]);
+/// ヒートマップの、空いているマスの5つが揃う確率の色
+const HEAT_FIVE: Rgb<u8> = Rgb([118, 42, 131]);
+/// これより低い確率は描かない (1% 未満)
+const HEAT_MIN_CHANCE: f64 = 0.005;
+
+/// テーマごとの石のない盤面 (添字は `Theme::ALL` の順)
+static BOARD_TEMPLATES: [OnceLock<ImageBuffer<Rgb<u8>, Vec<u8>>>; Theme::ALL.len()] =
+    [const { OnceLock::new() }; Theme::ALL.len()];
 
 pub struct GenImage {
     img: ImageBuffer<Rgb<u8>, Vec<u8>>,
+    palette: Palette,
+    stone_labels: bool,
 }
 
 impl GenImage {
-    async fn new() -> Self {
-        let self_ = Self {
-            img: Self::draw_board_cache().await,
-        };
-
-        self_
+    fn new(style: BoardStyle) -> Self {
+        Self {
+            img: Self::draw_board_cache(style.theme),
+            palette: style.theme.palette(),
+            stone_labels: style.shows_stone_labels(),
+        }
     }
 
     fn get_pos(row: i32, column: i32) -> (i32, i32) {
         (75 + (row * 50), 75 + (column * 50))
     }
 
-    fn draw_board() -> ImageBuffer<Rgb<u8>, Vec<u8>> {
+    fn draw_board(palette: Palette) -> ImageBuffer<Rgb<u8>, Vec<u8>> {
         let image_width = IMAGE_SIZE;
         let image_height = IMAGE_SIZE;
-        let mut img = ImageBuffer::from_pixel(image_width, image_height, BOARD_COLOR);
+        let mut img = ImageBuffer::from_pixel(image_width, image_height, palette.board);
 
         for i in 0..15 {
             let pos = Self::get_pos(i, 0);
             let rect = Rect::at(pos.0, pos.1).of_size(RECT_WIDTH, 700 + RECT_WIDTH);
-            draw_filled_rect_mut(&mut img, rect, RECT_COLOR);
+            draw_filled_rect_mut(&mut img, rect, palette.line);
 
             let pos = Self::get_pos(0, i);
             let rect = Rect::at(pos.0, pos.1).of_size(700 + RECT_WIDTH, RECT_WIDTH);
-            draw_filled_rect_mut(&mut img, rect, RECT_COLOR);
+            draw_filled_rect_mut(&mut img, rect, palette.line);
         }
 
         let mut cursor = (226, 226);
         for _ in 0..2 {
             for _ in 0..2 {
-                draw_filled_circle_mut(&mut img, cursor, 6, RECT_COLOR);
+                draw_filled_circle_mut(&mut img, cursor, 6, palette.line);
                 cursor.0 += 400;
             }
             cursor.0 -= 800;
             cursor.1 += 400;
         }
 
-        let scale = PxScale { x: 37.5, y: 37.5 };
-
-        // fontフォルダに任意のフォント (font.ttf) を用意する
-        let font = FontRef::try_from_slice(include_bytes!("../../font/font.ttf")).unwrap();
-
+        // フォントは起動時に font::load_font で読み込む (なければビットマップ文字)
         for i in 1..=15 {
-            draw_text_mut(
-                &mut img,
-                RECT_COLOR,
-                10,
-                i * 50 + 5,
-                scale,
-                &font,
-                &i.to_string(),
-            );
+            draw_label(&mut img, palette.line, 10, i * 50 + 5, &i.to_string());
         }
 
-        for (i, alphabet) in (1..=15).zip("ABCDEFGHIJKLMNO".chars().into_iter()) {
-            draw_text_mut(
+        for (i, alphabet) in (1..=15).zip("ABCDEFGHIJKLMNO".chars()) {
+            draw_label(
                 &mut img,
-                RECT_COLOR,
+                palette.line,
                 i * 50 + 13,
                 10,
-                scale,
-                &font,
                 &alphabet.to_string(),
             );
         }
@@ -92,40 +102,215 @@ impl GenImage {
         return img;
     }
 
-    async fn draw_board_cache() -> ImageBuffer<Rgb<u8>, Vec<u8>> {
-        BOARD_TEMPLATE
-            .get_or_init(|| async { Self::draw_board() })
-            .await
+    fn draw_board_cache(theme: Theme) -> ImageBuffer<Rgb<u8>, Vec<u8>> {
+        let index = Theme::ALL.iter().position(|t| *t == theme).unwrap();
+
+        BOARD_TEMPLATES[index]
+            .get_or_init(|| Self::draw_board(theme.palette()))
             .clone()
     }
 
     fn push_stone(&mut self, row: i32, column: i32, stone: Stone) {
-        let pos = Self::get_pos(row, column);
-        let color = match stone {
-            Stone::Black90 => Rgb([20, 20, 20]),
-            Stone::Black70 => Rgb([65, 65, 65]),
-            Stone::White90 => Rgb([230, 230, 230]),
-            Stone::White70 => Rgb([150, 150, 150]),
+        self.draw_quantum_stone(Self::get_pos(row, column), stone);
+    }
+
+    fn draw_quantum_stone(&mut self, pos: (i32, i32), stone: Stone) {
+        let (color, label) = match stone {
+            Stone::Black90 => (self.palette.black90, "90"),
+            Stone::Black70 => (self.palette.black70, "70"),
+            Stone::White90 => (self.palette.white90, "90"),
+            Stone::White70 => (self.palette.white70, "70"),
             Stone::None => panic!("'None' never comes in here."),
         };
 
-        draw_filled_circle_mut(&mut self.img, pos, 24, color);
+        self.draw_stone(pos, color);
+
+        if self.stone_labels {
+            let text_color = text_color_on(color);
+
+            // 70% の石は色だけでなく内側の輪でも見分けられるようにする
+            if matches!(stone, Stone::Black70 | Stone::White70) {
+                draw_hollow_circle_mut(&mut self.img, pos, STONE_RADIUS - 5, text_color);
+            }
+
+            draw_small_label(&mut self.img, text_color, pos, label);
+        }
     }
 
     fn push_observed_stone(&mut self, row: i32, column: i32, stone: ObservedStone) {
         let pos = Self::get_pos(row, column);
         let color = match stone {
-            ObservedStone::Black => Rgb([20, 20, 20]),
-            ObservedStone::White => Rgb([230, 230, 230]),
+            ObservedStone::Black => self.palette.black,
+            ObservedStone::White => self.palette.white,
             ObservedStone::None => panic!("'None' never comes in here."),
         };
 
-        draw_filled_circle_mut(&mut self.img, pos, 24, color);
+        self.draw_stone(pos, color);
+    }
+
+    fn draw_stone(&mut self, pos: (i32, i32), color: Rgb<u8>) {
+        draw_filled_circle_mut(&mut self.img, pos, STONE_RADIUS, color);
+
+        if let Some(outline) = self.palette.outline {
+            draw_hollow_circle_mut(&mut self.img, pos, STONE_RADIUS, outline);
+        }
+    }
+
+    /// 盤面の下に凡例の場所を付け足し、その中心の y 座標を返す
+    fn extend_for_legend(&mut self) -> i32 {
+        let mut img =
+            ImageBuffer::from_pixel(IMAGE_SIZE, IMAGE_SIZE + LEGEND_HEIGHT, self.palette.board);
+        image::imageops::replace(&mut img, &self.img, 0, 0);
+        self.img = img;
+
+        return (IMAGE_SIZE + LEGEND_HEIGHT / 2) as i32;
+    }
+
+    /// 盤面の下に、量子の石の色と確率の凡例を付け足す
+    fn add_legend(&mut self) {
+        let center_y = self.extend_for_legend();
+        self.draw_legend(0, center_y);
+    }
+
+    /// x = offset_x から、量子の石の色と確率の凡例を描く
+    fn draw_legend(&mut self, offset_x: i32, center_y: i32) {
+        draw_quantum_legend(
+            &mut self.img,
+            &self.palette,
+            self.stone_labels,
+            (offset_x, center_y),
+        );
+    }
+
+    /// 石のあるマスを、黒になる確率の色のマスで塗る
+    fn push_heat_tile(&mut self, pos: (i32, i32), black_possibility: f64) {
+        let color = heat_color(black_possibility);
+        let half = STONE_RADIUS - 2;
+
+        let rect = Rect::at(pos.0 - half, pos.1 - half).of_size(half as u32 * 2, half as u32 * 2);
+        draw_filled_rect_mut(&mut self.img, rect, color);
+
+        let percent = (black_possibility * 100.0).round().to_string();
+        draw_small_label(&mut self.img, text_color_on(color), pos, &percent);
+    }
+
+    /// 空いているマスに、置いて観測したときに5つが揃う確率を描く
+    fn push_five_chance(&mut self, pos: (i32, i32), chance: f64) {
+        // 低い確率でも見えるように、色の濃さは 25% から
+        let color = mix(self.palette.board, HEAT_FIVE, 0.25 + chance.min(1.0) * 0.75);
+        draw_filled_circle_mut(&mut self.img, pos, STONE_RADIUS - 6, color);
+
+        let percent = ((chance * 100.0).round() as u32).max(1).to_string();
+        draw_small_label(&mut self.img, text_color_on(color), pos, &percent);
+    }
+
+    /// ヒートマップの凡例
+    fn add_heatmap_legend(&mut self) {
+        let center_y = self.extend_for_legend();
+
+        let mut left = 30;
+        for black_possibility in [0.9, 0.7, 0.3, 0.1] {
+            self.push_heat_tile((left + STONE_RADIUS, center_y), black_possibility);
+            left += STONE_RADIUS * 2 + 4;
+        }
+        draw_small_text(
+            &mut self.img,
+            self.palette.line,
+            (left + 8, center_y - SMALL_HEIGHT / 2),
+            "BLACK %",
+        );
+
+        let left = 470;
+        self.push_five_chance((left + STONE_RADIUS, center_y), 0.3);
+        draw_small_text(
+            &mut self.img,
+            self.palette.line,
+            (left + STONE_RADIUS * 2 + 12, center_y - SMALL_HEIGHT / 2),
+            "FIVE CHANCE %",
+        );
+    }
+
+    /// 観測した石のうち、なりやすい方と違う色になった石に印を付ける
+    fn mark_flipped(
+        &mut self,
+        board: &[[Stone; 19]; 19],
+        observed_board: &[[ObservedStone; 19]; 19],
+    ) {
+        for (row, rows) in board.iter().enumerate() {
+            for (column, stone) in rows.iter().enumerate() {
+                if *stone == Stone::None || stone.majority() == observed_board[row][column] {
+                    continue;
+                }
+
+                self.draw_flipped_mark(Self::get_pos(row as i32, column as i32));
+            }
+        }
+    }
+
+    fn draw_flipped_mark(&mut self, pos: (i32, i32)) {
+        for radius in STONE_RADIUS - 8..=STONE_RADIUS - 5 {
+            draw_hollow_circle_mut(&mut self.img, pos, radius, FLIPPED_COLOR);
+        }
+    }
+
+    fn mark_last_move(&mut self, row: i32, column: i32) {
+        let pos = Self::get_pos(row, column);
+
+        // 石の上の確率を隠さないように、確率を描くときは外側の輪にする
+        if self.stone_labels {
+            for radius in STONE_RADIUS + 1..=STONE_RADIUS + 3 {
+                draw_hollow_circle_mut(&mut self.img, pos, radius, self.palette.highlight);
+            }
+        } else {
+            draw_filled_circle_mut(&mut self.img, pos, 7, self.palette.highlight);
+        }
+    }
+
+    fn mark_connected_mass(&mut self, mass: ConnectedMass) {
+        for (row, column) in mass {
+            let pos = Self::get_pos(row as i32, column as i32);
+
+            for radius in STONE_RADIUS..=STONE_RADIUS + 3 {
+                draw_hollow_circle_mut(&mut self.img, pos, radius, self.palette.highlight);
+            }
+        }
+
+        // 端の石から端の石まで太線を引く
+        let start = Self::get_pos(mass[0].0 as i32, mass[0].1 as i32);
+        let end = Self::get_pos(mass[4].0 as i32, mass[4].1 as i32);
+
+        let steps = (end.0 - start.0).abs().max((end.1 - start.1).abs());
+        for i in 0..=steps {
+            let pos = (
+                start.0 + (end.0 - start.0) * i / steps,
+                start.1 + (end.1 - start.1) * i / steps,
+            );
+
+            draw_filled_circle_mut(&mut self.img, pos, 4, self.palette.highlight);
+        }
     }
 }
 
-pub async fn gen_quantum_board_image(board: [[Stone; 19]; 19]) -> Vec<u8> {
-    let mut img = GenImage::new().await;
+/// 量子の石の盤面の画像 (同じ盤面ならキャッシュから返す)
+pub async fn gen_quantum_board_image(
+    board: [[Stone; 19]; 19],
+    last_move: Option<(usize, usize)>,
+    style: BoardStyle,
+) -> Vec<u8> {
+    render(
+        ("quantum_board", board, last_move, style, output()),
+        move || draw_quantum_board_image(board, last_move, style),
+    )
+    .await
+}
+
+/// 量子の石の盤面の画像をこのスレッドで描く (重いので、非同期の処理からは gen_quantum_board_image を使う)
+pub fn draw_quantum_board_image(
+    board: [[Stone; 19]; 19],
+    last_move: Option<(usize, usize)>,
+    style: BoardStyle,
+) -> Vec<u8> {
+    let mut img = GenImage::new(style);
 
     for (row, rows) in board.iter().enumerate() {
         for (column, stone) in rows.iter().enumerate() {
@@ -135,15 +320,200 @@ pub async fn gen_quantum_board_image(board: [[Stone; 19]; 19]) -> Vec<u8> {
         }
     }
 
-    let mut bytes: Vec<u8> = Vec::new();
-    let mut writer = Cursor::new(&mut bytes);
-    img.img.write_to(&mut writer, ImageFormat::Png).unwrap();
+    if let Some((row, column)) = last_move {
+        img.mark_last_move(row as i32, column as i32);
+    }
+
+    img.add_legend();
+
+    return encode(&img.img, output());
+}
+
+/// 観測する前の盤面 (左) と観測した結果 (右) を並べた画像 (同じ盤面ならキャッシュから返す)
+///
+/// 観測した結果では、なりやすい方と違う色になった石に印を付ける。
+pub async fn gen_observation_image(
+    board: [[Stone; 19]; 19],
+    last_move: Option<(usize, usize)>,
+    observed_board: [[ObservedStone; 19]; 19],
+    connected_mass: Option<ConnectedMass>,
+    style: BoardStyle,
+) -> Vec<u8> {
+    let key = (
+        "observation",
+        board,
+        last_move,
+        observed_board,
+        connected_mass,
+        style,
+        output(),
+    );
+
+    render(key, move || {
+        draw_observation_image(board, last_move, observed_board, connected_mass, style)
+    })
+    .await
+}
+
+pub fn draw_observation_image(
+    board: [[Stone; 19]; 19],
+    last_move: Option<(usize, usize)>,
+    observed_board: [[ObservedStone; 19]; 19],
+    connected_mass: Option<ConnectedMass>,
+    style: BoardStyle,
+) -> Vec<u8> {
+    let mut quantum = GenImage::new(style);
+    for (row, rows) in board.iter().enumerate() {
+        for (column, stone) in rows.iter().enumerate() {
+            if *stone != Stone::None {
+                quantum.push_stone(row as i32, column as i32, *stone)
+            }
+        }
+    }
+    if let Some((row, column)) = last_move {
+        quantum.mark_last_move(row as i32, column as i32);
+    }
+
+    let mut observed = GenImage::new(style);
+    for (row, rows) in observed_board.iter().enumerate() {
+        for (column, observed_stone) in rows.iter().enumerate() {
+            if *observed_stone != ObservedStone::None {
+                observed.push_observed_stone(row as i32, column as i32, *observed_stone)
+            }
+        }
+    }
+    observed.mark_flipped(&board, &observed_board);
+    if let Some(mass) = connected_mass {
+        observed.mark_connected_mass(mass);
+    }
+
+    let palette = quantum.palette;
+    let mut img = GenImage {
+        img: ImageBuffer::from_pixel(
+            IMAGE_SIZE * 2,
+            TITLE_HEIGHT + IMAGE_SIZE + LEGEND_HEIGHT,
+            palette.board,
+        ),
+        palette,
+        stone_labels: quantum.stone_labels,
+    };
+    image::imageops::replace(&mut img.img, &quantum.img, 0, TITLE_HEIGHT as i64);
+    image::imageops::replace(
+        &mut img.img,
+        &observed.img,
+        IMAGE_SIZE as i64,
+        TITLE_HEIGHT as i64,
+    );
+
+    // 2枚の盤面の仕切り
+    let rect = Rect::at(IMAGE_SIZE as i32 - 1, 0).of_size(2, img.img.height());
+    draw_filled_rect_mut(&mut img.img, rect, palette.line);
+
+    draw_label(&mut img.img, palette.line, 75, 8, "SUPERPOSED");
+    draw_label(
+        &mut img.img,
+        palette.line,
+        IMAGE_SIZE as i32 + 75,
+        8,
+        "OBSERVED",
+    );
+
+    let center_y = (TITLE_HEIGHT + IMAGE_SIZE + LEGEND_HEIGHT / 2) as i32;
+    img.draw_legend(0, center_y);
+
+    let left = IMAGE_SIZE as i32 + 30;
+    img.draw_stone((left + STONE_RADIUS, center_y), palette.black);
+    img.draw_flipped_mark((left + STONE_RADIUS, center_y));
+    draw_small_text(
+        &mut img.img,
+        palette.line,
+        (left + STONE_RADIUS * 2 + 12, center_y - SMALL_HEIGHT / 2),
+        "FLIPPED FROM LIKELY COLOR",
+    );
+
+    return encode(&img.img, output());
+}
 
-    return bytes;
+/// 石のあるマスを黒になる確率の色で、空いているマスを置いて観測したときに5つが揃う確率で描く
+pub async fn gen_quantum_heatmap_image(gomoku: &QuantumGomoku, style: BoardStyle) -> Vec<u8> {
+    let gomoku = gomoku.clone();
+    let key = (
+        "quantum_heatmap",
+        gomoku.board,
+        gomoku.current_turn,
+        style,
+        output(),
+    );
+
+    render(key, move || draw_quantum_heatmap_image(&gomoku, style)).await
 }
 
-pub async fn gen_quantum_observedboard_image(observed_board: [[ObservedStone; 19]; 19]) -> Vec<u8> {
-    let mut img = GenImage::new().await;
+pub fn draw_quantum_heatmap_image(gomoku: &QuantumGomoku, style: BoardStyle) -> Vec<u8> {
+    let mut img = GenImage::new(style);
+
+    for (row, chances) in five_chances(gomoku).iter().enumerate() {
+        for (column, chance) in chances.iter().enumerate() {
+            let pos = GenImage::get_pos(row as i32, column as i32);
+
+            match chance {
+                None => img.push_heat_tile(pos, gomoku.board[row][column].black_possibility()),
+                Some(chance) if *chance >= HEAT_MIN_CHANCE => img.push_five_chance(pos, *chance),
+                Some(_) => (),
+            }
+        }
+    }
+
+    img.add_heatmap_legend();
+
+    return encode(&img.img, output());
+}
+
+/// 黒になる確率の色 (白になりやすいほど赤、黒になりやすいほど青)
+fn heat_color(black_possibility: f64) -> Rgb<u8> {
+    if black_possibility < 0.5 {
+        mix(HEAT_WHITE, HEAT_EVEN, black_possibility * 2.0)
+    } else {
+        mix(HEAT_EVEN, HEAT_BLACK, (black_possibility - 0.5) * 2.0)
+    }
+}
+
+/// from と to を rate (0.0 - 1.0) の割合で混ぜた色
+fn mix(from: Rgb<u8>, to: Rgb<u8>, rate: f64) -> Rgb<u8> {
+    let channel =
+        |i: usize| (from.0[i] as f64 + (to.0[i] as f64 - from.0[i] as f64) * rate).round() as u8;
+
+    Rgb([channel(0), channel(1), channel(2)])
+}
+
+/// 黒白のみの盤面を描画する (通常の五目並べでも使う)
+pub async fn gen_observedboard_image(
+    observed_board: [[ObservedStone; 19]; 19],
+    last_move: Option<(usize, usize)>,
+    connected_mass: Option<ConnectedMass>,
+    style: BoardStyle,
+) -> Vec<u8> {
+    let key = (
+        "observed_board",
+        observed_board,
+        last_move,
+        connected_mass,
+        style,
+        output(),
+    );
+
+    render(key, move || {
+        draw_observedboard_image(observed_board, last_move, connected_mass, style)
+    })
+    .await
+}
+
+pub fn draw_observedboard_image(
+    observed_board: [[ObservedStone; 19]; 19],
+    last_move: Option<(usize, usize)>,
+    connected_mass: Option<ConnectedMass>,
+    style: BoardStyle,
+) -> Vec<u8> {
+    let mut img = GenImage::new(style);
 
     for (row, rows) in observed_board.iter().enumerate() {
         for (column, observed_stone) in rows.iter().enumerate() {
@@ -153,30 +523,200 @@ pub async fn gen_quantum_observedboard_image(observed_board: [[ObservedStone; 19
         }
     }
 
-    let mut bytes: Vec<u8> = Vec::new();
-    let mut writer = Cursor::new(&mut bytes);
-    img.img.write_to(&mut writer, ImageFormat::Png).unwrap();
+    if let Some((row, column)) = last_move {
+        img.mark_last_move(row as i32, column as i32);
+    }
 
-    return bytes;
+    if let Some(mass) = connected_mass {
+        img.mark_connected_mass(mass);
+    }
+
+    return encode(&img.img, output());
 }
 
 #[cfg(test)]
 mod tests {
     use super::GenImage;
-    use crate::quantum_gomoku::gomoku::{ObservedStone, Stone};
+    use crate::quantum_gomoku::gomoku::{ObservedStone, QuantumGomoku, Stone};
+    use crate::render::theme::{BoardStyle, Theme};
+
+    #[test]
+    fn labels_without_font() {
+        // テストではフォントを読み込まないので、ビットマップ文字で描かれる
+        let img = GenImage::new(BoardStyle::default());
+
+        let label_pixels = (0..50)
+            .flat_map(|x| (50..100).map(move |y| (x, y)))
+            .filter(|&(x, y)| *img.img.get_pixel(x, y) == img.palette.line)
+            .count();
+
+        assert!(label_pixels > 0, "座標の \"1\" が描かれていない");
+    }
+
+    #[test]
+    fn themes() {
+        for theme in Theme::ALL {
+            let img = GenImage::new(BoardStyle::new(theme));
+
+            assert_eq!(
+                theme.palette().board,
+                *img.img.get_pixel(1, 1),
+                "{:?}",
+                theme
+            );
+        }
+    }
+
+    #[test]
+    fn stone_labels() {
+        // 石の中に、石の色でない文字 ("90") が描かれるか
+        fn label_pixels(style: BoardStyle) -> usize {
+            let mut img = GenImage::new(style);
+            img.push_stone(7, 7, Stone::Black90);
+
+            let (x, y) = GenImage::get_pos(7, 7);
+            let fill = style.theme.palette().black90;
+
+            (x - 12..x + 12)
+                .flat_map(|x| (y - 8..y + 8).map(move |y| (x as u32, y as u32)))
+                .filter(|&(x, y)| *img.img.get_pixel(x, y) != fill)
+                .count()
+        }
+
+        let wood = BoardStyle::new(Theme::Wood);
+        assert_eq!(0, label_pixels(wood));
+
+        let labeled = BoardStyle {
+            stone_labels: true,
+            ..wood
+        };
+        assert!(label_pixels(labeled) > 0);
+
+        // 色覚配慮のテーマでは設定にかかわらず描く
+        assert!(label_pixels(BoardStyle::new(Theme::Colorblind)) > 0);
+    }
+
+    #[tokio::test]
+    async fn legend_below_quantum_board() {
+        let png =
+            super::gen_quantum_board_image([[Stone::None; 19]; 19], None, BoardStyle::default())
+                .await;
+        let img = image::load_from_memory(&png).unwrap().to_rgb8();
+
+        assert_eq!(
+            (super::IMAGE_SIZE, super::IMAGE_SIZE + super::LEGEND_HEIGHT),
+            img.dimensions()
+        );
+
+        // 1つ目の凡例の石 (90%黒)
+        let palette = Theme::default().palette();
+        let y = super::IMAGE_SIZE + super::LEGEND_HEIGHT / 2;
+        assert_eq!(palette.black90, *img.get_pixel(30 + 24, y));
+
+        // 観測した盤面には凡例を付けない
+        let png = super::gen_observedboard_image(
+            [[ObservedStone::None; 19]; 19],
+            None,
+            None,
+            BoardStyle::default(),
+        )
+        .await;
+        let img = image::load_from_memory(&png).unwrap();
+        assert_eq!(super::IMAGE_SIZE, img.height());
+    }
+
+    #[tokio::test]
+    async fn heatmap() {
+        let mut gomoku = QuantumGomoku::new();
+        for column in 3..7 {
+            gomoku.board[7][column] = Stone::Black90;
+        }
+        gomoku.board[0][0] = Stone::White70;
+
+        let png = super::gen_quantum_heatmap_image(&gomoku, BoardStyle::default()).await;
+        let img = image::load_from_memory(&png).unwrap().to_rgb8();
+
+        assert_eq!(super::IMAGE_SIZE + super::LEGEND_HEIGHT, img.height());
+
+        // 石のあるマスは黒になる確率の色 (角は文字がかからない)
+        let (x, y) = GenImage::get_pos(0, 0);
+        assert_eq!(
+            super::heat_color(0.3),
+            *img.get_pixel(x as u32 - 20, y as u32 - 20)
+        );
+
+        // 揃う見込みのある空きマス (7, 7) は塗られ、見込みのない (10, 10) は塗られない
+        let board = Theme::default().palette().board;
+        let (x, y) = GenImage::get_pos(7, 7);
+        assert_ne!(board, *img.get_pixel(x as u32 - 10, y as u32 - 10));
+        let (x, y) = GenImage::get_pos(10, 10);
+        assert_eq!(board, *img.get_pixel(x as u32 - 10, y as u32 - 10));
+    }
 
     #[tokio::test]
+    async fn observation_side_by_side() {
+        let mut board = [[Stone::None; 19]; 19];
+        board[7][7] = Stone::Black90;
+        board[7][8] = Stone::White70;
+        let mut observed_board = [[ObservedStone::None; 19]; 19];
+        observed_board[7][7] = ObservedStone::Black;
+        // 70% で白になるはずの石が黒になった
+        observed_board[7][8] = ObservedStone::Black;
+
+        let png = super::gen_observation_image(
+            board,
+            Some((7, 8)),
+            observed_board,
+            None,
+            BoardStyle::default(),
+        )
+        .await;
+        let img = image::load_from_memory(&png).unwrap().to_rgb8();
+
+        assert_eq!(
+            (
+                super::IMAGE_SIZE * 2,
+                super::TITLE_HEIGHT + super::IMAGE_SIZE + super::LEGEND_HEIGHT
+            ),
+            img.dimensions()
+        );
+
+        // 右の盤面の石の、中心から輪の半径だけ離れたところ
+        let mark_pixel = |row: i32, column: i32| {
+            let (x, y) = GenImage::get_pos(row, column);
+            let x = super::IMAGE_SIZE as i32 + x + super::STONE_RADIUS - 6;
+            *img.get_pixel(x as u32, (super::TITLE_HEIGHT as i32 + y) as u32)
+        };
+        assert_eq!(super::FLIPPED_COLOR, mark_pixel(7, 8));
+        assert_eq!(Theme::default().palette().black, mark_pixel(7, 7));
+
+        // 左は観測する前の量子の石
+        let (x, y) = GenImage::get_pos(7, 8);
+        assert_eq!(
+            Theme::default().palette().white70,
+            *img.get_pixel((x - 15) as u32, (super::TITLE_HEIGHT as i32 + y) as u32)
+        );
+    }
+
+    #[test]
+    fn heat_colors() {
+        assert_eq!(super::HEAT_WHITE, super::heat_color(0.0));
+        assert_eq!(super::HEAT_EVEN, super::heat_color(0.5));
+        assert_eq!(super::HEAT_BLACK, super::heat_color(1.0));
+    }
+
+    #[test]
     #[ignore]
-    async fn draw_board() {
-        let img = GenImage::new().await;
+    fn draw_board() {
+        let img = GenImage::new(BoardStyle::default());
 
         img.img.save("test.png").expect("error");
     }
 
-    #[tokio::test]
+    #[test]
     #[ignore]
-    async fn push_stone() {
-        let mut img = GenImage::new().await;
+    fn push_stone() {
+        let mut img = GenImage::new(BoardStyle::default());
 
         for i in 0..9 {
             img.push_stone(i, i, Stone::Black90);
@@ -188,10 +728,10 @@ mod tests {
         img.img.save("test.png").expect("error");
     }
 
-    #[tokio::test]
+    #[test]
     #[ignore]
-    async fn push_observed_stone() {
-        let mut img = GenImage::new().await;
+    fn push_observed_stone() {
+        let mut img = GenImage::new(BoardStyle::default());
 
         for i in 0..9 {
             img.push_observed_stone(i, i, ObservedStone::Black);
@@ -201,23 +741,29 @@ mod tests {
         img.img.save("test.png").expect("error");
     }
 
-    #[tokio::test]
+    #[test]
     #[ignore]
-    async fn gen_board_image() {
-        let mut board = [[Stone::White70; 19]; 19];
-        board[0] = [Stone::Black90; 19];
+    fn mark_connected_mass() {
+        let mut img = GenImage::new(BoardStyle::default());
 
-        let vec = super::gen_quantum_board_image(board).await;
-        println!("{:?}", vec);
+        for i in 0..5 {
+            img.push_observed_stone(i + 2, 8 - i, ObservedStone::Black);
+        }
+        img.mark_connected_mass([(2, 8), (3, 7), (4, 6), (5, 5), (6, 4)]);
+
+        img.push_observed_stone(10, 10, ObservedStone::White);
+        img.mark_last_move(10, 10);
+
+        img.img.save("test.png").expect("error");
     }
 
     #[tokio::test]
     #[ignore]
-    async fn gen_observedboard_image() {
-        let mut board = [[ObservedStone::White; 19]; 19];
-        board[0] = [ObservedStone::Black; 19];
+    async fn gen_board_image() {
+        let mut board = [[Stone::White70; 19]; 19];
+        board[0] = [Stone::Black90; 19];
 
-        let vec = super::gen_quantum_observedboard_image(board).await;
+        let vec = super::gen_quantum_board_image(board, Some((0, 0)), BoardStyle::default()).await;
         println!("{:?}", vec);
     }
 }
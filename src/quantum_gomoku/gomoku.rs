@@ -7,7 +7,15 @@ https://www.youtube.com/watch?v=mitAxA3f4U4
 
 use rand::Rng;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+use crate::color::Color;
+
+/// 横・縦・右下がり・左下がりの4方向 (五目並べ・AIと共通)
+pub const LINE_MOVES: [(i32, i32); 4] = [(0, 1), (1, 0), (1, 1), (1, -1)];
+
+/// 揃った5つの石の座標 (端から順番)
+pub type ConnectedMass = [(usize, usize); 5];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Stone {
     Black90,
     Black70,
@@ -16,48 +24,147 @@ pub enum Stone {
     None,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+impl Stone {
+    /// 観測したときに黒になる確率
+    pub fn black_possibility(&self) -> f64 {
+        match self {
+            Stone::Black90 => 0.9,
+            Stone::Black70 => 0.7,
+            Stone::White70 => 0.3,
+            Stone::White90 => 0.1,
+            Stone::None => panic!("'None' never comes in here."),
+        }
+    }
+
+    /// 観測したときに color になる確率
+    pub fn color_possibility(&self, color: ObservedStone) -> f64 {
+        match color {
+            ObservedStone::Black => self.black_possibility(),
+            ObservedStone::White => 1.0 - self.black_possibility(),
+            ObservedStone::None => panic!("'None' never comes in here."),
+        }
+    }
+
+    /// なりやすい方の色 (観測してこの色と違えば「ひっくり返った」石)
+    pub fn majority(&self) -> ObservedStone {
+        match self {
+            Stone::Black90 | Stone::Black70 => ObservedStone::Black,
+            Stone::White90 | Stone::White70 => ObservedStone::White,
+            Stone::None => ObservedStone::None,
+        }
+    }
+}
+
+/// 手番に置く石 (石のないマスを含まない `Stone`)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Turn {
+    Black90,
+    White90,
+    Black70,
+    White70,
+}
+
+impl Turn {
+    /// 次の手番 (黒90% → 白90% → 黒70% → 白70% の順に回る)
+    pub fn next(self) -> Turn {
+        match self {
+            Turn::Black90 => Turn::White90,
+            Turn::White90 => Turn::Black70,
+            Turn::Black70 => Turn::White70,
+            Turn::White70 => Turn::Black90,
+        }
+    }
+
+    /// 盤面に置く石
+    pub fn stone(self) -> Stone {
+        match self {
+            Turn::Black90 => Stone::Black90,
+            Turn::White90 => Stone::White90,
+            Turn::Black70 => Stone::Black70,
+            Turn::White70 => Stone::White70,
+        }
+    }
+
+    /// 手番の人の色 (置く石のなりやすい方の色)
+    pub fn color(self) -> Color {
+        match self {
+            Turn::Black90 | Turn::Black70 => Color::Black,
+            Turn::White90 | Turn::White70 => Color::White,
+        }
+    }
+
+    pub fn majority(self) -> ObservedStone {
+        self.color().into()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum ObservedStone {
     Black,
     White,
     None,
 }
 
-#[derive(Debug)]
+impl From<Color> for ObservedStone {
+    fn from(color: Color) -> Self {
+        match color {
+            Color::Black => ObservedStone::Black,
+            Color::White => ObservedStone::White,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlaceError {
+    OutOfBoardRange,
+    AlreadyPlaced,
+}
+
+#[derive(Clone, Debug)]
 pub struct QuantumGomoku {
     pub board: [[Stone; 19]; 19],
-    pub current_turn: Stone,
+    pub current_turn: Turn,
+    pub last_move: Option<(usize, usize)>,
+}
+
+impl Default for QuantumGomoku {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl QuantumGomoku {
     pub fn new() -> Self {
         Self {
             board: [[Stone::None; 19]; 19],
-            current_turn: Stone::Black90,
+            current_turn: Turn::Black90,
+            last_move: None,
         }
     }
 
     pub fn switch_turn(&mut self) {
-        match self.current_turn {
-            Stone::Black90 => self.current_turn = Stone::White90,
-            Stone::White90 => self.current_turn = Stone::Black70,
-            Stone::Black70 => self.current_turn = Stone::White70,
-            Stone::White70 => self.current_turn = Stone::Black90,
-            Stone::None => panic!("'None' never comes in here."),
-        }
+        self.current_turn = self.current_turn.next();
     }
 
-    pub fn do_place(&mut self, row: usize, column: usize) -> Result<String, String> {
+    pub fn do_place(&mut self, row: usize, column: usize) -> Result<(), PlaceError> {
         if !(row <= 18 && column <= 18) {
-            return Err("OutOfBoardRange".to_string());
+            return Err(PlaceError::OutOfBoardRange);
         }
 
         if self.board[row][column] != Stone::None {
-            return Err("AlreadyPlace".to_string());
+            return Err(PlaceError::AlreadyPlaced);
         }
 
-        self.board[row][column] = self.current_turn;
-        return Ok("Success".to_string());
+        self.board[row][column] = self.current_turn.stone();
+        self.last_move = Some((row, column));
+        return Ok(());
+    }
+
+    /// 左上の size x size のマスがすべて埋まったか (メニューで選べるのは 15x15)
+    pub fn is_full(&self, size: usize) -> bool {
+        self.board[..size]
+            .iter()
+            .all(|row| row[..size].iter().all(|stone| *stone != Stone::None))
     }
 
     fn create_observed_board(&self) -> [[ObservedStone; 19]; 19] {
@@ -71,13 +178,7 @@ impl QuantumGomoku {
                 }
 
                 let randfloat: f64 = rng.gen();
-                let black_possibility = match elem {
-                    Stone::Black90 => 0.9,
-                    Stone::Black70 => 0.7,
-                    Stone::White70 => 0.3,
-                    Stone::White90 => 0.1,
-                    Stone::None => panic!("'None' never comes in here."),
-                };
+                let black_possibility = elem.black_possibility();
 
                 if randfloat < black_possibility {
                     observed[irow][icolumn] = ObservedStone::Black
@@ -90,33 +191,32 @@ impl QuantumGomoku {
         return observed;
     }
 
-    pub fn judge_winner(&self) -> (ObservedStone, [[ObservedStone; 19]; 19]) {
+    pub fn judge_winner(
+        &self,
+    ) -> (
+        ObservedStone,
+        [[ObservedStone; 19]; 19],
+        Option<ConnectedMass>,
+    ) {
         let observed_board = self.create_observed_board();
 
-        let turn_player = match self.current_turn {
-            Stone::Black90 => ObservedStone::Black,
-            Stone::Black70 => ObservedStone::Black,
-            Stone::White90 => ObservedStone::White,
-            Stone::White70 => ObservedStone::White,
-            Stone::None => panic!("'None' never comes in here."),
-        };
+        let turn_player = self.current_turn.color();
 
-        let is_black_connected = Self::is_mass_connected(observed_board, ObservedStone::Black);
-        let is_white_connected = Self::is_mass_connected(observed_board, ObservedStone::White);
+        let black_mass = Self::find_connected_mass(&observed_board, ObservedStone::Black);
+        let white_mass = Self::find_connected_mass(&observed_board, ObservedStone::White);
 
-        let winner = match (is_black_connected, is_white_connected) {
-            (true, false) => ObservedStone::Black,
-            (false, true) => ObservedStone::White,
-            (false, false) => ObservedStone::None,
+        let (winner, winner_mass) = match (black_mass, white_mass) {
+            (Some(mass), None) => (ObservedStone::Black, Some(mass)),
+            (None, Some(mass)) => (ObservedStone::White, Some(mass)),
+            (None, None) => (ObservedStone::None, None),
 
-            (true, true) => match turn_player {
-                ObservedStone::Black => ObservedStone::Black,
-                ObservedStone::White => ObservedStone::White,
-                ObservedStone::None => panic!("'None' never comes in here."),
+            (Some(black_mass), Some(white_mass)) => match turn_player {
+                Color::Black => (ObservedStone::Black, Some(black_mass)),
+                Color::White => (ObservedStone::White, Some(white_mass)),
             },
         };
 
-        return (winner, observed_board);
+        return (winner, observed_board, winner_mass);
     }
 
     fn get_mass_around(
@@ -136,19 +236,7 @@ impl QuantumGomoku {
             upperleft_line,
         ];
 
-        let horizontal_move = (0, 1);
-        let vertical_move = (1, 0);
-        let upperright_move = (1, 1);
-        let upperleft_move = (1, -1);
-
-        let moves = [
-            horizontal_move,
-            vertical_move,
-            upperright_move,
-            upperleft_move,
-        ];
-
-        for (line, move_) in lines.iter_mut().zip(moves) {
+        for (line, move_) in lines.iter_mut().zip(LINE_MOVES) {
             let (move_row, move_column) = move_;
             let (cur_row, cur_column) = (row - (move_row * 2), column - (move_column * 2));
 
@@ -156,7 +244,7 @@ impl QuantumGomoku {
                 let irow = cur_row + (move_row * i);
                 let icolumn = cur_column + (move_column * i);
 
-                if 0 <= irow && irow <= 18 && 0 <= icolumn && icolumn <= 18 {
+                if (0..=18).contains(&irow) && (0..=18).contains(&icolumn) {
                     line[i as usize] = observed_board[irow as usize][icolumn as usize];
                 } else {
                     line[i as usize] = ObservedStone::None;
@@ -167,33 +255,35 @@ impl QuantumGomoku {
         return lines;
     }
 
-    fn is_mass_connected(
-        observed_board: [[ObservedStone; 19]; 19],
+    fn find_connected_mass(
+        observed_board: &[[ObservedStone; 19]; 19],
         check_color: ObservedStone,
-    ) -> bool {
-        let mut is_complete = false;
-
+    ) -> Option<ConnectedMass> {
         for row in 0..19 {
             for column in 0..19 {
-                let around_mass = Self::get_mass_around(&observed_board, row, column);
-                for line in around_mass {
-                    for stone in line {
-                        is_complete = true;
-
-                        if stone != check_color {
-                            is_complete = false;
-                            break;
-                        }
+                let around_mass = Self::get_mass_around(observed_board, row, column);
+
+                for (line, (move_row, move_column)) in around_mass.iter().zip(LINE_MOVES) {
+                    if line.iter().any(|stone| *stone != check_color) {
+                        continue;
                     }
 
-                    if is_complete {
-                        return true;
+                    // get_mass_around は中心から前後2マスずつを見ている
+                    let mut mass = [(0, 0); 5];
+                    for (i, pos) in mass.iter_mut().enumerate() {
+                        let offset = i as i32 - 2;
+                        *pos = (
+                            (row + move_row * offset) as usize,
+                            (column + move_column * offset) as usize,
+                        );
                     }
+
+                    return Some(mass);
                 }
             }
         }
 
-        return false;
+        return None;
     }
 }
 
@@ -204,19 +294,26 @@ mod tests {
     #[test]
     fn turn_change() {
         let mut gomoku = QuantumGomoku::new();
-        assert_eq!(gomoku.current_turn, Stone::Black90);
+        assert_eq!(gomoku.current_turn, Turn::Black90);
 
         gomoku.switch_turn();
-        assert_eq!(gomoku.current_turn, Stone::White90);
+        assert_eq!(gomoku.current_turn, Turn::White90);
 
         gomoku.switch_turn();
-        assert_eq!(gomoku.current_turn, Stone::Black70);
+        assert_eq!(gomoku.current_turn, Turn::Black70);
 
         gomoku.switch_turn();
-        assert_eq!(gomoku.current_turn, Stone::White70);
+        assert_eq!(gomoku.current_turn, Turn::White70);
 
         gomoku.switch_turn();
-        assert_eq!(gomoku.current_turn, Stone::Black90);
+        assert_eq!(gomoku.current_turn, Turn::Black90);
+    }
+
+    #[test]
+    fn color_possibility() {
+        assert_eq!(0.7, Stone::Black70.color_possibility(ObservedStone::Black));
+        assert!((0.9 - Stone::White90.color_possibility(ObservedStone::White)).abs() < 1e-9);
+        assert_eq!(ObservedStone::White, Stone::White70.majority());
     }
 
     #[test]
@@ -224,9 +321,10 @@ mod tests {
         let mut gomoku = QuantumGomoku::new();
         gomoku.board[0][0] = Stone::Black90;
 
-        assert_eq!(Err("OutOfBoardRange".to_string()), gomoku.do_place(19, 19));
-        assert_eq!(Err("AlreadyPlace".to_string()), gomoku.do_place(0, 0));
-        assert_eq!(Ok("Success".to_string()), gomoku.do_place(0, 1));
+        assert_eq!(Err(PlaceError::OutOfBoardRange), gomoku.do_place(19, 19));
+        assert_eq!(Err(PlaceError::AlreadyPlaced), gomoku.do_place(0, 0));
+        assert_eq!(Ok(()), gomoku.do_place(0, 1));
+        assert_eq!(Some((0, 1)), gomoku.last_move);
     }
 
     #[test]
@@ -285,42 +383,89 @@ mod tests {
     }
 
     #[test]
-    fn is_mass_connected() {
+    fn mass_connected() {
         let mut observed = [[ObservedStone::Black; 19]; 19];
         observed[0] = [ObservedStone::White; 19];
 
         assert_eq!(
             true,
-            QuantumGomoku::is_mass_connected(observed, ObservedStone::Black)
+            QuantumGomoku::find_connected_mass(&observed, ObservedStone::Black).is_some()
         );
 
         assert_eq!(
             true,
-            QuantumGomoku::is_mass_connected(observed, ObservedStone::White)
+            QuantumGomoku::find_connected_mass(&observed, ObservedStone::White).is_some()
         );
 
         let observed = [[ObservedStone::Black; 19]; 19];
 
         assert_eq!(
             true,
-            QuantumGomoku::is_mass_connected(observed, ObservedStone::Black)
+            QuantumGomoku::find_connected_mass(&observed, ObservedStone::Black).is_some()
         );
 
         assert_eq!(
             false,
-            QuantumGomoku::is_mass_connected(observed, ObservedStone::White)
+            QuantumGomoku::find_connected_mass(&observed, ObservedStone::White).is_some()
         );
 
         let observed = [[ObservedStone::None; 19]; 19];
 
         assert_eq!(
             false,
-            QuantumGomoku::is_mass_connected(observed, ObservedStone::Black)
+            QuantumGomoku::find_connected_mass(&observed, ObservedStone::Black).is_some()
         );
 
         assert_eq!(
             false,
-            QuantumGomoku::is_mass_connected(observed, ObservedStone::White)
+            QuantumGomoku::find_connected_mass(&observed, ObservedStone::White).is_some()
         );
     }
+
+    #[test]
+    fn find_connected_mass() {
+        let mut observed = [[ObservedStone::None; 19]; 19];
+        for i in 3..8 {
+            observed[i][10 - i] = ObservedStone::White;
+        }
+
+        assert_eq!(
+            None,
+            QuantumGomoku::find_connected_mass(&observed, ObservedStone::Black)
+        );
+
+        assert_eq!(
+            Some([(3, 7), (4, 6), (5, 5), (6, 4), (7, 3)]),
+            QuantumGomoku::find_connected_mass(&observed, ObservedStone::White)
+        );
+
+        observed[7][3] = ObservedStone::Black;
+
+        assert_eq!(
+            None,
+            QuantumGomoku::find_connected_mass(&observed, ObservedStone::White)
+        );
+    }
+
+    #[test]
+    fn connected_mass_in_each_direction() {
+        // 横・縦・右下がりの5つは、左上の端の石から順番に並ぶ
+        let expected: [ConnectedMass; 3] = [
+            [(9, 0), (9, 1), (9, 2), (9, 3), (9, 4)],
+            [(0, 18), (1, 18), (2, 18), (3, 18), (4, 18)],
+            [(14, 14), (15, 15), (16, 16), (17, 17), (18, 18)],
+        ];
+
+        for mass in expected {
+            let mut observed = [[ObservedStone::None; 19]; 19];
+            for (row, column) in mass {
+                observed[row][column] = ObservedStone::Black;
+            }
+
+            assert_eq!(
+                Some(mass),
+                QuantumGomoku::find_connected_mass(&observed, ObservedStone::Black)
+            );
+        }
+    }
 }
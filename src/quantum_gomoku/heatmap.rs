@@ -0,0 +1,175 @@
+/*
+量子五目並べのヒートマップ用の確率です。
+
+空いているマスごとに「今の手番の石をそこに置いてすぐ観測したとき、そのマスを含む5つが揃う確率」を求めます。
+置いた石の色を決めれば、4方向の並びは置いた石以外の石を共有しないので方向ごとに独立に計算でき、
+1方向の中ではそのマスから左右に同じ色が何個続くかの分布から正確に求められます。
+*/
+
+use super::gomoku::{ObservedStone, QuantumGomoku, Stone, LINE_MOVES};
+
+// 盤面の配列は 19x19 だが、メニューで選べるのは 15x15
+pub const PLAY_SIZE: usize = 15;
+
+/// 空いているマスごとの、置いて観測したときに5つが揃う確率 (石のあるマスは None)
+pub fn five_chances(gomoku: &QuantumGomoku) -> [[Option<f64>; PLAY_SIZE]; PLAY_SIZE] {
+    let mut chances = [[None; PLAY_SIZE]; PLAY_SIZE];
+
+    for (row, rows) in chances.iter_mut().enumerate() {
+        for (column, chance) in rows.iter_mut().enumerate() {
+            if gomoku.board[row][column] == Stone::None {
+                *chance = Some(five_chance(gomoku, row, column));
+            }
+        }
+    }
+
+    return chances;
+}
+
+/// (row, column) に今の手番の石を置いてすぐ観測したとき、そのマスを含む5つが (どちらかの色で) 揃う確率
+pub fn five_chance(gomoku: &QuantumGomoku, row: usize, column: usize) -> f64 {
+    let black_possibility = gomoku.current_turn.stone().black_possibility();
+
+    black_possibility * five_chance_of(gomoku, row, column, ObservedStone::Black)
+        + (1.0 - black_possibility) * five_chance_of(gomoku, row, column, ObservedStone::White)
+}
+
+/// 置いた石が color だったときに、そのマスを含む color の5つが揃う確率
+fn five_chance_of(gomoku: &QuantumGomoku, row: usize, column: usize, color: ObservedStone) -> f64 {
+    let none_aligned: f64 = LINE_MOVES
+        .iter()
+        .map(|&move_| 1.0 - line_chance(gomoku, (row as i32, column as i32), move_, color))
+        .product();
+
+    return 1.0 - none_aligned;
+}
+
+/// 1方向で、置いたマスの前後に合わせて4つ以上 color が続く確率
+fn line_chance(
+    gomoku: &QuantumGomoku,
+    pos: (i32, i32),
+    move_: (i32, i32),
+    color: ObservedStone,
+) -> f64 {
+    let backward = at_least(gomoku, pos, (-move_.0, -move_.1), color);
+    let forward = at_least(gomoku, pos, move_, color);
+
+    // 後ろにちょうど n 個続き、前に 4 - n 個以上続く
+    (0..=4)
+        .map(|n| {
+            let exactly = backward[n] - if n < 4 { backward[n + 1] } else { 0.0 };
+            exactly * forward[4 - n]
+        })
+        .sum()
+}
+
+/// [k] = pos から move_ の向きに k 個以上 color が続く確率 (k = 0..=4)
+fn at_least(
+    gomoku: &QuantumGomoku,
+    pos: (i32, i32),
+    move_: (i32, i32),
+    color: ObservedStone,
+) -> [f64; 5] {
+    let mut chances = [0.0; 5];
+    chances[0] = 1.0;
+
+    for k in 1..=4 {
+        let (row, column) = (pos.0 + move_.0 * k as i32, pos.1 + move_.1 * k as i32);
+        let in_board =
+            (0..PLAY_SIZE as i32).contains(&row) && (0..PLAY_SIZE as i32).contains(&column);
+
+        let possibility = match in_board {
+            true => match gomoku.board[row as usize][column as usize] {
+                Stone::None => 0.0,
+                stone => stone.color_possibility(color),
+            },
+            false => 0.0,
+        };
+
+        chances[k] = chances[k - 1] * possibility;
+    }
+
+    return chances;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quantum_gomoku::gomoku::Turn;
+
+    fn assert_close(expected: f64, actual: f64) {
+        assert!(
+            (expected - actual).abs() < 1e-9,
+            "{} != {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn empty_board() {
+        let gomoku = QuantumGomoku::new();
+
+        for rows in five_chances(&gomoku) {
+            for chance in rows {
+                assert_eq!(Some(0.0), chance);
+            }
+        }
+    }
+
+    #[test]
+    fn four_in_a_row() {
+        let mut gomoku = QuantumGomoku::new();
+        for column in 3..7 {
+            gomoku.board[7][column] = Stone::Black90;
+        }
+
+        // 黒90%を置くと、5つすべてが黒になるか、(10%で白になり) すべてが白になる確率
+        let expected = 0.9_f64.powi(5) + 0.1_f64.powi(5);
+        assert_close(expected, five_chance(&gomoku, 7, 7));
+        assert_close(expected, five_chance(&gomoku, 7, 2));
+
+        gomoku.current_turn = Turn::White70;
+        assert_close(
+            0.3 * 0.9_f64.powi(4) + 0.7 * 0.1_f64.powi(4),
+            five_chance(&gomoku, 7, 7),
+        );
+
+        // 間が空いていれば揃わない
+        assert_close(0.0, five_chance(&gomoku, 7, 8));
+        assert_eq!(None, five_chances(&gomoku)[7][3]);
+    }
+
+    #[test]
+    fn both_sides_and_overlap() {
+        let mut gomoku = QuantumGomoku::new();
+        // 置くマス (7, 7) の左右に2つずつと、さらに右に1つ
+        for column in [5, 6, 8, 9, 10] {
+            gomoku.board[7][column] = Stone::Black90;
+        }
+
+        // C5-C9 か C6-C10 のどちらかが黒: 0.9^4 (5..=9) + (5 が白で 6..=10 が黒)
+        let black_given_black = 0.9_f64.powi(4) + 0.1 * 0.9_f64.powi(4);
+        let white_given_white = 0.1_f64.powi(4) + 0.9 * 0.1_f64.powi(4);
+        assert_close(
+            0.9 * black_given_black + 0.1 * white_given_white,
+            five_chance(&gomoku, 7, 7),
+        );
+    }
+
+    #[test]
+    fn two_directions() {
+        let mut gomoku = QuantumGomoku::new();
+        for i in 1..5 {
+            gomoku.board[7][7 + i] = Stone::Black70;
+            gomoku.board[7 + i][7] = Stone::Black70;
+        }
+
+        // 置いた石の色を決めれば、横と縦は独立
+        let either = |line: f64| 1.0 - (1.0 - line) * (1.0 - line);
+        assert_close(
+            0.9 * either(0.7_f64.powi(4)) + 0.1 * either(0.3_f64.powi(4)),
+            five_chance(&gomoku, 7, 7),
+        );
+    }
+}
@@ -1,3 +1,4 @@
+pub mod ai;
 pub mod gen_image;
 pub mod gomoku;
-pub mod stats;
+pub mod heatmap;
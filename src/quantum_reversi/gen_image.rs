@@ -0,0 +1,433 @@
+/*
+量子リバーシの盤面の画像です。
+
+色・文字・画像の形式・キャッシュは量子五目並べの画像と同じものを使います。
+行 (A-H) は縦、列 (1-8) は横に並べ、手番の人が置けるマスには小さい点を描きます。
+色の決まった石と同じ色に見える量子の石があるので、量子の石には設定にかかわらず確率を描きます。
+*/
+
+use image::{ImageBuffer, Rgb};
+use imageproc::{
+    drawing::{draw_filled_circle_mut, draw_filled_rect_mut, draw_hollow_circle_mut},
+    rect::Rect,
+};
+
+use super::reversi::{Disc, ObservedBoard, ALPHABETS, SIZE};
+use crate::quantum_gomoku::gomoku::{ObservedStone, Stone};
+use crate::render::encode::{encode, output, FULL_WIDTH};
+use crate::render::font::{draw_label, draw_small_label, draw_small_text, SMALL_HEIGHT};
+use crate::render::legend::draw_quantum_legend;
+use crate::render::render_cache::render;
+use crate::render::theme::{text_color_on, BoardStyle, Palette};
+
+const IMAGE_SIZE: u32 = FULL_WIDTH;
+/// 盤面の下の凡例の高さ
+const LEGEND_HEIGHT: u32 = 80;
+/// 観測の前後を並べた画像の、盤面の上の見出しの高さ
+const TITLE_HEIGHT: u32 = 50;
+
+/// 盤の外側の余白 (座標の文字を描く)
+const MARGIN: i32 = 65;
+const CELL: i32 = 90;
+const LINE_WIDTH: u32 = 3;
+
+const STONE_RADIUS: i32 = 36;
+const HINT_RADIUS: i32 = 7;
+
+/// 観測して、なりやすい方と違う色になった石の印 (量子五目並べと同じ色)
+const FLIPPED_COLOR: Rgb<u8> = Rgb([0, 158, 115]);
+
+struct GenImage {
+    img: ImageBuffer<Rgb<u8>, Vec<u8>>,
+    palette: Palette,
+    /// 70% の石に模様を描く
+    patterns: bool,
+}
+
+impl GenImage {
+    fn new(style: BoardStyle) -> Self {
+        let palette = style.theme.palette();
+
+        Self {
+            img: Self::draw_board(palette),
+            palette,
+            patterns: style.shows_stone_labels(),
+        }
+    }
+
+    /// マスの中心 (x, y)
+    fn get_pos(row: usize, column: usize) -> (i32, i32) {
+        (
+            MARGIN + column as i32 * CELL + CELL / 2,
+            MARGIN + row as i32 * CELL + CELL / 2,
+        )
+    }
+
+    fn draw_board(palette: Palette) -> ImageBuffer<Rgb<u8>, Vec<u8>> {
+        let mut img = ImageBuffer::from_pixel(IMAGE_SIZE, IMAGE_SIZE, palette.board);
+        let length = (CELL * SIZE as i32) as u32 + LINE_WIDTH;
+
+        for i in 0..=SIZE as i32 {
+            let offset = MARGIN + i * CELL - LINE_WIDTH as i32 / 2;
+
+            let rect = Rect::at(offset, MARGIN - 1).of_size(LINE_WIDTH, length);
+            draw_filled_rect_mut(&mut img, rect, palette.line);
+
+            let rect = Rect::at(MARGIN - 1, offset).of_size(length, LINE_WIDTH);
+            draw_filled_rect_mut(&mut img, rect, palette.line);
+        }
+
+        // 盤の目印の4つの点
+        for row in [2, 6] {
+            for column in [2, 6] {
+                let center = (MARGIN + column * CELL, MARGIN + row * CELL);
+                draw_filled_circle_mut(&mut img, center, 6, palette.line);
+            }
+        }
+
+        for column in 0..SIZE {
+            let (x, _) = Self::get_pos(0, column);
+            draw_label(&mut img, palette.line, x - 8, 15, &(column + 1).to_string());
+        }
+
+        for (row, alphabet) in ALPHABETS.chars().enumerate() {
+            let (_, y) = Self::get_pos(row, 0);
+            draw_label(&mut img, palette.line, 22, y - 16, &alphabet.to_string());
+        }
+
+        return img;
+    }
+
+    fn push_disc(&mut self, row: usize, column: usize, disc: Disc) {
+        let pos = Self::get_pos(row, column);
+
+        match disc {
+            Disc::Quantum(stone) => self.draw_quantum_stone(pos, stone),
+            Disc::Observed(stone) => self.draw_observed_stone(pos, stone),
+            Disc::None => (),
+        }
+    }
+
+    fn draw_quantum_stone(&mut self, pos: (i32, i32), stone: Stone) {
+        let (color, label) = match stone {
+            Stone::Black90 => (self.palette.black90, "90"),
+            Stone::Black70 => (self.palette.black70, "70"),
+            Stone::White90 => (self.palette.white90, "90"),
+            Stone::White70 => (self.palette.white70, "70"),
+            Stone::None => panic!("'None' never comes in here."),
+        };
+
+        self.draw_stone(pos, color);
+        let text_color = text_color_on(color);
+
+        // 70% の石は色だけでなく内側の輪でも見分けられるようにする
+        if self.patterns && matches!(stone, Stone::Black70 | Stone::White70) {
+            draw_hollow_circle_mut(&mut self.img, pos, STONE_RADIUS - 6, text_color);
+        }
+
+        draw_small_label(&mut self.img, text_color, pos, label);
+    }
+
+    fn draw_observed_stone(&mut self, pos: (i32, i32), stone: ObservedStone) {
+        let color = match stone {
+            ObservedStone::Black => self.palette.black,
+            ObservedStone::White => self.palette.white,
+            ObservedStone::None => panic!("'None' never comes in here."),
+        };
+
+        self.draw_stone(pos, color);
+    }
+
+    fn draw_stone(&mut self, pos: (i32, i32), color: Rgb<u8>) {
+        self.draw_circle(pos, STONE_RADIUS, color);
+    }
+
+    /// 石 (凡例では小さい石) を描く
+    fn draw_circle(&mut self, pos: (i32, i32), radius: i32, color: Rgb<u8>) {
+        draw_filled_circle_mut(&mut self.img, pos, radius, color);
+
+        if let Some(outline) = self.palette.outline {
+            draw_hollow_circle_mut(&mut self.img, pos, radius, outline);
+        }
+    }
+
+    fn mark_hint(&mut self, row: usize, column: usize) {
+        let pos = Self::get_pos(row, column);
+        draw_hollow_circle_mut(&mut self.img, pos, HINT_RADIUS, self.palette.line);
+    }
+
+    /// 石の上の確率を隠さないように、石の外側に輪を描く
+    fn mark_last_move(&mut self, row: usize, column: usize) {
+        let pos = Self::get_pos(row, column);
+
+        for radius in STONE_RADIUS + 1..=STONE_RADIUS + 3 {
+            draw_hollow_circle_mut(&mut self.img, pos, radius, self.palette.highlight);
+        }
+    }
+
+    /// 観測した石のうち、なりやすい方と違う色になった石 (返らなかった・勝手に返った石) に印を付ける
+    fn mark_flipped(&mut self, board: &[[Disc; SIZE]; SIZE], observed_board: &ObservedBoard) {
+        for (row, rows) in board.iter().enumerate() {
+            for (column, disc) in rows.iter().enumerate() {
+                if *disc == Disc::None || disc.majority() == observed_board[row][column] {
+                    continue;
+                }
+
+                self.draw_flipped_mark(Self::get_pos(row, column), STONE_RADIUS);
+            }
+        }
+    }
+
+    fn draw_flipped_mark(&mut self, pos: (i32, i32), stone_radius: i32) {
+        for radius in stone_radius * 3 / 4 - 2..=stone_radius * 3 / 4 + 2 {
+            draw_hollow_circle_mut(&mut self.img, pos, radius, FLIPPED_COLOR);
+        }
+    }
+
+    /// x = offset_x から、量子の石の色と確率の凡例を描く
+    fn draw_legend(&mut self, offset_x: i32, center_y: i32) {
+        draw_quantum_legend(
+            &mut self.img,
+            &self.palette,
+            self.patterns,
+            (offset_x, center_y),
+        );
+    }
+}
+
+/// 盤面の画像 (同じ盤面ならキャッシュから返す)
+///
+/// `hints` は手番の人が置けるマス。
+pub async fn gen_board_image(
+    board: [[Disc; SIZE]; SIZE],
+    last_move: Option<(usize, usize)>,
+    hints: Vec<(usize, usize)>,
+    style: BoardStyle,
+) -> Vec<u8> {
+    let key = (
+        "quantum_reversi_board",
+        board,
+        last_move,
+        hints.clone(),
+        style,
+        output(),
+    );
+
+    render(key, move || {
+        draw_board_image(board, last_move, &hints, style)
+    })
+    .await
+}
+
+/// 盤面の画像をこのスレッドで描く (重いので、非同期の処理からは gen_board_image を使う)
+pub fn draw_board_image(
+    board: [[Disc; SIZE]; SIZE],
+    last_move: Option<(usize, usize)>,
+    hints: &[(usize, usize)],
+    style: BoardStyle,
+) -> Vec<u8> {
+    let mut img = GenImage::new(style);
+    draw_discs(&mut img, &board, last_move);
+
+    for &(row, column) in hints {
+        img.mark_hint(row, column);
+    }
+
+    // 盤面の下に凡例を付け足す
+    let mut full =
+        ImageBuffer::from_pixel(IMAGE_SIZE, IMAGE_SIZE + LEGEND_HEIGHT, img.palette.board);
+    image::imageops::replace(&mut full, &img.img, 0, 0);
+    img.img = full;
+    img.draw_legend(0, (IMAGE_SIZE + LEGEND_HEIGHT / 2) as i32);
+
+    return encode(&img.img, output());
+}
+
+fn draw_discs(img: &mut GenImage, board: &[[Disc; SIZE]; SIZE], last_move: Option<(usize, usize)>) {
+    for (row, rows) in board.iter().enumerate() {
+        for (column, disc) in rows.iter().enumerate() {
+            img.push_disc(row, column, *disc);
+        }
+    }
+
+    if let Some((row, column)) = last_move {
+        img.mark_last_move(row, column);
+    }
+}
+
+/// 観測する前の盤面 (左) と観測した結果 (右) を並べた画像 (同じ盤面ならキャッシュから返す)
+///
+/// 観測した結果では、なりやすい方と違う色になった石に印を付け、見出しに石の数を書く。
+pub async fn gen_observation_image(
+    board: [[Disc; SIZE]; SIZE],
+    last_move: Option<(usize, usize)>,
+    observed_board: ObservedBoard,
+    style: BoardStyle,
+) -> Vec<u8> {
+    let key = (
+        "quantum_reversi_observation",
+        board,
+        last_move,
+        observed_board,
+        style,
+        output(),
+    );
+
+    render(key, move || {
+        draw_observation_image(board, last_move, observed_board, style)
+    })
+    .await
+}
+
+pub fn draw_observation_image(
+    board: [[Disc; SIZE]; SIZE],
+    last_move: Option<(usize, usize)>,
+    observed_board: ObservedBoard,
+    style: BoardStyle,
+) -> Vec<u8> {
+    let mut quantum = GenImage::new(style);
+    draw_discs(&mut quantum, &board, last_move);
+
+    let mut observed = GenImage::new(style);
+    for (row, rows) in observed_board.iter().enumerate() {
+        for (column, stone) in rows.iter().enumerate() {
+            if *stone != ObservedStone::None {
+                observed.push_disc(row, column, Disc::Observed(*stone));
+            }
+        }
+    }
+    observed.mark_flipped(&board, &observed_board);
+
+    let palette = quantum.palette;
+    let mut img = GenImage {
+        img: ImageBuffer::from_pixel(
+            IMAGE_SIZE * 2,
+            TITLE_HEIGHT + IMAGE_SIZE + LEGEND_HEIGHT,
+            palette.board,
+        ),
+        palette,
+        patterns: quantum.patterns,
+    };
+    image::imageops::replace(&mut img.img, &quantum.img, 0, TITLE_HEIGHT as i64);
+    image::imageops::replace(
+        &mut img.img,
+        &observed.img,
+        IMAGE_SIZE as i64,
+        TITLE_HEIGHT as i64,
+    );
+
+    // 2枚の盤面の仕切り
+    let rect = Rect::at(IMAGE_SIZE as i32 - 1, 0).of_size(2, img.img.height());
+    draw_filled_rect_mut(&mut img.img, rect, palette.line);
+
+    let count = |color| {
+        observed_board
+            .iter()
+            .flatten()
+            .filter(|stone| **stone == color)
+            .count()
+    };
+    draw_label(&mut img.img, palette.line, MARGIN, 8, "SUPERPOSED");
+    draw_label(
+        &mut img.img,
+        palette.line,
+        IMAGE_SIZE as i32 + MARGIN,
+        8,
+        &format!(
+            "OBSERVED  B {}  W {}",
+            count(ObservedStone::Black),
+            count(ObservedStone::White)
+        ),
+    );
+
+    let center_y = (TITLE_HEIGHT + IMAGE_SIZE + LEGEND_HEIGHT / 2) as i32;
+    img.draw_legend(0, center_y);
+
+    let left = IMAGE_SIZE as i32 + 20;
+    let radius = STONE_RADIUS * 2 / 3;
+    img.draw_circle((left + radius, center_y), radius, palette.black);
+    img.draw_flipped_mark((left + radius, center_y), radius);
+    draw_small_text(
+        &mut img.img,
+        palette.line,
+        (left + radius * 2 + 10, center_y - SMALL_HEIGHT / 2),
+        "FLIPPED FROM LIKELY COLOR",
+    );
+
+    return encode(&img.img, output());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quantum_reversi::reversi::QuantumReversi;
+    use crate::render::theme::Theme;
+
+    #[tokio::test]
+    async fn board_with_hints() {
+        let mut reversi = QuantumReversi::new();
+        reversi.do_place(2, 3).unwrap();
+
+        let hints = vec![(2, 2)];
+        let png = gen_board_image(
+            reversi.board,
+            reversi.last_move,
+            hints,
+            BoardStyle::default(),
+        )
+        .await;
+        let img = image::load_from_memory(&png).unwrap().to_rgb8();
+        assert_eq!((IMAGE_SIZE, IMAGE_SIZE + LEGEND_HEIGHT), img.dimensions());
+
+        let palette = Theme::default().palette();
+        let pixel = |row, column, dx: i32| {
+            let (x, y) = GenImage::get_pos(row, column);
+            *img.get_pixel((x + dx) as u32, y as u32)
+        };
+
+        // 挟んだ石は 90%黒 の量子の石、初めの石は色の決まった石
+        assert_eq!(palette.black90, pixel(3, 3, 20));
+        assert_eq!(palette.white, pixel(4, 4, 20));
+        // 最後に置いた石の印と、置けるマスの点
+        assert_eq!(palette.highlight, pixel(2, 3, STONE_RADIUS + 2));
+        assert_eq!(palette.line, pixel(2, 2, HINT_RADIUS));
+        assert_eq!(palette.board, pixel(2, 2, 20));
+    }
+
+    #[tokio::test]
+    async fn observation_side_by_side() {
+        let mut board = [[Disc::None; SIZE]; SIZE];
+        board[3][3] = Disc::Quantum(Stone::Black90);
+        board[3][4] = Disc::Observed(ObservedStone::Black);
+        let mut observed_board = [[ObservedStone::None; SIZE]; SIZE];
+        // 90% で黒になるはずの石が白になった (返らなかった)
+        observed_board[3][3] = ObservedStone::White;
+        observed_board[3][4] = ObservedStone::Black;
+
+        let png =
+            gen_observation_image(board, Some((3, 3)), observed_board, BoardStyle::default()).await;
+        let img = image::load_from_memory(&png).unwrap().to_rgb8();
+
+        assert_eq!(
+            (IMAGE_SIZE * 2, TITLE_HEIGHT + IMAGE_SIZE + LEGEND_HEIGHT),
+            img.dimensions()
+        );
+
+        let palette = Theme::default().palette();
+        let right_pixel = |row, column, dx: i32| {
+            let (x, y) = GenImage::get_pos(row, column);
+            let x = IMAGE_SIZE as i32 + x + dx;
+            *img.get_pixel(x as u32, (TITLE_HEIGHT as i32 + y) as u32)
+        };
+        assert_eq!(FLIPPED_COLOR, right_pixel(3, 3, STONE_RADIUS - 8));
+        assert_eq!(palette.white, right_pixel(3, 3, STONE_RADIUS - 2));
+        assert_eq!(palette.black, right_pixel(3, 4, STONE_RADIUS - 8));
+
+        // 左は観測する前の量子の石
+        let (x, y) = GenImage::get_pos(3, 3);
+        assert_eq!(
+            palette.black90,
+            *img.get_pixel((x - 20) as u32, (TITLE_HEIGHT as i32 + y) as u32)
+        );
+    }
+}
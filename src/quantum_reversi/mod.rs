@@ -0,0 +1,2 @@
+pub mod gen_image;
+pub mod reversi;
@@ -0,0 +1,348 @@
+/*
+量子リバーシです (量子五目並べの石をリバーシで使います)。
+
+置く石は量子五目並べと同じく 90%黒 → 90%白 → 70%黒 → 70%白 の順に回ります。
+置けるマスと挟める石は、それぞれの石をなりやすい方の色とみなして決めます。
+挟んだ石は置いた石と同じ確率の石になり、本当に返ったかどうかは観測するまで決まりません。
+観測すると盤面のすべての石の色が決まり、そのあとは色の決まった石として扱います (また挟まれると量子の石に戻る)。
+どちらも置けなくなったら観測し、それでもどちらも置けなければ石が多いほうの勝ちです。
+*/
+
+use rand::Rng;
+
+use crate::quantum_gomoku::gomoku::{ObservedStone, Stone, Turn};
+
+pub const SIZE: usize = 8;
+pub const ALPHABETS: &str = "ABCDEFGH";
+
+const DIRECTIONS: [(i32, i32); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+pub type ObservedBoard = [[ObservedStone; SIZE]; SIZE];
+
+/// 盤面のマスの石
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Disc {
+    None,
+    /// 観測するまで色の決まらない石 (置いた石・挟まれた石)
+    Quantum(Stone),
+    /// 観測して色の決まった石 (初めの4つも)
+    Observed(ObservedStone),
+}
+
+impl Disc {
+    /// 観測したときに黒になる確率
+    pub fn black_possibility(&self) -> f64 {
+        match self {
+            Disc::Quantum(stone) => stone.black_possibility(),
+            Disc::Observed(ObservedStone::Black) => 1.0,
+            Disc::Observed(ObservedStone::White) => 0.0,
+            Disc::None | Disc::Observed(ObservedStone::None) => {
+                panic!("'None' never comes in here.")
+            }
+        }
+    }
+
+    /// なりやすい方の色 (置けるマス・挟める石はこの色で決める)
+    pub fn majority(&self) -> ObservedStone {
+        match self {
+            Disc::None => ObservedStone::None,
+            Disc::Quantum(stone) => stone.majority(),
+            Disc::Observed(stone) => *stone,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlaceError {
+    /// 盤面の外
+    OutOfRange,
+    /// すでに石がある
+    Occupied,
+    /// 相手の石 (なりやすい方の色) を挟めない
+    NoFlip,
+}
+
+#[derive(Clone, Debug)]
+pub struct QuantumReversi {
+    pub board: [[Disc; SIZE]; SIZE],
+    pub current_turn: Turn,
+    pub last_move: Option<(usize, usize)>,
+}
+
+impl Default for QuantumReversi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl QuantumReversi {
+    pub fn new() -> Self {
+        let mut board = [[Disc::None; SIZE]; SIZE];
+
+        board[3][3] = Disc::Observed(ObservedStone::White);
+        board[4][4] = Disc::Observed(ObservedStone::White);
+
+        board[3][4] = Disc::Observed(ObservedStone::Black);
+        board[4][3] = Disc::Observed(ObservedStone::Black);
+
+        Self {
+            board,
+            current_turn: Turn::Black90,
+            last_move: None,
+        }
+    }
+
+    /// 量子五目並べと同じ順に手番の石を回す
+    pub fn switch_turn(&mut self) {
+        self.current_turn = self.current_turn.next();
+    }
+
+    /// 手番の人の色
+    pub fn turn_color(&self) -> ObservedStone {
+        self.current_turn.majority()
+    }
+
+    /// color が (row, column) に置いたときに挟む石 (置けなければ空)
+    pub fn flips(&self, row: usize, column: usize, color: ObservedStone) -> Vec<(usize, usize)> {
+        if !(row < SIZE && column < SIZE) || self.board[row][column] != Disc::None {
+            return vec![];
+        }
+
+        let mut flips = vec![];
+
+        for (move_row, move_column) in DIRECTIONS {
+            let mut line = vec![];
+            let mut cur_row = row as i32 + move_row;
+            let mut cur_column = column as i32 + move_column;
+
+            while (0..SIZE as i32).contains(&cur_row) && (0..SIZE as i32).contains(&cur_column) {
+                let square = (cur_row as usize, cur_column as usize);
+
+                match self.board[square.0][square.1].majority() {
+                    ObservedStone::None => break,
+                    stone if stone == color => {
+                        flips.append(&mut line);
+                        break;
+                    }
+                    _ => line.push(square),
+                }
+
+                cur_row += move_row;
+                cur_column += move_column;
+            }
+        }
+
+        return flips;
+    }
+
+    /// color が置けるマスの一覧 (行・列の順)
+    pub fn legal_squares(&self, color: ObservedStone) -> Vec<(usize, usize)> {
+        let mut squares = vec![];
+
+        for row in 0..SIZE {
+            for column in 0..SIZE {
+                if !self.flips(row, column, color).is_empty() {
+                    squares.push((row, column));
+                }
+            }
+        }
+
+        return squares;
+    }
+
+    pub fn can_place(&self, color: ObservedStone) -> bool {
+        !self.legal_squares(color).is_empty()
+    }
+
+    pub fn is_game_end(&self) -> bool {
+        !(self.can_place(ObservedStone::Black) || self.can_place(ObservedStone::White))
+    }
+
+    /// 手番の石を置き、挟んだ石を手番の石と同じ確率の石にする
+    pub fn do_place(&mut self, row: usize, column: usize) -> Result<(), PlaceError> {
+        if !(row < SIZE && column < SIZE) {
+            return Err(PlaceError::OutOfRange);
+        }
+
+        if self.board[row][column] != Disc::None {
+            return Err(PlaceError::Occupied);
+        }
+
+        let flips = self.flips(row, column, self.turn_color());
+        if flips.is_empty() {
+            return Err(PlaceError::NoFlip);
+        }
+
+        let disc = Disc::Quantum(self.current_turn.stone());
+        for (flip_row, flip_column) in flips {
+            self.board[flip_row][flip_column] = disc;
+        }
+
+        self.board[row][column] = disc;
+        self.last_move = Some((row, column));
+        return Ok(());
+    }
+
+    /// 観測していない石があるか
+    pub fn has_quantum(&self) -> bool {
+        self.board
+            .iter()
+            .flatten()
+            .any(|disc| matches!(disc, Disc::Quantum(_)))
+    }
+
+    /// 盤面を観測してすべての石の色を決め、観測した盤面を返す
+    pub fn observe(&mut self, rng: &mut impl Rng) -> ObservedBoard {
+        let observed_board = self.create_observed_board(rng);
+        self.collapse(&observed_board);
+
+        return observed_board;
+    }
+
+    fn create_observed_board(&self, rng: &mut impl Rng) -> ObservedBoard {
+        let mut observed = [[ObservedStone::None; SIZE]; SIZE];
+
+        for (irow, rows) in self.board.iter().enumerate() {
+            for (icolumn, disc) in rows.iter().enumerate() {
+                if *disc == Disc::None {
+                    continue;
+                }
+
+                let randfloat: f64 = rng.gen();
+
+                if randfloat < disc.black_possibility() {
+                    observed[irow][icolumn] = ObservedStone::Black
+                } else {
+                    observed[irow][icolumn] = ObservedStone::White
+                }
+            }
+        }
+
+        return observed;
+    }
+
+    /// 観測した色で盤面の石を色の決まった石にする
+    fn collapse(&mut self, observed_board: &ObservedBoard) {
+        for (rows, observed_rows) in self.board.iter_mut().zip(observed_board) {
+            for (disc, observed) in rows.iter_mut().zip(observed_rows) {
+                if *disc != Disc::None {
+                    *disc = Disc::Observed(*observed);
+                }
+            }
+        }
+    }
+
+    /// なりやすい方の色が color の石の数 (すべて観測したあとなら実際の数)
+    pub fn count(&self, color: ObservedStone) -> usize {
+        self.board
+            .iter()
+            .flatten()
+            .filter(|disc| **disc != Disc::None && disc.majority() == color)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    #[test]
+    fn turn_change() {
+        let mut reversi = QuantumReversi::new();
+        assert_eq!(ObservedStone::Black, reversi.turn_color());
+
+        for expected in [Turn::White90, Turn::Black70, Turn::White70, Turn::Black90] {
+            reversi.switch_turn();
+            assert_eq!(expected, reversi.current_turn);
+        }
+    }
+
+    #[test]
+    fn place_makes_quantum_flips() {
+        let mut reversi = QuantumReversi::new();
+
+        assert_eq!(
+            vec![(2, 3), (3, 2), (4, 5), (5, 4)],
+            reversi.legal_squares(ObservedStone::Black)
+        );
+        assert_eq!(Err(PlaceError::OutOfRange), reversi.do_place(8, 0));
+        assert_eq!(Err(PlaceError::Occupied), reversi.do_place(3, 3));
+        assert_eq!(Err(PlaceError::NoFlip), reversi.do_place(0, 0));
+
+        // 挟んだ白の石は、置いた石と同じ 90%黒 の石になる (まだ返ったとは決まらない)
+        assert_eq!(Ok(()), reversi.do_place(2, 3));
+        assert_eq!(Disc::Quantum(Stone::Black90), reversi.board[2][3]);
+        assert_eq!(Disc::Quantum(Stone::Black90), reversi.board[3][3]);
+        assert_eq!(Disc::Observed(ObservedStone::White), reversi.board[4][4]);
+        assert_eq!(Some((2, 3)), reversi.last_move);
+        assert!(reversi.has_quantum());
+        assert_eq!(4, reversi.count(ObservedStone::Black));
+        assert_eq!(1, reversi.count(ObservedStone::White));
+
+        // 量子の石もなりやすい方の色で挟まれ、挟んだ人の石になる
+        reversi.switch_turn();
+        assert_eq!(vec![(3, 3)], reversi.flips(2, 2, ObservedStone::White));
+        assert_eq!(Ok(()), reversi.do_place(2, 2));
+        assert_eq!(Disc::Quantum(Stone::White90), reversi.board[3][3]);
+    }
+
+    #[test]
+    fn observation_fixes_colors() {
+        let mut reversi = QuantumReversi::new();
+        reversi.do_place(2, 3).unwrap();
+
+        let mut observed = [[ObservedStone::None; SIZE]; SIZE];
+        observed[2][3] = ObservedStone::Black;
+        // 10% の方が出て、挟んだ石は返らなかった
+        observed[3][3] = ObservedStone::White;
+        observed[3][4] = ObservedStone::Black;
+        observed[4][3] = ObservedStone::Black;
+        observed[4][4] = ObservedStone::White;
+        reversi.collapse(&observed);
+
+        assert!(!reversi.has_quantum());
+        assert_eq!(Disc::Observed(ObservedStone::White), reversi.board[3][3]);
+        assert_eq!(Disc::None, reversi.board[0][0]);
+        assert_eq!(3, reversi.count(ObservedStone::Black));
+        assert_eq!(2, reversi.count(ObservedStone::White));
+
+        // 色の決まった石は観測しても変わらない
+        let before = reversi.board;
+        assert_eq!(observed, reversi.observe(&mut rand::thread_rng()));
+        assert_eq!(before, reversi.board);
+    }
+
+    #[test]
+    fn observation_follows_possibility() {
+        let mut reversi = QuantumReversi::new();
+        reversi.board[0][0] = Disc::Quantum(Stone::Black70);
+        reversi.board[0][1] = Disc::Quantum(Stone::White90);
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let (mut black70, mut white90) = (0, 0);
+        for _ in 0..10000 {
+            let observed = reversi.create_observed_board(&mut rng);
+            black70 += (observed[0][0] == ObservedStone::Black) as u32;
+            white90 += (observed[0][1] == ObservedStone::Black) as u32;
+
+            // 色の決まった石と空いているマスは変わらない
+            assert_eq!(ObservedStone::White, observed[3][3]);
+            assert_eq!(ObservedStone::None, observed[7][7]);
+        }
+
+        assert!((6700..7300).contains(&black70), "{}", black70);
+        assert!((800..1200).contains(&white90), "{}", white90);
+    }
+}
@@ -0,0 +1,275 @@
+/*
+盤面の画像をファイルにする方法です。
+
+`config.json` の `image` で形式 (PNG・減色した PNG・WebP・JPEG) と幅を選び、起動時に `set_output` で設定します。
+盤面は 850px の幅で描き、小さくするときは最後に同じ割合で縮めます (盤面を2枚並べた画像は 1700px から縮めます)。
+*/
+
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::sync::OnceLock;
+
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::imageops::{self, FilterType};
+use image::{ExtendedColorType, ImageFormat, Rgb, RgbImage};
+use serde::{Deserialize, Serialize};
+
+/// 描くときの盤面1枚の幅 (これより大きくはしない)
+pub const FULL_WIDTH: u32 = 850;
+pub const MIN_WIDTH: u32 = 200;
+
+const JPEG_QUALITY: u8 = 85;
+/// 減色した PNG の色の数
+const PALETTE_SIZE: usize = 256;
+
+static OUTPUT: OnceLock<ImageOutput> = OnceLock::new();
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputFormat {
+    #[default]
+    Png,
+    /// 256色までに減らした PNG (盤面の画像はほぼ見た目が変わらず、小さくなる)
+    PalettePng,
+    /// 可逆圧縮の WebP
+    Webp,
+    Jpeg,
+}
+
+impl OutputFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            OutputFormat::Png | OutputFormat::PalettePng => "png",
+            OutputFormat::Webp => "webp",
+            OutputFormat::Jpeg => "jpg",
+        }
+    }
+}
+
+/// 画像の形式と大きさ (`config.json` の `image`)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct ImageOutput {
+    pub format: OutputFormat,
+    /// 盤面1枚の幅 (px、画像全体を同じ割合で縮める)
+    pub width: u32,
+}
+
+impl Default for ImageOutput {
+    fn default() -> Self {
+        ImageOutput {
+            format: OutputFormat::Png,
+            width: FULL_WIDTH,
+        }
+    }
+}
+
+/// 起動時に一度だけ設定する (設定済みなら Err で返す)
+pub fn set_output(output: ImageOutput) -> Result<(), ImageOutput> {
+    OUTPUT.set(output)
+}
+
+/// 設定した形式 (設定していなければ 850px の PNG)
+pub fn output() -> ImageOutput {
+    OUTPUT.get().copied().unwrap_or_default()
+}
+
+/// 添付するファイルの名前 ("board" -> "board.webp" など)
+pub fn file_name(stem: &str) -> String {
+    format!("{}.{}", stem, output().format.extension())
+}
+
+/// 描いた画像を output の幅の割合に縮め、output の形式にする
+pub fn encode(img: &RgbImage, output: ImageOutput) -> Vec<u8> {
+    let resized;
+    let img = if output.width < FULL_WIDTH {
+        let scale = |length: u32| (length * output.width + FULL_WIDTH / 2) / FULL_WIDTH;
+        resized = imageops::resize(
+            img,
+            scale(img.width()),
+            scale(img.height()),
+            FilterType::Triangle,
+        );
+        &resized
+    } else {
+        img
+    };
+
+    let mut bytes: Vec<u8> = Vec::new();
+
+    match output.format {
+        OutputFormat::Png => img
+            .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+            .unwrap(),
+        OutputFormat::PalettePng => write_palette_png(img, &mut bytes),
+        OutputFormat::Webp => WebPEncoder::new_lossless(&mut bytes)
+            .encode(
+                img.as_raw(),
+                img.width(),
+                img.height(),
+                ExtendedColorType::Rgb8,
+            )
+            .unwrap(),
+        OutputFormat::Jpeg => JpegEncoder::new_with_quality(&mut bytes, JPEG_QUALITY)
+            .encode_image(img)
+            .unwrap(),
+    }
+
+    return bytes;
+}
+
+fn write_palette_png(img: &RgbImage, bytes: &mut Vec<u8>) {
+    let (palette, indices) = quantize(img);
+
+    let mut encoder = png::Encoder::new(bytes, img.width(), img.height());
+    encoder.set_color(png::ColorType::Indexed);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_palette(palette.iter().flat_map(|color| color.0).collect::<Vec<_>>());
+    encoder.set_compression(png::Compression::Best);
+
+    let mut writer = encoder.write_header().unwrap();
+    writer.write_image_data(&indices).unwrap();
+}
+
+/// よく使われている色から 256 色を選び、画素ごとに一番近い色の番号にする
+///
+/// 盤面の画像は盤・線・石の色がほとんどで、256色を超えるのは文字や縮めたときのふちの色だけ。
+fn quantize(img: &RgbImage) -> (Vec<Rgb<u8>>, Vec<u8>) {
+    let mut counts: HashMap<Rgb<u8>, usize> = HashMap::new();
+    for pixel in img.pixels() {
+        *counts.entry(*pixel).or_default() += 1;
+    }
+
+    let mut colors: Vec<(Rgb<u8>, usize)> = counts.into_iter().collect();
+    // 同じ数なら色の値の順にして、毎回同じ画像にする
+    colors.sort_by(|a, b| b.1.cmp(&a.1).then(a.0 .0.cmp(&b.0 .0)));
+    let palette: Vec<Rgb<u8>> = colors
+        .into_iter()
+        .take(PALETTE_SIZE)
+        .map(|(color, _)| color)
+        .collect();
+
+    let mut nearest: HashMap<Rgb<u8>, u8> = HashMap::new();
+    let indices = img
+        .pixels()
+        .map(|pixel| {
+            *nearest.entry(*pixel).or_insert_with(|| {
+                (0..palette.len())
+                    .min_by_key(|&i| distance(palette[i], *pixel))
+                    .unwrap() as u8
+            })
+        })
+        .collect();
+
+    return (palette, indices);
+}
+
+fn distance(a: Rgb<u8>, b: Rgb<u8>) -> u32 {
+    (0..3)
+        .map(|i| (a.0[i] as i32 - b.0[i] as i32).pow(2) as u32)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quantum_gomoku::gen_image::draw_quantum_board_image;
+    use crate::quantum_gomoku::gomoku::Stone;
+    use crate::render::theme::BoardStyle;
+
+    /// 凡例の付いた 850x930 の盤面
+    fn board_image() -> RgbImage {
+        let mut board = [[Stone::None; 19]; 19];
+        board[7][7] = Stone::Black90;
+        board[7][8] = Stone::White70;
+
+        let png = draw_quantum_board_image(board, Some((7, 8)), BoardStyle::default());
+        image::load_from_memory(&png).unwrap().to_rgb8()
+    }
+
+    #[test]
+    fn decodes_to_expected_dimensions() {
+        let img = board_image();
+        assert_eq!((850, 930), img.dimensions());
+
+        let formats = [
+            (OutputFormat::Png, ImageFormat::Png),
+            (OutputFormat::PalettePng, ImageFormat::Png),
+            (OutputFormat::Webp, ImageFormat::WebP),
+            (OutputFormat::Jpeg, ImageFormat::Jpeg),
+        ];
+
+        for (format, image_format) in formats {
+            for (width, expected) in [(850, (850, 930)), (425, (425, 465)), (1000, (850, 930))] {
+                let bytes = encode(&img, ImageOutput { format, width });
+
+                assert_eq!(
+                    image_format,
+                    image::guess_format(&bytes).unwrap(),
+                    "{:?}",
+                    format
+                );
+                let decoded = image::load_from_memory(&bytes).unwrap();
+                assert_eq!(
+                    expected,
+                    (decoded.width(), decoded.height()),
+                    "{:?}",
+                    format
+                );
+            }
+        }
+
+        // 盤面を2枚並べた画像も、盤面1枚の幅と同じ割合で縮める
+        let wide = RgbImage::new(1700, 980);
+        let bytes = encode(
+            &wide,
+            ImageOutput {
+                format: OutputFormat::Png,
+                width: 425,
+            },
+        );
+        let decoded = image::load_from_memory(&bytes).unwrap();
+        assert_eq!((850, 490), (decoded.width(), decoded.height()));
+    }
+
+    #[test]
+    fn palette_png() {
+        let img = board_image();
+
+        let png = encode(&img, ImageOutput::default());
+        let palette_png = encode(
+            &img,
+            ImageOutput {
+                format: OutputFormat::PalettePng,
+                width: FULL_WIDTH,
+            },
+        );
+        assert!(palette_png.len() < png.len());
+
+        // 盤と石の色はそのまま残る
+        let decoded = image::load_from_memory(&palette_png).unwrap().to_rgb8();
+        for (x, y) in [(1, 1), (425, 425), (30 + 24, 890)] {
+            assert_eq!(img.get_pixel(x, y), decoded.get_pixel(x, y));
+        }
+    }
+
+    #[test]
+    fn quantize_to_nearest() {
+        // 黒の背景に (1, 0, 0) - (255, 0, 0) と (0, 200, 0) の 257 色
+        let mut img = RgbImage::from_pixel(20, 20, Rgb([0, 0, 0]));
+        for i in 1..=255u32 {
+            img.put_pixel(i % 20, i / 20, Rgb([i as u8, 0, 0]));
+        }
+        img.put_pixel(16, 12, Rgb([0, 200, 0]));
+
+        let (palette, indices) = quantize(&img);
+        let color_at = |x: usize, y: usize| palette[indices[y * 20 + x] as usize];
+
+        assert_eq!(PALETTE_SIZE, palette.len());
+        assert_eq!(Rgb([0, 0, 0]), color_at(0, 0));
+        assert_eq!(Rgb([0, 200, 0]), color_at(16, 12));
+        // 同じ数の色は値の小さい順に選ぶので、(255, 0, 0) は一番近い (254, 0, 0) になる
+        assert_eq!(Rgb([254, 0, 0]), color_at(255 % 20, 255 / 20));
+    }
+}
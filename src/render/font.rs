@@ -0,0 +1,207 @@
+use std::path::Path;
+use std::sync::OnceLock;
+
+use ab_glyph::{FontVec, PxScale};
+use image::{ImageBuffer, Rgb};
+use imageproc::{
+    drawing::{draw_filled_rect_mut, draw_text_mut},
+    rect::Rect,
+};
+
+/// 盤面の座標の文字の大きさ
+const SCALE: PxScale = PxScale { x: 37.5, y: 37.5 };
+
+/// フォントがないときに使う 5x7 の文字 (1行5ビット)
+#[rustfmt::skip]
+const GLYPHS: [(char, [u8; 7]); 37] = [
+    ('0', [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110]),
+    ('1', [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110]),
+    ('2', [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111]),
+    ('3', [0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110]),
+    ('4', [0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010]),
+    ('5', [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110]),
+    ('6', [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110]),
+    ('7', [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000]),
+    ('8', [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110]),
+    ('9', [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100]),
+    ('A', [0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001]),
+    ('B', [0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110]),
+    ('C', [0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110]),
+    ('D', [0b11100, 0b10010, 0b10001, 0b10001, 0b10001, 0b10010, 0b11100]),
+    ('E', [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111]),
+    ('F', [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000]),
+    ('G', [0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111]),
+    ('H', [0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001]),
+    ('I', [0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110]),
+    ('J', [0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100]),
+    ('K', [0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001]),
+    ('L', [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111]),
+    ('M', [0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001]),
+    ('N', [0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001]),
+    ('O', [0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110]),
+    ('P', [0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000]),
+    ('Q', [0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101]),
+    ('R', [0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001]),
+    ('S', [0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110]),
+    ('T', [0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100]),
+    ('U', [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110]),
+    ('V', [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100]),
+    ('W', [0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010]),
+    ('X', [0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001]),
+    ('Y', [0b10001, 0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100]),
+    ('Z', [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111]),
+    ('%', [0b11000, 0b11001, 0b00010, 0b00100, 0b01000, 0b10011, 0b00011]),
+];
+
+/// ビットマップの1ドットの大きさ (5x7 が 20x28 になり、フォントの文字と同じくらいになる)
+const DOT: u32 = 4;
+/// 石の上の小さい文字の1ドットの大きさ
+const SMALL_DOT: u32 = 2;
+/// フォントの文字の上の余白に合わせる
+const BITMAP_TOP: i32 = 6;
+
+static FONT: OnceLock<FontVec> = OnceLock::new();
+
+#[derive(Debug)]
+pub enum FontError {
+    Read(std::io::Error),
+    Invalid,
+    /// 盤面を描いたあとなどで、すでに読み込んである
+    AlreadyLoaded,
+}
+
+impl std::fmt::Display for FontError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FontError::Read(why) => write!(f, "{}", why),
+            FontError::Invalid => write!(f, "not a TrueType/OpenType font"),
+            FontError::AlreadyLoaded => write!(f, "a font is already loaded"),
+        }
+    }
+}
+
+/// 座標の文字に使うフォントを読み込む (最初の盤面を描く前に呼ぶ)
+///
+/// 読み込まなかった・読み込めなかったときは、組み込みのビットマップ文字で描く。
+pub fn load_font(path: &Path) -> Result<(), FontError> {
+    let bytes = std::fs::read(path).map_err(FontError::Read)?;
+    let font = FontVec::try_from_vec(bytes).map_err(|_| FontError::Invalid)?;
+
+    FONT.set(font).map_err(|_| FontError::AlreadyLoaded)
+}
+
+/// (x, y) を左上にして文字を描く
+pub fn draw_label(
+    img: &mut ImageBuffer<Rgb<u8>, Vec<u8>>,
+    color: Rgb<u8>,
+    x: i32,
+    y: i32,
+    text: &str,
+) {
+    match FONT.get() {
+        Some(font) => draw_text_mut(img, color, x, y, SCALE, font, text),
+        None => draw_bitmap_text(img, color, x, y + BITMAP_TOP, text, DOT),
+    }
+}
+
+/// 石の上などに、center を中心にして小さい文字を描く (フォントがあっても常にビットマップ文字)
+pub fn draw_small_label(
+    img: &mut ImageBuffer<Rgb<u8>, Vec<u8>>,
+    color: Rgb<u8>,
+    center: (i32, i32),
+    text: &str,
+) {
+    let width = (text.chars().count() as i32 * 6 - 1) * SMALL_DOT as i32;
+
+    draw_small_text(
+        img,
+        color,
+        (center.0 - width / 2, center.1 - SMALL_HEIGHT / 2),
+        text,
+    );
+}
+
+/// 小さい文字の高さ
+pub const SMALL_HEIGHT: i32 = 7 * SMALL_DOT as i32;
+
+/// (x, y) を左上にして小さい文字を描く (英数字と空白・% のみ)
+pub fn draw_small_text(
+    img: &mut ImageBuffer<Rgb<u8>, Vec<u8>>,
+    color: Rgb<u8>,
+    (x, y): (i32, i32),
+    text: &str,
+) {
+    draw_bitmap_text(img, color, x, y, text, SMALL_DOT);
+}
+
+fn draw_bitmap_text(
+    img: &mut ImageBuffer<Rgb<u8>, Vec<u8>>,
+    color: Rgb<u8>,
+    x: i32,
+    y: i32,
+    text: &str,
+    dot: u32,
+) {
+    let dot_size = dot as i32;
+    let mut left = x;
+
+    for c in text.chars() {
+        if let Some((_, rows)) = GLYPHS
+            .iter()
+            .find(|(glyph, _)| *glyph == c.to_ascii_uppercase())
+        {
+            for (row, bits) in rows.iter().enumerate() {
+                for column in 0..5 {
+                    if bits & (0b10000 >> column) == 0 {
+                        continue;
+                    }
+
+                    let rect = Rect::at(left + column * dot_size, y + row as i32 * dot_size)
+                        .of_size(dot, dot);
+                    draw_filled_rect_mut(img, rect, color);
+                }
+            }
+        }
+
+        // 1文字の幅と、文字の間の1ドット
+        left += 6 * dot_size;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bitmap_covers_coordinates() {
+        for c in "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ%".chars() {
+            assert!(GLYPHS.iter().any(|(glyph, _)| *glyph == c), "{}", c);
+        }
+    }
+
+    #[test]
+    fn bitmap_text() {
+        let white = Rgb([255, 255, 255]);
+        let black = Rgb([0, 0, 0]);
+        let mut img = ImageBuffer::from_pixel(60, 40, white);
+
+        draw_bitmap_text(&mut img, black, 0, 0, "1", DOT);
+
+        // "1" の縦線 (3列目) は塗られ、1列目は一番下の行だけ塗られない
+        assert_eq!(black, *img.get_pixel(2 * DOT + 1, 3 * DOT + 1));
+        assert_eq!(white, *img.get_pixel(1, 3 * DOT + 1));
+        assert_eq!(
+            white,
+            *img.get_pixel(6 * DOT + 1, 1),
+            "2文字目の位置には何も描かない"
+        );
+    }
+
+    #[test]
+    fn missing_font_file() {
+        assert!(matches!(
+            load_font(Path::new("no/such/font.ttf")),
+            Err(FontError::Read(_))
+        ));
+    }
+}
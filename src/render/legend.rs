@@ -0,0 +1,68 @@
+/*
+量子五目並べ・量子リバーシの画像の下に描く、量子の石の凡例です。
+*/
+
+use image::{ImageBuffer, Rgb};
+use imageproc::drawing::{draw_filled_circle_mut, draw_hollow_circle_mut};
+
+use super::font::{draw_small_label, draw_small_text, SMALL_HEIGHT};
+use super::theme::{text_color_on, Palette};
+use crate::quantum_gomoku::gomoku::Turn;
+
+/// 凡例の石の半径
+const RADIUS: i32 = 24;
+/// 凡例の1項目の幅
+const ENTRY_WIDTH: i32 = 200;
+
+const ENTRIES: [(Turn, &str, &str); 4] = [
+    (Turn::Black90, "90", "BLACK 90%"),
+    (Turn::Black70, "70", "BLACK 70%"),
+    (Turn::White70, "70", "WHITE 70%"),
+    (Turn::White90, "90", "WHITE 90%"),
+];
+
+/// x = offset_x から、量子の石の色と確率の凡例を描く
+///
+/// `labels` なら盤面の石と同じく、石に確率と 70% の石の模様を描く。
+pub fn draw_quantum_legend(
+    img: &mut ImageBuffer<Rgb<u8>, Vec<u8>>,
+    palette: &Palette,
+    labels: bool,
+    (offset_x, center_y): (i32, i32),
+) {
+    for (i, (turn, label, text)) in ENTRIES.into_iter().enumerate() {
+        let left = offset_x + 30 + i as i32 * ENTRY_WIDTH;
+        let pos = (left + RADIUS, center_y);
+        let color = legend_color(palette, turn);
+
+        draw_filled_circle_mut(img, pos, RADIUS, color);
+        if let Some(outline) = palette.outline {
+            draw_hollow_circle_mut(img, pos, RADIUS, outline);
+        }
+
+        if labels {
+            let text_color = text_color_on(color);
+
+            if matches!(turn, Turn::Black70 | Turn::White70) {
+                draw_hollow_circle_mut(img, pos, RADIUS - 5, text_color);
+            }
+            draw_small_label(img, text_color, pos, label);
+        }
+
+        draw_small_text(
+            img,
+            palette.line,
+            (left + RADIUS * 2 + 12, center_y - SMALL_HEIGHT / 2),
+            text,
+        );
+    }
+}
+
+fn legend_color(palette: &Palette, turn: Turn) -> Rgb<u8> {
+    match turn {
+        Turn::Black90 => palette.black90,
+        Turn::Black70 => palette.black70,
+        Turn::White70 => palette.white70,
+        Turn::White90 => palette.white90,
+    }
+}
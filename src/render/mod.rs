@@ -0,0 +1,5 @@
+pub mod encode;
+pub mod font;
+pub mod legend;
+pub mod render_cache;
+pub mod theme;
@@ -0,0 +1,119 @@
+/*
+盤面の画像のキャッシュです。
+
+石を描いて PNG などにするのは重いので、tokio のスレッドを止めないように spawn_blocking で描きます。
+同じ盤面を同じ描き方でもう一度描くとき (メッセージの更新やボタンの押し直しなど) は、最近描いた画像を使い回します。
+*/
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+
+/// 覚えておく画像の数
+const CAPACITY: usize = 32;
+
+static CACHE: Mutex<RenderCache> = Mutex::new(RenderCache::new(CAPACITY));
+
+/// 盤面のハッシュと画像の対応 (古いものから捨てる)
+pub struct RenderCache {
+    capacity: usize,
+    /// 後ろほど最近使ったもの
+    entries: VecDeque<(u64, Vec<u8>)>,
+}
+
+impl RenderCache {
+    pub const fn new(capacity: usize) -> Self {
+        RenderCache {
+            capacity,
+            entries: VecDeque::new(),
+        }
+    }
+
+    pub fn get(&mut self, key: u64) -> Option<Vec<u8>> {
+        let index = self.entries.iter().position(|(k, _)| *k == key)?;
+
+        let entry = self.entries.remove(index)?;
+        let bytes = entry.1.clone();
+        self.entries.push_back(entry);
+
+        return Some(bytes);
+    }
+
+    pub fn insert(&mut self, key: u64, bytes: Vec<u8>) {
+        self.entries.retain(|(k, _)| *k != key);
+
+        if self.entries.len() >= self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back((key, bytes));
+    }
+}
+
+/// key (盤面と描き方) の画像を覚えていればそれを、なければ draw で描いて覚える
+pub async fn render(key: impl Hash, draw: impl FnOnce() -> Vec<u8> + Send + 'static) -> Vec<u8> {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    let key = hasher.finish();
+
+    if let Some(bytes) = CACHE.lock().unwrap().get(key) {
+        return bytes;
+    }
+
+    let bytes = tokio::task::spawn_blocking(draw)
+        .await
+        .expect("rendering panicked");
+
+    CACHE.lock().unwrap().insert(key, bytes.clone());
+
+    return bytes;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use super::*;
+
+    #[test]
+    fn drops_least_recently_used() {
+        let mut cache = RenderCache::new(2);
+        cache.insert(1, vec![1]);
+        cache.insert(2, vec![2]);
+
+        // 1 を使ったので、次に捨てるのは 2
+        assert_eq!(Some(vec![1]), cache.get(1));
+        cache.insert(3, vec![3]);
+
+        assert_eq!(None, cache.get(2));
+        assert_eq!(Some(vec![1]), cache.get(1));
+        assert_eq!(Some(vec![3]), cache.get(3));
+
+        cache.insert(3, vec![4]);
+        assert_eq!(Some(vec![4]), cache.get(3));
+        assert_eq!(2, cache.entries.len());
+    }
+
+    #[tokio::test]
+    async fn draws_once_per_key() {
+        let draws = Arc::new(AtomicUsize::new(0));
+
+        for key in [
+            "render_cache_test_a",
+            "render_cache_test_a",
+            "render_cache_test_b",
+        ] {
+            let draws = draws.clone();
+            let bytes = render(key, move || {
+                draws.fetch_add(1, Ordering::SeqCst);
+                key.as_bytes().to_vec()
+            })
+            .await;
+
+            assert_eq!(key.as_bytes(), bytes);
+        }
+
+        assert_eq!(2, draws.load(Ordering::SeqCst));
+    }
+}
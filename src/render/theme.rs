@@ -0,0 +1,173 @@
+use image::Rgb;
+use serde::{Deserialize, Serialize};
+
+/// 盤面の画像の色合い (利用者ごとに `/settings theme` で選ぶ)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Theme {
+    /// 木の盤 (もとの色)
+    #[default]
+    Wood,
+    Dark,
+    HighContrast,
+    /// 色覚の違いがあっても見分けやすい色と、石の上の確率の表示
+    Colorblind,
+}
+
+/// 盤面の画像の描き方 (利用者ごとの設定から作る)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BoardStyle {
+    pub theme: Theme,
+    /// 量子の石に確率を描く (色覚配慮のテーマでは常に描く)
+    pub stone_labels: bool,
+}
+
+impl BoardStyle {
+    pub fn new(theme: Theme) -> Self {
+        BoardStyle {
+            theme,
+            stone_labels: false,
+        }
+    }
+
+    pub fn shows_stone_labels(&self) -> bool {
+        self.stone_labels || self.theme.palette().stone_labels
+    }
+}
+
+/// テーマごとの色
+#[derive(Clone, Copy, Debug)]
+pub struct Palette {
+    pub board: Rgb<u8>,
+    /// 線と座標の文字
+    pub line: Rgb<u8>,
+    /// 最後に置いた石と揃った列の強調表示
+    pub highlight: Rgb<u8>,
+    pub black90: Rgb<u8>,
+    pub black70: Rgb<u8>,
+    pub white70: Rgb<u8>,
+    pub white90: Rgb<u8>,
+    /// 観測したあとの石
+    pub black: Rgb<u8>,
+    pub white: Rgb<u8>,
+    /// 石のふち (盤と見分けにくい石があるテーマだけ)
+    pub outline: Option<Rgb<u8>>,
+    /// 設定にかかわらず、量子の石に確率 ("90", "70") と 70% の石の模様を描く
+    pub stone_labels: bool,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 4] = [
+        Theme::Wood,
+        Theme::Dark,
+        Theme::HighContrast,
+        Theme::Colorblind,
+    ];
+
+    /// 設定ファイル・コマンドの選択肢の値
+    pub fn id(self) -> &'static str {
+        match self {
+            Theme::Wood => "wood",
+            Theme::Dark => "dark",
+            Theme::HighContrast => "high_contrast",
+            Theme::Colorblind => "colorblind",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Theme> {
+        Theme::ALL.into_iter().find(|theme| theme.id() == id)
+    }
+
+    pub fn palette(self) -> Palette {
+        match self {
+            Theme::Wood => Palette {
+                board: Rgb([216, 179, 77]),
+                line: Rgb([0, 0, 0]),
+                highlight: Rgb([220, 40, 40]),
+                black90: Rgb([20, 20, 20]),
+                black70: Rgb([65, 65, 65]),
+                white70: Rgb([150, 150, 150]),
+                white90: Rgb([230, 230, 230]),
+                black: Rgb([20, 20, 20]),
+                white: Rgb([230, 230, 230]),
+                outline: None,
+                stone_labels: false,
+            },
+            Theme::Dark => Palette {
+                board: Rgb([40, 44, 52]),
+                line: Rgb([150, 155, 165]),
+                highlight: Rgb([255, 100, 100]),
+                black90: Rgb([10, 10, 10]),
+                black70: Rgb([75, 75, 80]),
+                white70: Rgb([165, 165, 170]),
+                white90: Rgb([240, 240, 240]),
+                black: Rgb([10, 10, 10]),
+                white: Rgb([240, 240, 240]),
+                outline: Some(Rgb([150, 155, 165])),
+                stone_labels: false,
+            },
+            Theme::HighContrast => Palette {
+                board: Rgb([255, 255, 255]),
+                line: Rgb([0, 0, 0]),
+                highlight: Rgb([230, 0, 0]),
+                black90: Rgb([0, 0, 0]),
+                black70: Rgb([85, 85, 85]),
+                white70: Rgb([190, 190, 190]),
+                white90: Rgb([255, 255, 255]),
+                black: Rgb([0, 0, 0]),
+                white: Rgb([255, 255, 255]),
+                outline: Some(Rgb([0, 0, 0])),
+                stone_labels: false,
+            },
+            // Okabe-Ito の配色 (70% の石は青とオレンジ)
+            Theme::Colorblind => Palette {
+                board: Rgb([235, 225, 200]),
+                line: Rgb([60, 60, 60]),
+                highlight: Rgb([213, 94, 0]),
+                black90: Rgb([0, 0, 0]),
+                black70: Rgb([0, 114, 178]),
+                white70: Rgb([230, 159, 0]),
+                white90: Rgb([255, 255, 255]),
+                black: Rgb([0, 0, 0]),
+                white: Rgb([255, 255, 255]),
+                outline: Some(Rgb([60, 60, 60])),
+                stone_labels: true,
+            },
+        }
+    }
+}
+
+/// 石の上の文字の色 (明るい石には黒、暗い石には白)
+pub fn text_color_on(stone: Rgb<u8>) -> Rgb<u8> {
+    let [r, g, b] = stone.0;
+    let luminance = 299 * r as u32 + 587 * g as u32 + 114 * b as u32;
+
+    if luminance > 128 * 1000 {
+        Rgb([0, 0, 0])
+    } else {
+        Rgb([255, 255, 255])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids() {
+        for theme in Theme::ALL {
+            assert_eq!(Some(theme), Theme::from_id(theme.id()));
+
+            // 設定ファイルの値とコマンドの選択肢の値は同じ
+            let json = serde_json::to_string(&theme).unwrap();
+            assert_eq!(format!("\"{}\"", theme.id()), json);
+        }
+        assert_eq!(None, Theme::from_id("pink"));
+    }
+
+    #[test]
+    fn text_color() {
+        assert_eq!(Rgb([255, 255, 255]), text_color_on(Rgb([0, 114, 178])));
+        assert_eq!(Rgb([0, 0, 0]), text_color_on(Rgb([230, 159, 0])));
+    }
+}
@@ -0,0 +1,217 @@
+/*
+決着した試合の一手ごとの解析です。
+
+試合の手順を初めから打ち直し、それぞれの局面で最善手の評価値と実際に打った手の評価値を
+同じ深さで読んで、その差 (損失) から悪手・疑問手と正確さ (%) を出します。
+*/
+
+use super::reversi::Reversi;
+use super::search::{best_move, move_score};
+use crate::color::Color;
+
+/// 損失がこれ以上なら疑問手
+pub const MISTAKE_LOSS: i32 = 6;
+/// 損失がこれ以上なら悪手
+pub const BLUNDER_LOSS: i32 = 12;
+/// 損失がこの値のとき、正確さが 1/e (約37%) になる
+const ACCURACY_SCALE: f64 = 16.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tag {
+    Mistake,
+    Blunder,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MoveAnalysis {
+    pub color: Color,
+    pub square: (i32, i32),
+    pub best_square: (i32, i32),
+    /// 打ったあとの評価値 (黒から見た値、正なら黒が有利)
+    pub black_score: i32,
+    /// 最善手との評価値の差 (0 以上)
+    pub loss: i32,
+}
+
+impl MoveAnalysis {
+    pub fn tag(&self) -> Option<Tag> {
+        if self.loss >= BLUNDER_LOSS {
+            Some(Tag::Blunder)
+        } else if self.loss >= MISTAKE_LOSS {
+            Some(Tag::Mistake)
+        } else {
+            None
+        }
+    }
+
+    /// この手の正確さ (最善手なら 100%)
+    pub fn accuracy(&self) -> f64 {
+        100.0 * (-(self.loss as f64) / ACCURACY_SCALE).exp()
+    }
+}
+
+/// 試合の手順 (パスは含まない) を同じ盤の大きさ・初期配置・ルールで初めから打ち直して一手ずつ解析する
+/// (打てない手があればそこまで)
+pub fn analyze(game: &Reversi) -> Vec<MoveAnalysis> {
+    let mut reversi = Reversi::with_layout(game.size, game.layout).with_rule(game.rule);
+    let mut analyses = vec![];
+
+    for &(row, column) in &game.moves {
+        let color = reversi.turn;
+
+        let (best_square, best_score) = match best_move(&reversi) {
+            Some(best) => best,
+            None => break,
+        };
+        let score = move_score(&reversi, row, column);
+
+        if reversi.do_place(row, column, color).is_err() {
+            break;
+        }
+
+        analyses.push(MoveAnalysis {
+            color,
+            square: (row, column),
+            best_square,
+            black_score: match color {
+                Color::Black => score,
+                Color::White => -score,
+            },
+            loss: (best_score - score).max(0),
+        });
+
+        reversi.switch_turn();
+        if !reversi.player_can_place(reversi.turn) {
+            reversi.switch_turn();
+        }
+    }
+
+    return analyses;
+}
+
+/// color の手の正確さの平均 (一手も打っていなければ None)
+pub fn accuracy(analyses: &[MoveAnalysis], color: Color) -> Option<f64> {
+    let accuracies: Vec<f64> = analyses
+        .iter()
+        .filter(|analysis| analysis.color == color)
+        .map(|analysis| analysis.accuracy())
+        .collect();
+
+    if accuracies.is_empty() {
+        return None;
+    }
+
+    return Some(accuracies.iter().sum::<f64>() / accuracies.len() as f64);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reversi::opening::parse_moves;
+    use crate::reversi::reversi::{Layout, Rule, Stone};
+
+    /// 一番左上のマスに置き続けた試合
+    fn first_square_game(size: usize, layout: Layout, rule: Rule) -> Reversi {
+        let mut reversi = Reversi::with_layout(size, layout).with_rule(rule);
+
+        while !reversi.is_game_end() {
+            let color = reversi.turn;
+            if let Some(&(row, column)) = reversi.legal_squares(color).first() {
+                reversi.do_place(row, column, color).unwrap();
+            }
+            reversi.switch_turn();
+        }
+
+        return reversi;
+    }
+
+    #[test]
+    fn whole_game() {
+        let game = first_square_game(8, Layout::Cross, Rule::Standard);
+        let analyses = analyze(&game);
+
+        assert_eq!(game.moves.len(), analyses.len());
+        assert_eq!(Color::Black, analyses[0].color);
+        assert!(analyses.iter().all(|analysis| analysis.loss >= 0));
+
+        // 最後の手は最後まで読むので、評価値は実際の石差になる
+        let difference =
+            game.count_stones(Stone::BLACK) as i32 - game.count_stones(Stone::WHITE) as i32;
+        assert_eq!(difference, analyses.last().unwrap().black_score);
+
+        // 何も考えずに打つと悪手もある
+        assert!(analyses
+            .iter()
+            .any(|analysis| analysis.tag() == Some(Tag::Blunder)));
+
+        for color in [Color::Black, Color::White] {
+            let accuracy = accuracy(&analyses, color).unwrap();
+            assert!((0.0..100.0).contains(&accuracy), "{}", accuracy);
+        }
+    }
+
+    #[test]
+    fn best_move_has_no_loss() {
+        let moves = parse_moves("F5 F6 E6").unwrap();
+
+        let mut reversi = Reversi::new();
+        for &(row, column) in &moves {
+            reversi.do_place(row, column, reversi.turn).unwrap();
+            reversi.switch_turn();
+        }
+        let (best_square, _) = best_move(&reversi).unwrap();
+        reversi
+            .do_place(best_square.0, best_square.1, reversi.turn)
+            .unwrap();
+
+        let analyses = analyze(&reversi);
+        assert_eq!(best_square, analyses[3].best_square);
+        assert_eq!(0, analyses[3].loss);
+        assert_eq!(None, analyses[3].tag());
+        assert_eq!(100.0, analyses[3].accuracy());
+    }
+
+    #[test]
+    fn other_sizes() {
+        for (size, layout) in [(6, Layout::Cross), (10, Layout::Parallel)] {
+            let game = first_square_game(size, layout, Rule::Standard);
+            let analyses = analyze(&game);
+
+            assert_eq!(game.moves.len(), analyses.len());
+
+            let difference =
+                game.count_stones(Stone::BLACK) as i32 - game.count_stones(Stone::WHITE) as i32;
+            assert_eq!(difference, analyses.last().unwrap().black_score);
+        }
+    }
+
+    #[test]
+    fn anti_rule() {
+        // アンチリバーシでは、最後の評価値は石が少ないほうの有利になる
+        let game = first_square_game(6, Layout::Cross, Rule::Anti);
+        let analyses = analyze(&game);
+
+        let difference =
+            game.count_stones(Stone::WHITE) as i32 - game.count_stones(Stone::BLACK) as i32;
+        assert_eq!(difference, analyses.last().unwrap().black_score);
+    }
+
+    #[test]
+    fn tags() {
+        let analysis = |loss| MoveAnalysis {
+            color: Color::Black,
+            square: (0, 0),
+            best_square: (0, 0),
+            black_score: 0,
+            loss,
+        };
+
+        assert_eq!(None, analysis(MISTAKE_LOSS - 1).tag());
+        assert_eq!(Some(Tag::Mistake), analysis(MISTAKE_LOSS).tag());
+        assert_eq!(Some(Tag::Blunder), analysis(BLUNDER_LOSS).tag());
+
+        let accuracy = accuracy(&[analysis(0), analysis(16)], Color::Black).unwrap();
+        assert!((accuracy - (100.0 + 100.0 / std::f64::consts::E) / 2.0).abs() < 1e-9);
+        assert_eq!(None, super::accuracy(&[analysis(0)], Color::White));
+    }
+}
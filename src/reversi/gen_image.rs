@@ -0,0 +1,192 @@
+/*
+リバーシの解析のグラフの画像です。
+
+一手ごとに、打ったあとの評価値 (黒から見た値) を棒で描きます。
+0 より上 (黒が有利) は黒の石の色、下 (白が有利) は白の石の色で、悪手には塗った丸、疑問手には輪を付けます。
+色は盤面の画像と同じく、見る人のテーマの色を使います。
+*/
+
+use image::{ImageBuffer, Rgb};
+use imageproc::drawing::{draw_filled_circle_mut, draw_filled_rect_mut, draw_hollow_circle_mut};
+use imageproc::rect::Rect;
+
+use super::analysis::{MoveAnalysis, Tag};
+use crate::render::encode::{encode, output, FULL_WIDTH};
+use crate::render::font::{draw_small_text, SMALL_HEIGHT};
+use crate::render::theme::{BoardStyle, Palette};
+
+const HEIGHT: u32 = 400;
+const LEFT: i32 = 90;
+const RIGHT: i32 = 20;
+const TOP: i32 = 30;
+const BOTTOM: i32 = 40;
+/// 縦軸の最小の幅 (序盤の小さい差でも棒が潰れないようにする)
+const MIN_RANGE: i32 = 16;
+const MARK_RADIUS: i32 = 5;
+
+/// 解析のグラフの画像を描く (重いので、非同期の処理からは spawn_blocking などで呼ぶ)
+pub fn draw_analysis_graph(analyses: &[MoveAnalysis], style: BoardStyle) -> Vec<u8> {
+    let palette = style.theme.palette();
+    let mut img = ImageBuffer::from_pixel(FULL_WIDTH, HEIGHT, palette.board);
+
+    let range = score_range(analyses);
+    let plot_width = FULL_WIDTH as i32 - LEFT - RIGHT;
+    let plot_height = HEIGHT as i32 - TOP - BOTTOM;
+    let zero_y = TOP + plot_height / 2;
+    let y_of = |score: i32| zero_y - score.clamp(-range, range) * (plot_height / 2) / range;
+
+    // 一手の幅 (60手で盤面の画像と同じくらいの幅になる)
+    let count = analyses.len().max(1) as i32;
+    let step = plot_width / count;
+
+    for (index, analysis) in analyses.iter().enumerate() {
+        let left = LEFT + index as i32 * step;
+        let top = y_of(analysis.black_score);
+        let width = (step - 2).max(1) as u32;
+
+        let (color, y, height) = if analysis.black_score >= 0 {
+            (palette.black, top, zero_y - top)
+        } else {
+            (palette.white, zero_y, top - zero_y)
+        };
+        if height > 0 {
+            draw_bar(
+                &mut img,
+                &palette,
+                Rect::at(left, y).of_size(width, height as u32),
+                color,
+            );
+        }
+
+        let center = (left + step / 2, top);
+        match analysis.tag() {
+            Some(Tag::Blunder) => {
+                draw_filled_circle_mut(&mut img, center, MARK_RADIUS, palette.highlight)
+            }
+            Some(Tag::Mistake) => {
+                draw_hollow_circle_mut(&mut img, center, MARK_RADIUS, palette.highlight)
+            }
+            None => (),
+        }
+    }
+
+    // 0 の線と縦軸
+    draw_filled_rect_mut(
+        &mut img,
+        Rect::at(LEFT, zero_y).of_size(plot_width as u32, 1),
+        palette.line,
+    );
+    draw_filled_rect_mut(
+        &mut img,
+        Rect::at(LEFT - 1, TOP).of_size(1, plot_height as u32),
+        palette.line,
+    );
+
+    let label = format!("BLACK {}", range);
+    draw_small_text(&mut img, palette.line, (8, TOP), &label);
+    let label = format!("WHITE {}", range);
+    draw_small_text(
+        &mut img,
+        palette.line,
+        (8, TOP + plot_height - SMALL_HEIGHT),
+        &label,
+    );
+    draw_small_text(&mut img, palette.line, (8, zero_y - SMALL_HEIGHT / 2), "0");
+
+    // 10手ごとの目盛り
+    for number in (10..=analyses.len()).step_by(10) {
+        let x = LEFT + (number as i32 - 1) * step + step / 2;
+        draw_filled_rect_mut(
+            &mut img,
+            Rect::at(x, TOP + plot_height).of_size(1, 6),
+            palette.line,
+        );
+        draw_small_text(
+            &mut img,
+            palette.line,
+            (x - 11, TOP + plot_height + 12),
+            &number.to_string(),
+        );
+    }
+
+    return encode(&img, output());
+}
+
+/// 縦軸の上端 (評価値の絶対値の最大を 8 の倍数に切り上げる)
+fn score_range(analyses: &[MoveAnalysis]) -> i32 {
+    let max = analyses
+        .iter()
+        .map(|analysis| analysis.black_score.abs())
+        .max()
+        .unwrap_or(0);
+
+    return ((max + 7) / 8 * 8).max(MIN_RANGE);
+}
+
+fn draw_bar(
+    img: &mut ImageBuffer<Rgb<u8>, Vec<u8>>,
+    palette: &Palette,
+    rect: Rect,
+    color: Rgb<u8>,
+) {
+    // 盤と見分けにくい色のテーマではふちを付ける
+    if let Some(outline) = palette.outline {
+        draw_filled_rect_mut(img, rect, outline);
+
+        if rect.width() > 2 && rect.height() > 2 {
+            let inner = Rect::at(rect.left() + 1, rect.top() + 1)
+                .of_size(rect.width() - 2, rect.height() - 2);
+            draw_filled_rect_mut(img, inner, color);
+        }
+        return;
+    }
+
+    draw_filled_rect_mut(img, rect, color);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+
+    fn analysis(black_score: i32, loss: i32) -> MoveAnalysis {
+        MoveAnalysis {
+            color: Color::Black,
+            square: (0, 0),
+            best_square: (0, 0),
+            black_score,
+            loss,
+        }
+    }
+
+    #[test]
+    fn range() {
+        assert_eq!(MIN_RANGE, score_range(&[]));
+        assert_eq!(MIN_RANGE, score_range(&[analysis(3, 0), analysis(-10, 0)]));
+        assert_eq!(40, score_range(&[analysis(12, 0), analysis(-33, 0)]));
+    }
+
+    #[test]
+    fn bars_and_marks() {
+        let analyses = [analysis(16, 0), analysis(-16, 20), analysis(0, 0)];
+        let style = BoardStyle::default();
+        let palette = style.theme.palette();
+
+        let png = draw_analysis_graph(&analyses, style);
+        let img = image::load_from_memory(&png).unwrap().to_rgb8();
+        assert_eq!((FULL_WIDTH, HEIGHT), img.dimensions());
+
+        let step = (FULL_WIDTH as i32 - LEFT - RIGHT) / 3;
+        let zero_y = TOP + (HEIGHT as i32 - TOP - BOTTOM) / 2;
+        let pixel = |x: i32, y: i32| *img.get_pixel(x as u32, y as u32);
+
+        // 1手目は黒が有利 (0 の線より上に黒の棒)、2手目は白が有利で悪手
+        assert_eq!(palette.black, pixel(LEFT + step / 2, zero_y - 20));
+        assert_eq!(palette.board, pixel(LEFT + step / 2, zero_y + 20));
+        assert_eq!(palette.white, pixel(LEFT + step + step / 2, zero_y + 20));
+        assert_eq!(
+            palette.highlight,
+            pixel(LEFT + step + step / 2, HEIGHT as i32 - BOTTOM)
+        );
+    }
+}
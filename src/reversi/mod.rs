@@ -1,2 +1,5 @@
+pub mod analysis;
+pub mod gen_image;
+pub mod opening;
 pub mod reversi;
-pub mod stats;
+pub mod search;
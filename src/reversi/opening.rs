@@ -0,0 +1,236 @@
+/*
+リバーシの定石 (名前の付いた序盤の打ち方) です。
+
+定石は黒の1手目を F5 にした手順で持っています。
+初期配置は対角線での反転と180°の回転で変わらないので、1手目が D3・C4・E6 の試合は
+1手目が F5 になるように盤面を回してから定石と比べます。
+定石は 8x8 のふつうの初期配置の手順なので、ほかの大きさ・初期配置の試合では使いません。
+*/
+
+use super::reversi::{PlaceError, Reversi};
+use crate::coordinate::parse_coordinate;
+
+/// 定石の手順を読むときの行のアルファベット (8x8 だけ)
+const ALPHABETS: &str = "ABCDEFGH";
+
+pub struct Opening {
+    /// 1手目を F5 にした手順 ("F5 D6 C3" など)
+    pub moves: &'static str,
+    pub ja: &'static str,
+    pub en: &'static str,
+}
+
+pub const OPENINGS: &[Opening] = &[
+    Opening {
+        moves: "F5 D6",
+        ja: "縦取り",
+        en: "Perpendicular",
+    },
+    Opening {
+        moves: "F5 F6",
+        ja: "斜め取り",
+        en: "Diagonal",
+    },
+    Opening {
+        moves: "F5 F4",
+        ja: "並び取り",
+        en: "Parallel",
+    },
+    Opening {
+        moves: "F5 D6 C3 D3 C4",
+        ja: "虎",
+        en: "Tiger",
+    },
+    Opening {
+        moves: "F5 D6 C3 D3 C4 F4 C5 B3 C2",
+        ja: "ローズ",
+        en: "Rose",
+    },
+    Opening {
+        moves: "F5 D6 C5 F4 E3",
+        ja: "鼠",
+        en: "Rat",
+    },
+    Opening {
+        moves: "F5 D6 C5 F4 E3 F6",
+        ja: "牛",
+        en: "Cow",
+    },
+    Opening {
+        moves: "F5 F6 E6 F4 E3",
+        ja: "兎",
+        en: "Rabbit",
+    },
+    Opening {
+        moves: "F5 F6 E6 F4 C3",
+        ja: "バッファロー",
+        en: "Buffalo",
+    },
+];
+
+impl Opening {
+    pub fn moves(&self) -> Vec<(i32, i32)> {
+        parse_moves(self.moves).unwrap()
+    }
+}
+
+/// "F5 D6 C3" や "f5d6c3" のような手順を (行, 列) の並びにする
+pub fn parse_moves(text: &str) -> Option<Vec<(i32, i32)>> {
+    let chars: Vec<char> = text
+        .chars()
+        .filter(|char| !(char.is_whitespace() || *char == ',' || *char == '-'))
+        .collect();
+
+    if !chars.len().is_multiple_of(2) {
+        return None;
+    }
+
+    chars
+        .chunks(2)
+        .map(|square| {
+            let (row, column) = parse_coordinate(&square.iter().collect::<String>(), ALPHABETS)?;
+            Some((row as i32, column as i32))
+        })
+        .collect()
+}
+
+/// "F5 D6 C3" の形にする
+pub fn notation(moves: &[(i32, i32)]) -> String {
+    moves
+        .iter()
+        .map(|&(row, column)| {
+            // 解析では 8x8 以外の盤の手にも使う
+            let alphabets = super::reversi::ALPHABETS;
+            format!("{}{}", &alphabets[row as usize..=row as usize], column + 1)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 初めから順に打ち、打てない手があればその番号 (0 から) を返す (置けない人はパスする)
+pub fn replay(moves: &[(i32, i32)]) -> Result<Reversi, (usize, PlaceError)> {
+    let mut reversi = Reversi::new();
+
+    for (index, &(row, column)) in moves.iter().enumerate() {
+        reversi
+            .do_place(row, column, reversi.turn)
+            .map_err(|why| (index, why))?;
+
+        reversi.switch_turn();
+        if !reversi.player_can_place(reversi.turn) {
+            reversi.switch_turn();
+        }
+    }
+
+    return Ok(reversi);
+}
+
+/// 1手目が F5 になるように盤面を回す変換 (どれも2回行うと元に戻る)
+fn symmetry(first: Option<(i32, i32)>) -> fn((i32, i32)) -> (i32, i32) {
+    match first {
+        // D3
+        Some((3, 2)) => |(row, column)| (7 - column, 7 - row),
+        // C4
+        Some((2, 3)) => |(row, column)| (7 - row, 7 - column),
+        // E6
+        Some((4, 5)) => |(row, column)| (column, row),
+        _ => |square| square,
+    }
+}
+
+/// moves が始まる定石のうち一番長いもの
+pub fn find(moves: &[(i32, i32)]) -> Option<&'static Opening> {
+    let transform = symmetry(moves.first().copied());
+    let normalized: Vec<(i32, i32)> = moves.iter().map(|&square| transform(square)).collect();
+
+    OPENINGS
+        .iter()
+        .filter(|opening| normalized.starts_with(&opening.moves()))
+        .max_by_key(|opening| opening.moves().len())
+}
+
+/// moves の続きになる定石と、その手順 (moves と同じ向きに回したもの)
+pub fn continuations(moves: &[(i32, i32)]) -> Vec<(&'static Opening, Vec<(i32, i32)>)> {
+    let transform = symmetry(moves.first().copied());
+
+    OPENINGS
+        .iter()
+        .map(|opening| {
+            let opening_moves = opening.moves().into_iter().map(transform).collect();
+            (opening, opening_moves)
+        })
+        .filter(|(_, opening_moves): &(_, Vec<(i32, i32)>)| {
+            opening_moves.len() > moves.len() && opening_moves.starts_with(moves)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn openings_are_legal() {
+        for opening in OPENINGS {
+            assert!(replay(&opening.moves()).is_ok(), "{}", opening.en);
+        }
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(Some(vec![(5, 4), (3, 5)]), parse_moves("f5d6"));
+        assert_eq!(Some(vec![(5, 4), (3, 5)]), parse_moves(" F5 d6 "));
+        assert_eq!(Some(vec![(5, 4), (3, 5)]), parse_moves("F5, D6"));
+        assert_eq!(None, parse_moves("f5d"));
+        assert_eq!(None, parse_moves("f5i6"));
+        assert_eq!("F5 D6", notation(&parse_moves("f5d6").unwrap()));
+    }
+
+    #[test]
+    fn find_with_symmetry() {
+        let tiger = parse_moves("F5 D6 C3 D3 C4").unwrap();
+        assert_eq!("Tiger", find(&tiger).unwrap().en);
+        // 定石から外れても、それまでの定石の名前
+        assert_eq!(
+            "Tiger",
+            find(&parse_moves("F5 D6 C3 D3 C4 B4").unwrap()).unwrap().en
+        );
+
+        // 4通りの1手目のどれから始めても同じ定石になる
+        for first in ["D3", "C4", "E6"] {
+            let transform = symmetry(parse_moves(first).unwrap().first().copied());
+            let moves: Vec<(i32, i32)> = tiger.iter().map(|&square| transform(square)).collect();
+
+            assert_eq!(first, &notation(&moves)[..2]);
+            assert!(replay(&moves).is_ok());
+            assert_eq!("Tiger", find(&moves).unwrap().en);
+        }
+
+        assert!(find(&[]).is_none());
+        assert!(find(&parse_moves("F5").unwrap()).is_none());
+    }
+
+    #[test]
+    fn continue_in_same_orientation() {
+        // D3 C3 は F5 F6 (斜め取り) を回したもの
+        let moves = parse_moves("D3 C3").unwrap();
+        assert_eq!("Diagonal", find(&moves).unwrap().en);
+
+        let names: Vec<&str> = continuations(&moves)
+            .iter()
+            .map(|(opening, _)| opening.en)
+            .collect();
+        assert_eq!(vec!["Rabbit", "Buffalo"], names);
+
+        for (_, opening_moves) in continuations(&moves) {
+            assert!(opening_moves.starts_with(&moves));
+            assert!(replay(&opening_moves).is_ok());
+        }
+    }
+
+    #[test]
+    fn illegal_move() {
+        let moves = parse_moves("F5 D6 A1").unwrap();
+        assert_eq!(Err((2, PlaceError::NoFlip)), replay(&moves).map(|_| ()));
+    }
+}
@@ -1,3 +1,5 @@
+use crate::color::Color;
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Stone {
     NONE,
@@ -5,6 +7,28 @@ pub enum Stone {
     BLACK,
 }
 
+impl From<Color> for Stone {
+    fn from(color: Color) -> Self {
+        match color {
+            Color::Black => Stone::BLACK,
+            Color::White => Stone::WHITE,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlaceError {
+    /// 盤面の外
+    OutOfRange,
+    /// すでに石がある
+    Occupied,
+    /// 相手の石を挟めない
+    NoFlip,
+}
+
+const NUMBER_EMOJIS: [&str; MAX_SIZE] =
+    ["1️⃣", "2️⃣", "3️⃣", "4️⃣", "5️⃣", "6️⃣", "7️⃣", "8️⃣", "9️⃣", "🔟"];
+
 struct SideStones {
     stones: Vec<Stone>,
     move_row: i32,
@@ -15,40 +39,111 @@ impl SideStones {
     fn new(move_row: i32, move_column: i32) -> Self {
         Self {
             stones: vec![],
-            move_row: move_row,
-            move_column: move_column,
+            move_row,
+            move_column,
         }
     }
 }
 
-#[derive(Debug)]
+/// 盤面の配列の大きさ (実際に使うのは左上の size x size だけ)
+pub const MAX_SIZE: usize = 10;
+/// 選べる盤の大きさ
+pub const SIZES: [usize; 3] = [6, 8, 10];
+/// 行のアルファベット (大きさに合わせて先頭から使う)
+pub const ALPHABETS: &str = "ABCDEFGHIJ";
+
+/// 初期配置
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Layout {
+    /// 中央の4マスに同じ色が斜めに並ぶ (ふつうのリバーシ)
+    Cross,
+    /// 中央の4マスに同じ色が横に並ぶ
+    Parallel,
+}
+
+/// 勝ち負けの決め方
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rule {
+    /// 石が多いほうが勝ち
+    Standard,
+    /// 石が少ないほうが勝ち (アンチリバーシ)
+    Anti,
+}
+
+#[derive(Clone, Debug)]
 pub struct Reversi {
-    pub board: [[Stone; 8]; 8],
-    pub turn_stone: Stone,
+    pub board: [[Stone; MAX_SIZE]; MAX_SIZE],
+    /// 盤の一辺のマスの数 (SIZES のどれか)
+    pub size: usize,
+    pub layout: Layout,
+    pub rule: Rule,
+    pub turn: Color,
+    /// 置いた順の (行, 列) (パスは含まない)
+    pub moves: Vec<(i32, i32)>,
+}
+
+impl Default for Reversi {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Reversi {
+    /// 8x8 のふつうのリバーシ
     pub fn new() -> Self {
+        Self::with_layout(8, Layout::Cross)
+    }
+
+    pub fn with_layout(size: usize, layout: Layout) -> Self {
+        assert!(SIZES.contains(&size), "unsupported size: {}", size);
+
         let mut reversi = Self {
-            board: [[Stone::NONE; 8]; 8],
-            turn_stone: Stone::BLACK,
+            board: [[Stone::NONE; MAX_SIZE]; MAX_SIZE],
+            size,
+            layout,
+            rule: Rule::Standard,
+            turn: Color::Black,
+            moves: vec![],
         };
 
-        reversi.board[3][3] = Stone::WHITE;
-        reversi.board[4][4] = Stone::WHITE;
+        let center = size / 2;
+        match layout {
+            Layout::Cross => {
+                reversi.board[center - 1][center - 1] = Stone::WHITE;
+                reversi.board[center][center] = Stone::WHITE;
 
-        reversi.board[3][4] = Stone::BLACK;
-        reversi.board[4][3] = Stone::BLACK;
+                reversi.board[center - 1][center] = Stone::BLACK;
+                reversi.board[center][center - 1] = Stone::BLACK;
+            }
+            Layout::Parallel => {
+                reversi.board[center - 1][center - 1] = Stone::WHITE;
+                reversi.board[center - 1][center] = Stone::WHITE;
+
+                reversi.board[center][center - 1] = Stone::BLACK;
+                reversi.board[center][center] = Stone::BLACK;
+            }
+        }
 
         return reversi;
     }
 
+    pub fn with_rule(mut self, rule: Rule) -> Self {
+        self.rule = rule;
+        return self;
+    }
+
+    /// 8x8 のふつうの初期配置 (定石はこのときだけ使える)
+    pub fn is_standard(&self) -> bool {
+        self.size == 8 && self.layout == Layout::Cross
+    }
+
+    /// 行のアルファベット
+    pub fn alphabets(&self) -> &'static str {
+        &ALPHABETS[..self.size]
+    }
+
     pub fn switch_turn(&mut self) {
-        if self.turn_stone == Stone::BLACK {
-            self.turn_stone = Stone::WHITE
-        } else {
-            self.turn_stone = Stone::BLACK
-        }
+        self.turn = self.turn.rival();
     }
 
     fn sidestones_array(&self, row: i32, column: i32) -> [SideStones; 8] {
@@ -98,28 +193,25 @@ impl Reversi {
         return sidestones_array;
     }
 
-    pub fn can_place_square(&self, row: i32, column: i32, self_color: Stone) -> bool {
-        let rival_color = {
-            if self_color == Stone::BLACK {
-                Stone::WHITE
-            } else {
-                Stone::BLACK
-            }
-        };
+    pub fn can_place_square(&self, row: i32, column: i32, self_color: Color) -> bool {
+        if self.square_state(row, column) != Ok(Stone::NONE) {
+            return false;
+        }
+
+        let self_stone = Stone::from(self_color);
+        let rival_stone = Stone::from(self_color.rival());
 
         let sidestones_array = self.sidestones_array(row, column);
 
         for i in sidestones_array {
             let stone_vec = i.stones;
 
-            if stone_vec.len() == 0 {
-                continue;
-            } else if stone_vec[0] != rival_color {
+            if stone_vec.is_empty() || stone_vec[0] != rival_stone {
                 continue;
             }
 
             let first_self_stone = {
-                match stone_vec.iter().position(|&x| x == self_color) {
+                match stone_vec.iter().position(|&x| x == self_stone) {
                     Some(index) => index,
                     None => continue,
                 }
@@ -135,32 +227,31 @@ impl Reversi {
         return false;
     }
 
-    pub fn do_place(&mut self, row: i32, column: i32, self_color: Stone) -> Result<String, String> {
+    pub fn do_place(&mut self, row: i32, column: i32, self_color: Color) -> Result<(), PlaceError> {
+        match self.square_state(row, column) {
+            Ok(Stone::NONE) => (),
+            Ok(_) => return Err(PlaceError::Occupied),
+            Err(_) => return Err(PlaceError::OutOfRange),
+        }
+
         if !(self.can_place_square(row, column, self_color)) {
-            return Err("CannotPlaced".to_string());
+            return Err(PlaceError::NoFlip);
         }
 
-        let rival_color = {
-            if self_color == Stone::BLACK {
-                Stone::WHITE
-            } else {
-                Stone::BLACK
-            }
-        };
+        let self_stone = Stone::from(self_color);
+        let rival_stone = Stone::from(self_color.rival());
 
         let sidestones_array = self.sidestones_array(row, column);
 
         for i in sidestones_array {
             let stone_vec = i.stones;
 
-            if stone_vec.len() == 0 {
-                continue;
-            } else if stone_vec[0] != rival_color {
+            if stone_vec.is_empty() || stone_vec[0] != rival_stone {
                 continue;
             }
 
             let first_self_stone = {
-                match stone_vec.iter().position(|&x| x == self_color) {
+                match stone_vec.iter().position(|&x| x == self_stone) {
                     Some(index) => index,
                     None => continue,
                 }
@@ -177,25 +268,28 @@ impl Reversi {
                 cur_row += i.move_row;
                 cur_column += i.move_column;
 
-                self.board[cur_row as usize][cur_column as usize] = self_color
+                self.board[cur_row as usize][cur_column as usize] = self_stone
             }
         }
 
-        self.board[row as usize][column as usize] = self_color;
-        return Ok("Placed".to_string());
+        self.board[row as usize][column as usize] = self_stone;
+        self.moves.push((row, column));
+        return Ok(());
     }
 
     fn square_state(&self, row: i32, column: i32) -> Result<Stone, String> {
-        if 0 <= row && row <= 7 && 0 <= column && column <= 7 {
+        let size = self.size as i32;
+
+        if (0..size).contains(&row) && (0..size).contains(&column) {
             Ok(self.board[row as usize][column as usize])
         } else {
             Err("OutofRange".to_string())
         }
     }
 
-    pub fn player_can_place(&self, self_color: Stone) -> bool {
-        for row in 0..8 {
-            for column in 0..8 {
+    pub fn player_can_place(&self, self_color: Color) -> bool {
+        for row in 0..self.size as i32 {
+            for column in 0..self.size as i32 {
                 if self.can_place_square(row, column, self_color) {
                     return true;
                 }
@@ -205,24 +299,65 @@ impl Reversi {
         return false;
     }
 
+    /// self_color が置けるマスの一覧 (行・列の順)
+    pub fn legal_squares(&self, self_color: Color) -> Vec<(i32, i32)> {
+        let mut squares = vec![];
+
+        for row in 0..self.size as i32 {
+            for column in 0..self.size as i32 {
+                if self.can_place_square(row, column, self_color) {
+                    squares.push((row, column));
+                }
+            }
+        }
+
+        return squares;
+    }
+
     pub fn is_game_end(&self) -> bool {
-        if self.player_can_place(Stone::BLACK) || self.player_can_place(Stone::WHITE) {
-            false
-        } else {
-            true
+        !(self.player_can_place(Color::Black) || self.player_can_place(Color::White))
+    }
+
+    pub fn count_stones(&self, stone: Stone) -> usize {
+        self.board[..self.size]
+            .iter()
+            .flat_map(|row| &row[..self.size])
+            .filter(|square| **square == stone)
+            .count()
+    }
+
+    /// 勝った色 (引き分けなら None、決着したかどうかは見ない)
+    pub fn winner(&self) -> Option<Color> {
+        let black_count = self.count_stones(Stone::BLACK);
+        let white_count = self.count_stones(Stone::WHITE);
+
+        let more = match black_count.cmp(&white_count) {
+            std::cmp::Ordering::Greater => Color::Black,
+            std::cmp::Ordering::Less => Color::White,
+            std::cmp::Ordering::Equal => return None,
+        };
+
+        match self.rule {
+            Rule::Standard => Some(more),
+            Rule::Anti => Some(more.rival()),
         }
     }
 
     pub fn print_board(&self) -> String {
-        let mut board = String::from("🟦1️⃣2️⃣3️⃣4️⃣5️⃣6️⃣7️⃣8️⃣\n");
+        let mut board = String::from("🟦");
+        for number in &NUMBER_EMOJIS[..self.size] {
+            board += number;
+        }
+        board += "\n";
 
-        for (row, alphabet) in (0..8).zip("🇦🇧🇨🇩🇪🇫🇬🇭".chars()) {
+        for (row, alphabet) in (0..self.size).zip("🇦🇧🇨🇩🇪🇫🇬🇭🇮🇯".chars())
+        {
             board += &alphabet.to_string();
 
-            for column in 0..8 {
+            for column in 0..self.size {
                 match self.board[row][column] {
                     Stone::NONE => {
-                        if self.can_place_square(row as i32, column as i32, self.turn_stone) {
+                        if self.can_place_square(row as i32, column as i32, self.turn) {
                             board += "▫️"
                         } else {
                             board += "◽"
@@ -240,3 +375,89 @@ impl Reversi {
         return board;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legal_squares() {
+        let reversi = Reversi::new();
+
+        assert_eq!(
+            vec![(2, 3), (3, 2), (4, 5), (5, 4)],
+            reversi.legal_squares(Color::Black)
+        );
+        assert_eq!(
+            vec![(2, 4), (3, 5), (4, 2), (5, 3)],
+            reversi.legal_squares(Color::White)
+        );
+    }
+
+    #[test]
+    fn do_place_errors() {
+        let mut reversi = Reversi::new();
+
+        assert_eq!(
+            Err(PlaceError::OutOfRange),
+            reversi.do_place(8, 0, Color::Black)
+        );
+        assert_eq!(
+            Err(PlaceError::Occupied),
+            reversi.do_place(3, 3, Color::Black)
+        );
+        assert_eq!(
+            Err(PlaceError::NoFlip),
+            reversi.do_place(0, 0, Color::Black)
+        );
+        assert_eq!(Ok(()), reversi.do_place(2, 3, Color::Black));
+    }
+
+    #[test]
+    fn winner() {
+        let mut reversi = Reversi::new();
+        assert_eq!(None, reversi.winner());
+
+        reversi.do_place(2, 3, Color::Black).unwrap();
+        assert_eq!(Some(Color::Black), reversi.winner());
+
+        let reversi = reversi.with_rule(Rule::Anti);
+        assert_eq!(Some(Color::White), reversi.winner());
+    }
+
+    #[test]
+    fn sizes_and_layouts() {
+        let reversi = Reversi::with_layout(6, Layout::Cross);
+        assert_eq!(
+            vec![(1, 2), (2, 1), (3, 4), (4, 3)],
+            reversi.legal_squares(Color::Black)
+        );
+        assert_eq!("ABCDEF", reversi.alphabets());
+        assert!(reversi.print_board().starts_with("🟦1️⃣2️⃣3️⃣4️⃣5️⃣6️⃣\n🇦"));
+        assert_eq!(7, reversi.print_board().lines().count());
+
+        // 6x6 の外には置けない
+        let mut reversi = Reversi::with_layout(6, Layout::Cross);
+        assert_eq!(
+            Err(PlaceError::OutOfRange),
+            reversi.do_place(6, 0, Color::Black)
+        );
+
+        let mut reversi = Reversi::with_layout(10, Layout::Parallel);
+        assert_eq!(Stone::WHITE, reversi.board[4][4]);
+        assert_eq!(Stone::WHITE, reversi.board[4][5]);
+        assert_eq!(Stone::BLACK, reversi.board[5][4]);
+        assert_eq!(Stone::BLACK, reversi.board[5][5]);
+        assert_eq!(
+            vec![(3, 3), (3, 4), (3, 5), (3, 6)],
+            reversi.legal_squares(Color::Black)
+        );
+        assert!(reversi.print_board().ends_with("🇯◽◽◽◽◽◽◽◽◽◽\n"));
+        assert_eq!(
+            Err(PlaceError::NoFlip),
+            reversi.do_place(9, 9, Color::Black)
+        );
+        assert!(!reversi.is_standard());
+        assert!(Reversi::new().is_standard());
+    }
+}
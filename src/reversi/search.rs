@@ -0,0 +1,370 @@
+/*
+リバーシの手を読む探索です (解析で使います)。
+
+`Reversi` の置き方は1マスごとに Vec を作るので、読むときは盤面の配列だけを写して石を返します。
+盤の大きさ (size) は配列の大きさではなく、使うマスの範囲として引数で渡します。
+中盤の評価値はマスの重みと置ける場所の数の差で、終盤 (空きマスが ENDGAME_EMPTIES 以下) は最後まで読んだ石差です。
+アンチリバーシ (石が少ないほうが勝ち) では、マスの重みと石差の向きを逆にします。
+*/
+
+use super::reversi::{Reversi, Rule, Stone, MAX_SIZE};
+use crate::color::Color;
+
+/// 中盤に読む手数
+pub const MIDGAME_DEPTH: u32 = 4;
+/// 空きマスがこれ以下なら最後まで読む
+pub const ENDGAME_EMPTIES: usize = 8;
+
+const DIRECTIONS: [(i32, i32); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+const INFINITY: i32 = i32::MAX / 2;
+
+type Board = [[Stone; MAX_SIZE]; MAX_SIZE];
+
+/// 中盤の評価に使うマスの重み (隅は高く、隅の隣は低い)
+///
+/// 一番近い辺からの距離で決めるので、8x8 では昔からある重みの表と同じになる。
+fn weight(size: i32, row: i32, column: i32) -> i32 {
+    let row = row.min(size - 1 - row);
+    let column = column.min(size - 1 - column);
+    let (near, far) = (row.min(column), row.max(column));
+
+    match (near, far) {
+        (0, 0) => 30,
+        (0, 1) => -12,
+        (1, 1) => -15,
+        (0, 2) | (2, 2) => 0,
+        (1, _) => -3,
+        _ => -1,
+    }
+}
+
+/// color が (row, column) に置いたときに返る石の数 (置けなければ 0)
+fn flip_count(board: &Board, size: i32, row: i32, column: i32, color: Color) -> usize {
+    if board[row as usize][column as usize] != Stone::NONE {
+        return 0;
+    }
+
+    DIRECTIONS
+        .iter()
+        .map(|&direction| line_flips(board, size, row, column, direction, color))
+        .sum()
+}
+
+/// 1方向で挟める相手の石の数
+fn line_flips(
+    board: &Board,
+    size: i32,
+    row: i32,
+    column: i32,
+    (dr, dc): (i32, i32),
+    color: Color,
+) -> usize {
+    let (mut r, mut c) = (row + dr, column + dc);
+    let mut count = 0;
+
+    while (0..size).contains(&r) && (0..size).contains(&c) {
+        match board[r as usize][c as usize] {
+            Stone::NONE => return 0,
+            stone if stone == Stone::from(color) => return count,
+            _ => count += 1,
+        }
+
+        r += dr;
+        c += dc;
+    }
+
+    return 0;
+}
+
+fn place(board: &mut Board, size: i32, row: i32, column: i32, color: Color) {
+    for direction in DIRECTIONS {
+        let flips = line_flips(board, size, row, column, direction, color);
+
+        for k in 1..=flips as i32 {
+            board[(row + direction.0 * k) as usize][(column + direction.1 * k) as usize] =
+                color.into();
+        }
+    }
+
+    board[row as usize][column as usize] = color.into();
+}
+
+fn legal_squares(board: &Board, size: i32, color: Color) -> Vec<(i32, i32)> {
+    let mut squares = vec![];
+
+    for row in 0..size {
+        for column in 0..size {
+            if flip_count(board, size, row, column, color) > 0 {
+                squares.push((row, column));
+            }
+        }
+    }
+
+    return squares;
+}
+
+/// 石が多いほうが有利なら 1、少ないほうが有利なら -1
+fn sign(rule: Rule) -> i32 {
+    match rule {
+        Rule::Standard => 1,
+        Rule::Anti => -1,
+    }
+}
+
+/// 決着したときの color から見た石差 (アンチリバーシでは少ないほうが正)
+fn disc_difference(board: &Board, size: i32, rule: Rule, color: Color) -> i32 {
+    let difference: i32 = squares(board, size)
+        .map(|stone| match stone {
+            Stone::NONE => 0,
+            stone if stone == Stone::from(color) => 1,
+            _ => -1,
+        })
+        .sum();
+
+    return difference * sign(rule);
+}
+
+/// 盤の中のマスの石
+fn squares(board: &Board, size: i32) -> impl Iterator<Item = Stone> + '_ {
+    board[..size as usize]
+        .iter()
+        .flat_map(move |stones| stones[..size as usize].iter().copied())
+}
+
+/// 中盤の評価 (マスの重みと置ける場所の数の差)
+fn evaluate(board: &Board, size: i32, rule: Rule, color: Color) -> i32 {
+    let mut score = 0;
+
+    for row in 0..size {
+        for column in 0..size {
+            match board[row as usize][column as usize] {
+                Stone::NONE => (),
+                stone if stone == Stone::from(color) => score += weight(size, row, column),
+                _ => score -= weight(size, row, column),
+            }
+        }
+    }
+
+    let mobility = legal_squares(board, size, color).len() as i32
+        - legal_squares(board, size, color.rival()).len() as i32;
+
+    return score * sign(rule) + mobility;
+}
+
+fn negamax(
+    board: &Board,
+    size: i32,
+    rule: Rule,
+    color: Color,
+    depth: u32,
+    mut alpha: i32,
+    beta: i32,
+) -> i32 {
+    let squares = legal_squares(board, size, color);
+
+    if squares.is_empty() {
+        if legal_squares(board, size, color.rival()).is_empty() {
+            return disc_difference(board, size, rule, color);
+        }
+
+        return -negamax(board, size, rule, color.rival(), depth, -beta, -alpha);
+    }
+
+    if depth == 0 {
+        return evaluate(board, size, rule, color);
+    }
+
+    let mut best = -INFINITY;
+
+    for (row, column) in squares {
+        let mut next = *board;
+        place(&mut next, size, row, column, color);
+
+        let score = -negamax(&next, size, rule, color.rival(), depth - 1, -beta, -alpha);
+        best = best.max(score);
+        alpha = alpha.max(score);
+
+        if alpha >= beta {
+            break;
+        }
+    }
+
+    return best;
+}
+
+/// 読む手数 (終盤は最後まで)
+fn depth_for(board: &Board, size: i32) -> u32 {
+    let empties = squares(board, size)
+        .filter(|&stone| stone == Stone::NONE)
+        .count();
+
+    if empties <= ENDGAME_EMPTIES {
+        // パスがあっても最後まで届くように多めに読む
+        return empties as u32 * 2;
+    }
+
+    return MIDGAME_DEPTH;
+}
+
+/// 手番の人から見た評価値と最善手 (置ける場所がなければ None)
+pub fn best_move(reversi: &Reversi) -> Option<((i32, i32), i32)> {
+    let board = reversi.board;
+    let size = reversi.size as i32;
+    let color = reversi.turn;
+    let depth = depth_for(&board, size);
+
+    let mut best = None;
+    let mut alpha = -INFINITY;
+
+    for (row, column) in legal_squares(&board, size, color) {
+        let mut next = board;
+        place(&mut next, size, row, column, color);
+
+        let score = -negamax(
+            &next,
+            size,
+            reversi.rule,
+            color.rival(),
+            depth - 1,
+            -INFINITY,
+            -alpha,
+        );
+        if score > alpha {
+            alpha = score;
+            best = Some(((row, column), score));
+        }
+    }
+
+    return best;
+}
+
+/// 手番の人が (row, column) に置いたときの、手番の人から見た評価値 (best_move と同じ深さで読む)
+pub fn move_score(reversi: &Reversi, row: i32, column: i32) -> i32 {
+    let mut board = reversi.board;
+    let size = reversi.size as i32;
+    let color = reversi.turn;
+    let depth = depth_for(&board, size);
+
+    place(&mut board, size, row, column, color);
+
+    return -negamax(
+        &board,
+        size,
+        reversi.rule,
+        color.rival(),
+        depth - 1,
+        -INFINITY,
+        INFINITY,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reversi::reversi::Layout;
+
+    #[test]
+    fn same_moves_as_reversi() {
+        for (size, layout) in [
+            (8, Layout::Cross),
+            (6, Layout::Cross),
+            (10, Layout::Parallel),
+        ] {
+            let mut reversi = Reversi::with_layout(size, layout);
+            let size = size as i32;
+
+            // 黒と白が一番左上のマスに置き続け、置き方が Reversi と同じになることを確かめる
+            while !reversi.is_game_end() {
+                let color = reversi.turn;
+                let expected = reversi.legal_squares(color);
+                assert_eq!(expected, legal_squares(&reversi.board, size, color));
+
+                if let Some(&(row, column)) = expected.first() {
+                    let mut board = reversi.board;
+                    place(&mut board, size, row, column, color);
+                    reversi.do_place(row, column, color).unwrap();
+                    assert_eq!(reversi.board, board);
+                }
+
+                reversi.switch_turn();
+            }
+        }
+    }
+
+    #[test]
+    fn weights() {
+        #[rustfmt::skip]
+        let expected = [
+            [ 30, -12,  0, -1, -1,  0, -12,  30],
+            [-12, -15, -3, -3, -3, -3, -15, -12],
+            [  0,  -3,  0, -1, -1,  0,  -3,   0],
+            [ -1,  -3, -1, -1, -1, -1,  -3,  -1],
+            [ -1,  -3, -1, -1, -1, -1,  -3,  -1],
+            [  0,  -3,  0, -1, -1,  0,  -3,   0],
+            [-12, -15, -3, -3, -3, -3, -15, -12],
+            [ 30, -12,  0, -1, -1,  0, -12,  30],
+        ];
+
+        for (row, weights) in expected.iter().enumerate() {
+            for (column, &expected) in weights.iter().enumerate() {
+                assert_eq!(expected, weight(8, row as i32, column as i32));
+            }
+        }
+        assert_eq!(30, weight(6, 5, 0));
+        assert_eq!(-15, weight(10, 8, 8));
+    }
+
+    #[test]
+    fn endgame_is_exact() {
+        // 白が A1 を取ると A1-A8 の列がすべて白になる
+        let mut reversi = Reversi::new();
+        for row in 0..8 {
+            reversi.board[row][..8].fill(Stone::BLACK);
+        }
+        reversi.board[0][0] = Stone::NONE;
+        reversi.board[0][7] = Stone::WHITE;
+        reversi.turn = Color::White;
+
+        // 白は 1 + 6 + 1 = 8 個、黒は 56 個
+        assert_eq!(Some(((0, 0), 8 - 56)), best_move(&reversi));
+        assert_eq!(8 - 56, move_score(&reversi, 0, 0));
+
+        // アンチリバーシでは同じ手が白の大きな得になる
+        let reversi = reversi.with_rule(Rule::Anti);
+        assert_eq!(Some(((0, 0), 56 - 8)), best_move(&reversi));
+    }
+
+    #[test]
+    fn prefers_corner() {
+        // 黒は A1 (隅) か C1 に置ける
+        let mut reversi = Reversi::new();
+        reversi.board = [[Stone::NONE; MAX_SIZE]; MAX_SIZE];
+        reversi.board[1][1] = Stone::WHITE;
+        reversi.board[2][2] = Stone::BLACK;
+        reversi.board[0][1] = Stone::WHITE;
+        reversi.board[0][2] = Stone::BLACK;
+        for row in 3..6 {
+            for column in 3..6 {
+                reversi.board[row][column] = Stone::BLACK;
+            }
+        }
+        reversi.board[4][4] = Stone::WHITE;
+        reversi.turn = Color::Black;
+
+        assert_eq!(vec![(0, 0), (2, 0)], reversi.legal_squares(Color::Black));
+
+        let (square, score) = best_move(&reversi).unwrap();
+        assert_eq!((0, 0), square);
+        assert_eq!(score, move_score(&reversi, 0, 0));
+        assert!(score > move_score(&reversi, 2, 0));
+    }
+}
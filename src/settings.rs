@@ -0,0 +1,169 @@
+/*
+利用者ごとの設定です。`/settings` で変え、`storage_path` の settings.json に保存します。
+*/
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use serde::{Deserialize, Serialize};
+use serenity::all::UserId;
+
+use crate::render::theme::{BoardStyle, Theme};
+
+const FILE_NAME: &str = "settings.json";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UserSettings {
+    /// 盤面の画像の色合い
+    pub theme: Theme,
+    /// 量子の石に確率を描く
+    pub stone_labels: bool,
+}
+
+impl UserSettings {
+    pub fn board_style(&self) -> BoardStyle {
+        BoardStyle {
+            theme: self.theme,
+            stone_labels: self.stone_labels,
+        }
+    }
+}
+
+pub struct Settings {
+    /// 保存先 (None なら保存しない)
+    path: Option<PathBuf>,
+    users: HashMap<u64, UserSettings>,
+    /// 変えた回数
+    version: u64,
+    /// ファイルに書いた内容の version (写しと共有する)
+    saved: Arc<Mutex<u64>>,
+}
+
+/// ファイルに保存する内容 (表のロックの中で写し、ロックを離してから書く)
+pub struct Snapshot {
+    path: PathBuf,
+    users: HashMap<u64, UserSettings>,
+    version: u64,
+    saved: Arc<Mutex<u64>>,
+}
+
+impl Settings {
+    /// `storage_path` の settings.json を読む (なければ空、読めなければログを出して空にする)
+    pub fn load(storage_path: &Path) -> Settings {
+        let path = storage_path.join(FILE_NAME);
+
+        let users = match fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text).unwrap_or_else(|why| {
+                println!("Settings: '{}' is invalid: {why}", path.display());
+                HashMap::new()
+            }),
+            Err(_) => HashMap::new(),
+        };
+
+        Settings {
+            path: Some(path),
+            users,
+            version: 0,
+            saved: Arc::default(),
+        }
+    }
+
+    /// 保存しない設定 (テスト用)
+    #[cfg(test)]
+    pub fn in_memory() -> Settings {
+        Settings {
+            path: None,
+            users: HashMap::new(),
+            version: 0,
+            saved: Arc::default(),
+        }
+    }
+
+    pub fn get(&self, user_id: UserId) -> UserSettings {
+        self.users.get(&user_id.get()).copied().unwrap_or_default()
+    }
+
+    /// 設定を変える (ファイルには `snapshot` で写してから保存する)
+    pub fn set(&mut self, user_id: UserId, settings: UserSettings) {
+        self.users.insert(user_id.get(), settings);
+        self.version += 1;
+    }
+
+    /// 今の設定の写し (保存しない設定なら None)
+    pub fn snapshot(&self) -> Option<Snapshot> {
+        Some(Snapshot {
+            path: self.path.clone()?,
+            users: self.users.clone(),
+            version: self.version,
+            saved: self.saved.clone(),
+        })
+    }
+}
+
+impl Snapshot {
+    /// ファイルに書く (重いので、非同期の処理からは spawn_blocking で呼ぶ)
+    ///
+    /// あとで写した内容を先に書いていれば、古い内容で上書きしない。
+    pub fn save(self) -> std::io::Result<()> {
+        let mut saved = self.saved.lock().unwrap_or_else(PoisonError::into_inner);
+        if self.version <= *saved {
+            return Ok(());
+        }
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_string_pretty(&self.users).map_err(std::io::Error::other)?;
+        fs::write(&self.path, json)?;
+
+        *saved = self.version;
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn save_and_load() {
+        let dir = std::env::temp_dir().join(format!("reversibot_settings_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let alice = UserId::new(2);
+        let bob = UserId::new(3);
+
+        let mut settings = Settings::load(&dir);
+        assert_eq!(Theme::Wood, settings.get(alice).theme);
+
+        settings.set(
+            alice,
+            UserSettings {
+                theme: Theme::Dark,
+                stone_labels: false,
+            },
+        );
+        let old = settings.snapshot().unwrap();
+
+        settings.set(
+            alice,
+            UserSettings {
+                theme: Theme::Colorblind,
+                stone_labels: true,
+            },
+        );
+        settings.snapshot().unwrap().save().unwrap();
+        // 先に写した古い内容は、あとから書こうとしても上書きしない
+        old.save().unwrap();
+
+        let settings = Settings::load(&dir);
+        assert_eq!(Theme::Colorblind, settings.get(alice).theme);
+        assert!(settings.get(alice).stone_labels);
+        assert_eq!(Theme::Wood, settings.get(bob).theme);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}